@@ -41,5 +41,18 @@ pub fn updated_groups_to_js(group_changes: Vec<::michelangelo::GroupChange>) ->
     )
 }
 
+/// As given in the URL the UI was opened with, presented to the server with every change
+pub fn editor_token() -> ::access::EditorToken {
+    use stdweb::unstable::TryInto;
+
+    let maybe_token: Option<String> = js! {
+        return window.cbAccess.editorToken;
+    }
+    .try_into()
+    .unwrap();
+
+    ::compact::COption(maybe_token.map(::compact::CString::from))
+}
+
 mod kay_auto;
 pub use self::kay_auto::*;
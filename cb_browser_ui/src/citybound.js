@@ -38,7 +38,13 @@ import * as Transport from './transport_browser/Transport';
 import * as LandUse from './land_use_browser/LandUse';
 import * as Households from './households_browser/Households';
 import * as Vegetation from './vegetation_browser/Vegetation';
+import * as Construction from './construction_browser/Construction';
+import * as Terrain from './terrain_browser/Terrain';
+import * as Water from './water_browser/Water';
 import * as Time from './time_browser/Time';
+import * as Treasury from './treasury_browser/Treasury';
+import * as Statistics from './statistics_browser/Statistics';
+import * as Heatmaps from './heatmaps_browser/Heatmaps';
 import * as Debug from './debug/Debug';
 import * as Settings from './settings';
 import * as Menu from './menu';
@@ -53,6 +59,8 @@ require('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(cbRustBro
     const settingSpecs = {
         camera: Camera.settingSpec,
         debug: Debug.settingsSpec,
+        statistics: Statistics.settingsSpec,
+        heatmaps: Heatmaps.settingsSpec,
         planning: Planning.settingsSpec,
         rendering: {
             retinaFactor: { default: 2, description: "Oversampling/Retina Factor", min: 0.5, max: 4.0, step: 0.1 }
@@ -69,7 +77,12 @@ require('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(cbRustBro
                 landUse: LandUse.initialState,
                 households: Households.initialState,
                 vegetation: Vegetation.initialState,
+                construction: Construction.initialState,
+                terrain: Terrain.initialState,
+                water: Water.initialState,
                 debug: Debug.initialState,
+                statistics: Statistics.initialState,
+                heatmaps: Heatmaps.initialState,
                 uiMode: null,
                 system: {
                     networkingTurns: ""
@@ -78,6 +91,7 @@ require('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(cbRustBro
                     enabled: true
                 },
                 time: Time.initialState,
+                treasury: Treasury.initialState,
                 camera: Camera.initialState,
 
                 menu: Menu.initalState,
@@ -93,6 +107,8 @@ require('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(cbRustBro
         componentDidMount() {
             Camera.bindInputs(this.state, this.boundSetState);
             Debug.bindInputs(this.state, this.boundSetState);
+            Statistics.bindInputs(this.state, this.boundSetState);
+            Heatmaps.bindInputs(this.state, this.boundSetState);
             Planning.bindInputs(this.state, this.boundSetState);
         }
 
@@ -124,7 +140,12 @@ require('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(cbRustBro
                                 </div>
                                 <div key="ui2d" className="ui2d">
                                     <Time.Windows state={this.state} setState={this.boundSetState} />
+                                    <Treasury.Windows state={this.state} setState={this.boundSetState} />
+                                    <Construction.Windows state={this.state} setState={this.boundSetState} />
+                                    <Planning.Windows state={this.state} setState={this.boundSetState} />
                                     <Debug.Windows state={this.state} setState={this.boundSetState} />
+                                    <Statistics.Windows state={this.state} setState={this.boundSetState} />
+                                    <Heatmaps.Windows state={this.state} setState={this.boundSetState} />
                                     <Households.Windows state={this.state} setState={this.boundSetState} project3dTo2d={project3dTo2d} />
                                     <Menu.Windows state={this.state} setState={this.boundSetState} settingSpecs={settingSpecs} />
                                 </div>
@@ -133,11 +154,17 @@ require('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(cbRustBro
                                     <Utils.RenderContext.Provider value={layers}>
 
                                         <Households.Shapes state={this.state} setState={this.boundSetState} />
+                                        <Debug.Shapes state={this.state} />
+
+                                        <Terrain.Layers state={this.state} />
+                                        <Water.Layers state={this.state} />
+                                        <Heatmaps.Layers state={this.state} />
 
                                         <Planning.ShapesAndLayers state={this.state} setState={this.boundSetState} />
 
                                         <LandUse.Layers state={this.state} />
                                         <Vegetation.Layers state={this.state} />
+                                        <Construction.Layers state={this.state} />
                                         <Transport.Layers state={this.state} />
 
                                     </Utils.RenderContext.Provider>
@@ -50,6 +50,9 @@ const grass = [0.79, 0.88, 0.67];
 
 export default {
     grass,
+    terrainLow: mix([0.6, 0.82, 0.55], grass, 0.5),
+    terrainHigh: mix([0.86, 0.84, 0.7], grass, 0.5),
+    water: [0.35, 0.6, 0.85],
     trunks: [0.4, 0.3, 0.2],
     canopies: [0.3, 0.5, 0.2],
     asphalt: [0.6, 0.6, 0.6],
@@ -71,9 +74,18 @@ export default {
     destructedAsphalt: [1.0, 0.0, 0.0],
     buildingOutlines: [0.0, 0.0, 0.0],
 
+    constructionSite: [1.0, 0.6, 0.0],
+    demolitionSite: [0.8, 0.2, 0.1],
+    idleConstructionSite: [0.5, 0.5, 0.5],
+
+    heatmapLow: toLinFloat([255, 240, 160]),
+    heatmapHigh: toLinFloat([200, 20, 20]),
+
     controlPointMaster: [0.3, 0.3, 1.0],
     controlPointCurrentProject: [0.0, 0.061, 1.0],//[0, 72, 255]
     controlPointHover: [0.3, 0.361, 1.0],
+    controlPointConflict: [1.0, 0.1, 0.0],
+    controlPointRejected: [1.0, 0.6, 0.0],
 
     Residential: mix(toLinFloat([234, 203, 82]), grass, 0.9),
     Commercial: mix(toLinFloat([213, 94, 0]), grass, 0.9),
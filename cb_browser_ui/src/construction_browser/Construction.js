@@ -0,0 +1,69 @@
+import { RenderLayer } from "../browser_utils/Utils";
+import colors from '../colors';
+import renderOrder from '../renderOrder';
+import React from 'react';
+
+export const initialState = {
+    sites: []
+}
+
+// a simple post, scaled by construction progress
+const siteMarkerMesh = {
+    vertices: new Float32Array([
+        -1.0, -1.0, 0.0,
+        1.0, -1.0, 0.0,
+        1.0, 1.0, 0.0,
+        -1.0, 1.0, 0.0,
+        -1.0, -1.0, 6.0,
+        1.0, -1.0, 6.0,
+        1.0, 1.0, 6.0,
+        -1.0, 1.0, 6.0,
+    ]),
+    indices: new Uint16Array([
+        0, 1, 5, 0, 5, 4,
+        1, 2, 6, 1, 6, 5,
+        2, 3, 7, 2, 7, 6,
+        3, 0, 4, 3, 4, 7,
+        4, 5, 6, 4, 6, 7
+    ])
+}
+
+function siteColor(site) {
+    if (!site.is_worked_on) {
+        return colors.idleConstructionSite;
+    } else if (site.kind === "Destruct") {
+        return colors.demolitionSite;
+    } else {
+        return colors.constructionSite;
+    }
+}
+
+export function Layers(props) {
+    const { state } = props;
+
+    const instances = new Float32Array(state.construction.sites.reduce((allInstances, site) =>
+        allInstances.concat([
+            site.position[0], site.position[1], 0.0,
+            0.3 + 0.7 * site.progress, 0.0,
+            ...siteColor(site)
+        ]), []));
+
+    return <RenderLayer
+        key="constructionSites"
+        decal={false}
+        renderOrder={renderOrder.constructionSites}
+        batches={[{
+            mesh: siteMarkerMesh,
+            instances
+        }]} />;
+}
+
+export function Windows(props) {
+    const { state } = props;
+    const nSites = state.construction.sites.length;
+    const nWorkedOn = state.construction.sites.filter(site => site.is_worked_on).length;
+
+    return nSites > 0 && <div key="constructionStatus" className="construction-status">
+        {nSites} construction sites ({nWorkedOn} being worked on)
+    </div>;
+}
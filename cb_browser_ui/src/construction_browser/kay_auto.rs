@@ -0,0 +1,73 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for BrowserConstructionUI {
+    type ID = BrowserConstructionUIID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct BrowserConstructionUIID {
+    _raw_id: RawID
+}
+
+impl TypedID for BrowserConstructionUIID {
+    type Target = BrowserConstructionUI;
+
+    fn from_raw(id: RawID) -> Self {
+        BrowserConstructionUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BrowserConstructionUIID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = BrowserConstructionUIID::from_raw(world.allocate_instance_id::<BrowserConstructionUI>());
+        let swarm = world.local_broadcast::<BrowserConstructionUI>();
+        world.send(swarm, MSG_BrowserConstructionUI_spawn(id, ));
+        id
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserConstructionUI_spawn(pub BrowserConstructionUIID, );
+
+impl Into<FrameListenerID> for BrowserConstructionUIID {
+    fn into(self) -> FrameListenerID {
+        FrameListenerID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<ConstructionUIID> for BrowserConstructionUIID {
+    fn into(self) -> ConstructionUIID {
+        ConstructionUIID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    FrameListenerID::register_implementor::<BrowserConstructionUI>(system);
+    ConstructionUIID::register_implementor::<BrowserConstructionUI>(system);
+    system.add_spawner::<BrowserConstructionUI, _, _>(
+        |&MSG_BrowserConstructionUI_spawn(id, ), world| {
+            BrowserConstructionUI::spawn(id, world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,46 @@
+use kay::{World, ActorSystem, Actor, TypedID};
+use compact::CVec;
+use stdweb::serde::Serde;
+use browser_utils::{FrameListener, FrameListenerID};
+use construction::{ConstructionID, ConstructionUI, ConstructionUIID, ConstructionSiteInfo};
+
+#[derive(Compact, Clone)]
+pub struct BrowserConstructionUI {
+    id: BrowserConstructionUIID,
+}
+
+impl BrowserConstructionUI {
+    pub fn spawn(id: BrowserConstructionUIID, _: &mut World) -> BrowserConstructionUI {
+        BrowserConstructionUI { id }
+    }
+}
+
+impl FrameListener for BrowserConstructionUI {
+    fn on_frame(&mut self, world: &mut World) {
+        ConstructionID::global_first(world).get_construction_sites(self.id_as(), world);
+    }
+}
+
+impl ConstructionUI for BrowserConstructionUI {
+    fn on_construction_sites(&mut self, sites: &CVec<ConstructionSiteInfo>, _world: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                construction: {
+                    sites: {"$set": @{Serde(sites)}}
+                }
+            }));
+        }
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<BrowserConstructionUI>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    BrowserConstructionUIID::spawn(world);
+}
@@ -1,6 +1,6 @@
 import React from 'react';
-import { Button, InputNumber } from 'antd';
-import { fmtId } from '../browser_utils/Utils';
+import { Button, InputNumber, Input, Select } from 'antd';
+import { fmtId, Interactive3DShape } from '../browser_utils/Utils';
 
 export const initialState = {
     show: false,
@@ -12,10 +12,23 @@ export const initialState = {
         triesPerLane: 50
     },
     logLastEntry: 0,
-    logTextStart: 0,
-    logFirstEntry: 0,
     logEntries: [],
-    logText: []
+    logQuerySettings: {
+        minLevel: "Warning",
+        topic: "",
+        withinMinutes: 60
+    },
+    logQueryResult: [],
+    inspected: null,
+    picking: false
+}
+
+// actors implementing Inspectable in the simulation
+const INSPECTABLE_TYPES = ["Lane", "SwitchLane", "Trip", "Market", "Construction", "VacantLot", "Plant", "Building"];
+
+function isInspectable(id) {
+    const typeSplit = window.cbTypeIdMapping[parseInt(id.split("_")[0], 16)].split("::");
+    return INSPECTABLE_TYPES.includes(typeSplit[typeSplit.length - 1]);
 }
 
 export const settingsSpec = {
@@ -99,6 +112,10 @@ export function Windows(props) {
                             oldState => update(oldState, { rendering: { enabled: { $apply: e => !e } } })
                         )}>{state.rendering.enabled ? "Disable rendering" : "Enable rendering"}</Button>
                 </div>
+                {!window.cbAccess.spectator && <div key="shutdown">
+                    <Button
+                        onClick={() => cbRustBrowser.shutdown_server()}>Save &amp; shut down server</Button>
+                </div>}
             </details>
             <details>
                 <summary>Networking</summary>
@@ -114,18 +131,125 @@ export function Windows(props) {
             </details>
             <details>
                 <summary>Simulation Log</summary>
-                <div className="scrollableLog">{state.debug.logEntries.map((entry, i) => {
-                    let ts = state.debug.logTextStart;
-                    let topic = state.debug.logText.slice(entry.topic_start - ts, entry.message_start - ts);
-                    let message = state.debug.logText.slice(entry.message_start - ts, entry.message_start + entry.message_len - ts);
-                    return <div key={i} className={entry.level}>{state.debug.logFirstEntry + i} [{topic}] {fmtId(entry.from)}: {message}</div>
-                }
+                <div className="scrollableLog">{state.debug.logEntries.map(entry =>
+                    <LogEntry key={entry.index} entry={entry} />
+                )}</div>
+            </details>
+            <details>
+                <summary>Query Simulation Log</summary>
+                <div key="logQuery">
+                    At least
+                    <Select
+                        value={state.debug.logQuerySettings.minLevel}
+                        onChange={(minLevel) => setState(oldState => update(oldState, {
+                            debug: { logQuerySettings: { minLevel: { $set: minLevel } } }
+                        }))}>
+                        {["Debug", "Info", "Warning", "Error"].map(level =>
+                            <Select.Option key={level} value={level}>{level}</Select.Option>
+                        )}
+                    </Select>
+                    Topic
+                    <Input
+                        value={state.debug.logQuerySettings.topic}
+                        placeholder="(any)"
+                        onChange={(e) => {
+                            const topic = e.target.value;
+                            setState(oldState => update(oldState, {
+                                debug: { logQuerySettings: { topic: { $set: topic } } }
+                            }))
+                        }} />
+                    Within minutes
+                    <InputNumber
+                        value={state.debug.logQuerySettings.withinMinutes}
+                        onChange={(withinMinutes) => setState(oldState => update(oldState, {
+                            debug: { logQuerySettings: { withinMinutes: { $set: withinMinutes } } }
+                        }))}
+                        min={0} />
+                    <Button
+                        onClick={() => cbRustBrowser.query_log(
+                            state.debug.logQuerySettings.minLevel,
+                            state.debug.logQuerySettings.topic || null,
+                            state.debug.logQuerySettings.withinMinutes || null
+                        )}>Query</Button>
+                </div>
+                <div className="scrollableLog">{state.debug.logQueryResult.map(entry =>
+                    <LogEntry key={entry.index} entry={entry} />
                 )}</div>
             </details>
+            <details>
+                <summary>Inspector</summary>
+                <div key="pick">
+                    <Button
+                        onClick={() => setState(oldState => update(oldState, {
+                            debug: { picking: { $apply: p => !p } }
+                        }))}>{state.debug.picking ? "Stop picking" : "Pick lanes, buildings or plants to inspect"}</Button>
+                    {state.debug.inspected && <Button
+                        onClick={() => cbRustBrowser.inspect_actor(state.debug.inspected.id)}>Refresh</Button>}
+                </div>
+                {state.debug.inspected
+                    ? <div key="inspected">
+                        <h3>{fmtId(state.debug.inspected.id)}</h3>
+                        <table>
+                            <tbody>{state.debug.inspected.summary.fields.map(field =>
+                                <tr key={field.name}><td>{field.name}</td><td>{field.value}</td></tr>
+                            )}</tbody>
+                        </table>
+                    </div>
+                    : <div>(click on a lane, building or plant or on the sender of a log entry)</div>
+                }
+            </details>
         </div>,
         connectionIssue && <div className="window connection">{connectionIssue}</div>];
 }
 
+function LogEntry(props) {
+    const { entry } = props;
+    const from = entry.from && isInspectable(entry.from)
+        ? <a onClick={() => cbRustBrowser.inspect_actor(entry.from)}>{fmtId(entry.from)}</a>
+        : fmtId(entry.from);
+    return <div className={entry.level}>{entry.index} @{entry.instant} [{entry.topic}] {from}: {entry.message}</div>
+}
+
+function pickable(id, shape, zIndex) {
+    return <Interactive3DShape
+        key={id}
+        id={id + "pick"}
+        shape={shape}
+        zIndex={zIndex}
+        cursorHover="pointer"
+        cursorActive="pointer"
+        onEvent={e => {
+            if (e.drag && e.drag.end) {
+                cbRustBrowser.inspect_actor(id);
+            }
+        }} />
+}
+
+export function Shapes(props) {
+    const { state } = props;
+    if (!state.debug.show || !state.debug.picking) {
+        return null;
+    } else {
+        return [
+            ...Object.keys(state.transport.lanePaths).map(laneId => pickable(laneId, {
+                type: "path",
+                path: state.transport.lanePaths[laneId],
+                maxDistanceLeft: 1.5,
+                maxDistanceRight: 1.5,
+            }, 3)),
+            ...Object.keys(state.vegetation.plantPositions).map(plantId => pickable(plantId, {
+                type: "circle",
+                center: [...state.vegetation.plantPositions[plantId], 0],
+                radius: 1.5,
+            }, 4)),
+            ...Object.keys(state.households.buildingShapes).map(buildingId => pickable(buildingId, {
+                type: "polygon",
+                area: state.households.buildingShapes[buildingId],
+            }, 2)),
+        ];
+    }
+}
+
 export function bindInputs(state, setState) {
     const inputActions = {
         "toggleDebugView": () => setState(oldState => update(oldState, {
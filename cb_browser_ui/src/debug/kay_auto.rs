@@ -53,11 +53,18 @@ impl Into<LogRecipientID> for LogUIID {
     }
 }
 
+impl Into<InspectorID> for LogUIID {
+    fn into(self) -> InspectorID {
+        InspectorID::from_raw(self.as_raw())
+    }
+}
+
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
     LogRecipientID::register_implementor::<LogUI>(system);
+    InspectorID::register_implementor::<LogUI>(system);
     system.add_spawner::<LogUI, _, _>(
         |&MSG_LogUI_spawn(id, ), world| {
             LogUI::spawn(id, world)
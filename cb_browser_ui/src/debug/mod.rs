@@ -3,6 +3,7 @@ use stdweb::serde::Serde;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 use stdweb::js_export;
 use SYSTEM;
+use browser_utils::editor_token;
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn plan_grid(project_id: Serde<::planning::ProjectID>, n: Serde<isize>, spacing: Serde<f32>) {
@@ -24,9 +25,10 @@ pub fn plan_grid(project_id: Serde<::planning::ProjectID>, n: Serde<isize>, spac
             id,
             GestureIntent::Road(RoadIntent::new(3, 3)),
             p1,
+            editor_token(),
             world,
         );
-        plan_manager.add_control_point(project_id.0, id, p2, true, true, world);
+        plan_manager.add_control_point(project_id.0, id, p2, true, true, editor_token(), world);
     }
 
     for y in -n.0 / 2..n.0 / 2 {
@@ -38,9 +40,10 @@ pub fn plan_grid(project_id: Serde<::planning::ProjectID>, n: Serde<isize>, spac
             id,
             GestureIntent::Road(RoadIntent::new(3, 3)),
             p1,
+            editor_token(),
             world,
         );
-        plan_manager.add_control_point(project_id.0, id, p2, true, true, world);
+        plan_manager.add_control_point(project_id.0, id, p2, true, true, editor_token(), world);
     }
 }
 
@@ -53,9 +56,18 @@ pub fn spawn_cars(tries_per_lane: usize) {
     }
 }
 
-use kay::{World, ActorSystem};
-use compact::{CVec, CString};
-use log::{LogID, LogRecipient, LogRecipientID, Entry};
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn shutdown_server() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::api::ApiBridgeID::global_first(world).request_shutdown(editor_token(), world);
+}
+
+use kay::{World, ActorSystem, RawID};
+use compact::{CVec, CString, COption};
+use log::{LogID, LogLevel, LogQuery, LogRecipient, LogRecipientID, Entry};
+use inspection::{Inspector, InspectorID, InspectableID, StateSummary};
+use time::Duration;
 
 #[derive(Compact, Clone)]
 pub struct LogUI {
@@ -69,40 +81,49 @@ impl LogUI {
 }
 
 impl LogRecipient for LogUI {
-    fn receive_newest_logs(
-        &mut self,
-        entries: &CVec<Entry>,
-        text: &CString,
-        effective_last: u32,
-        effective_text_start: u32,
-        _: &mut World,
-    ) {
+    fn receive_newest_logs(&mut self, entries: &CVec<Entry>, effective_last: u32, _: &mut World) {
         js! {
             const entries = @{Serde(entries)};
-            const text = @{Serde(text)};
-            if (window.cbReactApp.state.debug.logLastEntry == @{effective_last as u32}) {
+            if (window.cbReactApp.state.debug.logLastEntry == @{effective_last}) {
                 // append
                 window.cbReactApp.boundSetState(oldState => update(oldState, {
                     debug: {
                         logLastEntry: {"$apply": n => n + @{entries.len() as u32}},
-                        logEntries: {"$push": entries},
-                        logText: {"$apply": t => t + text}
+                        logEntries: {"$push": entries}
                     }
                 }));
             } else {
-                // replace, keep offset
+                // replace
                 window.cbReactApp.boundSetState(oldState => update(oldState, {
                     debug: {
                         logLastEntry: {"$set": @{effective_last + entries.len() as u32}},
-                        logTextStart: {"$set": @{effective_text_start}},
-                        logFirstEntry: {"$set": @{effective_last}},
-                        logEntries: {"$set": entries},
-                        logText: {"$set": text}
+                        logEntries: {"$set": entries}
                     }
                 }));
             }
         };
     }
+
+    fn receive_log_query_result(&mut self, _query_id: u32, entries: &CVec<Entry>, _: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                debug: { logQueryResult: {"$set": @{Serde(entries)}} }
+            }));
+        };
+    }
+}
+
+impl Inspector for LogUI {
+    fn on_inspected(&mut self, id: RawID, summary: &StateSummary, _: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                debug: { inspected: {"$set": {
+                    id: @{Serde(id)},
+                    summary: @{Serde(summary)}
+                }}}
+            }));
+        };
+    }
 }
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
@@ -127,6 +148,36 @@ pub fn get_newest_log_messages() {
     );
 }
 
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn query_log(
+    min_level: Serde<LogLevel>,
+    topic: Serde<Option<String>>,
+    within_minutes: Serde<Option<usize>>,
+) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+
+    let query = LogQuery {
+        min_level: min_level.0,
+        topic: COption(topic.0.map(CString::from)),
+        from: None,
+        within: within_minutes.0.map(Duration::from_minutes),
+        after: 0,
+        max: 500,
+    };
+
+    LogID::global_first(world).query(query, 0, LogUIID::local_first(world).into(), world);
+}
+
+/// Only works for actors that implement `Inspectable`, the UI checks this by actor type
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn inspect_actor(id: Serde<RawID>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+
+    InspectableID::from_raw(id.0).inspect(LogUIID::local_first(world).into(), world);
+}
+
 mod kay_auto;
 pub use self::kay_auto::*;
 
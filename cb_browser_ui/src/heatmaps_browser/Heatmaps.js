@@ -0,0 +1,125 @@
+import React from 'react';
+import { Select } from 'antd';
+import update from 'immutability-helper';
+import { RenderLayer } from "../browser_utils/Utils";
+import colors from '../colors';
+import renderOrder from '../renderOrder';
+
+export const initialState = {
+    show: false,
+    shownLayer: null,
+    cells: {},
+    cellMesh: null,
+    cellSize: 100
+}
+
+export const settingsSpec = {
+    toggleHeatmapsWindowKey: { default: { key: ';' }, description: "Toggle Heatmaps Window" }
+}
+
+// keys are the HeatmapLayer variants of the simulation
+const LAYERS = {
+    CarDensity: { description: "Traffic density (cars per km of lane)" },
+    AverageSpeed: { description: "Average car speed (m/s, red is slow)", lowIsWorse: true },
+    FailedTrips: { description: "Origins of failed trips" },
+    Graveness: { description: "Unmet needs of households" },
+    Vacancy: { description: "Vacant units and lots (share)", max: 1.0 }
+};
+
+function cellColor(layer, value, max) {
+    const normalized = Math.min(1.0, value / max);
+    const alpha = layer.lowIsWorse ? 1.0 - normalized : normalized;
+    return [0, 1, 2].map(i => colors.heatmapHigh[i] * alpha + colors.heatmapLow[i] * (1 - alpha));
+}
+
+// the simulation pushes a new heatmap of the subscribed layer after each sample period
+class HeatmapSubscription extends React.Component {
+    componentDidMount() {
+        this.subscribe();
+    }
+
+    componentDidUpdate(prevProps) {
+        if (prevProps.layer != this.props.layer) {
+            this.subscribe();
+        }
+    }
+
+    componentWillUnmount() {
+        cbRustBrowser.unsubscribe_heatmap();
+    }
+
+    subscribe() {
+        if (this.props.layer) {
+            cbRustBrowser.subscribe_heatmap(this.props.layer);
+        } else {
+            cbRustBrowser.unsubscribe_heatmap();
+        }
+    }
+
+    render() {
+        return null;
+    }
+}
+
+export function Windows(props) {
+    const { state, setState } = props;
+    const shownLayer = state.heatmaps.shownLayer;
+
+    return [
+        <HeatmapSubscription key="heatmapSubscription" layer={shownLayer} />,
+        state.heatmaps.show && <div key="heatmaps" className="window heatmaps">
+            <h1>Heatmaps</h1>
+            <Select style={{ width: "100%" }} value={shownLayer || "none"}
+                onChange={layer => {
+                    setState(oldState => update(oldState, {
+                        heatmaps: { shownLayer: { $set: layer == "none" ? null : layer } }
+                    }));
+                }}>
+                <Select.Option value="none">None</Select.Option>
+                {Object.keys(LAYERS).map(layer =>
+                    <Select.Option key={layer} value={layer}>{LAYERS[layer].description}</Select.Option>
+                )}
+            </Select>
+            {shownLayer && !state.heatmaps.cells[shownLayer] && <p>(no samples yet)</p>}
+        </div>
+    ];
+}
+
+export function Layers(props) {
+    const { state } = props;
+    const shownLayer = state.heatmaps.shownLayer;
+    const cells = shownLayer && state.heatmaps.cells[shownLayer];
+
+    if (!cells || !state.heatmaps.cellMesh) return null;
+
+    const layer = LAYERS[shownLayer];
+    const max = layer.max || Math.max(...cells.map(cell => cell.value), 0.0001);
+    const instances = new Float32Array(cells.length * 8);
+
+    cells.forEach((cell, i) => {
+        instances.set([
+            cell.x * state.heatmaps.cellSize, cell.y * state.heatmaps.cellSize, 0.0,
+            1.0, 0.0,
+            ...cellColor(layer, cell.value, max)
+        ], i * 8);
+    });
+
+    return <RenderLayer
+        key="heatmap"
+        decal={true}
+        renderOrder={renderOrder.heatmap}
+        batches={[{
+            mesh: state.heatmaps.cellMesh,
+            instances
+        }]} />;
+}
+
+export function bindInputs(state, setState) {
+    const inputActions = {
+        "toggleHeatmapsView": () => setState(oldState => update(oldState, {
+            heatmaps: { show: { $apply: b => !b } }
+        })),
+    }
+
+    Mousetrap.bind(state.settings.heatmaps.toggleHeatmapsWindowKey.key, inputActions["toggleHeatmapsView"]);
+}
@@ -0,0 +1,66 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for BrowserHeatmapUI {
+    type ID = BrowserHeatmapUIID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct BrowserHeatmapUIID {
+    _raw_id: RawID
+}
+
+impl TypedID for BrowserHeatmapUIID {
+    type Target = BrowserHeatmapUI;
+
+    fn from_raw(id: RawID) -> Self {
+        BrowserHeatmapUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BrowserHeatmapUIID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = BrowserHeatmapUIID::from_raw(world.allocate_instance_id::<BrowserHeatmapUI>());
+        let swarm = world.local_broadcast::<BrowserHeatmapUI>();
+        world.send(swarm, MSG_BrowserHeatmapUI_spawn(id, ));
+        id
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserHeatmapUI_spawn(pub BrowserHeatmapUIID, );
+
+impl Into<HeatmapUIID> for BrowserHeatmapUIID {
+    fn into(self) -> HeatmapUIID {
+        HeatmapUIID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    HeatmapUIID::register_implementor::<BrowserHeatmapUI>(system);
+    system.add_spawner::<BrowserHeatmapUI, _, _>(
+        |&MSG_BrowserHeatmapUI_spawn(id, ), world| {
+            BrowserHeatmapUI::spawn(id, world)
+        }, false
+    );
+}
\ No newline at end of file
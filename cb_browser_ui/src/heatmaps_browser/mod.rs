@@ -0,0 +1,95 @@
+use kay::{World, ActorSystem, TypedID};
+use stdweb::serde::Serde;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+use stdweb::js_export;
+use michelangelo::{Mesh, Vertex};
+use browser_utils::to_js_mesh;
+use SYSTEM;
+
+use heatmaps::{HeatmapsID, HeatmapLayer, HeatmapCell, HEATMAP_CELL_SIZE};
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn subscribe_heatmap(layer: Serde<HeatmapLayer>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    HeatmapsID::global_first(world).subscribe(
+        layer.0,
+        BrowserHeatmapUIID::local_first(world).into(),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn unsubscribe_heatmap() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    HeatmapsID::global_first(world)
+        .unsubscribe(BrowserHeatmapUIID::local_first(world).into(), world);
+}
+
+#[derive(Compact, Clone)]
+pub struct BrowserHeatmapUI {
+    id: BrowserHeatmapUIID,
+}
+
+impl BrowserHeatmapUI {
+    pub fn spawn(id: BrowserHeatmapUIID, _: &mut World) -> BrowserHeatmapUI {
+        // one grid cell, slightly inset so neighbouring cells stay distinguishable
+        let (min, max) = (1.0, HEATMAP_CELL_SIZE - 1.0);
+        let cell_mesh = Mesh::new(
+            vec![
+                Vertex {
+                    position: [min, min, 0.0],
+                },
+                Vertex {
+                    position: [max, min, 0.0],
+                },
+                Vertex {
+                    position: [max, max, 0.0],
+                },
+                Vertex {
+                    position: [min, max, 0.0],
+                },
+            ],
+            vec![0, 1, 2, 0, 2, 3],
+        );
+
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                heatmaps: {
+                    cellMesh: {"$set": @{to_js_mesh(&cell_mesh)}},
+                    cellSize: {"$set": @{HEATMAP_CELL_SIZE}}
+                }
+            }));
+        }
+
+        BrowserHeatmapUI { id }
+    }
+}
+
+use heatmaps::{HeatmapUI, HeatmapUIID};
+use compact::CVec;
+
+impl HeatmapUI for BrowserHeatmapUI {
+    fn on_heatmap(&mut self, layer: HeatmapLayer, cells: &CVec<HeatmapCell>, _: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                heatmaps: {
+                    cells: {[@{Serde(layer)}]: {"$set": @{Serde(cells)}}}
+                }
+            }));
+        }
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<BrowserHeatmapUI>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    BrowserHeatmapUIID::spawn(world);
+}
@@ -123,7 +123,10 @@ impl LandUseUI for BrowserLandUseUI {
                     buildingMeshes: @{material_unsets},
                     buildingProps: @{prop_unsets}
                 }},
-                households: {buildingPositions: {"$unset": [@{Serde(id)}]}}
+                households: {
+                    buildingPositions: {"$unset": [@{Serde(id)}]},
+                    buildingShapes: {"$unset": [@{Serde(id)}]}
+                }
             }));
         }
     }
@@ -24,10 +24,16 @@ use std::panic;
 pub mod planning_browser;
 pub mod debug;
 pub mod time_browser;
+pub mod treasury_browser;
+pub mod statistics_browser;
+pub mod heatmaps_browser;
 pub mod households_browser;
 pub mod transport_browser;
 pub mod land_use_browser;
 pub mod vegetation_browser;
+pub mod construction_browser;
+pub mod terrain_browser;
+pub mod water_browser;
 pub mod browser_utils;
 
 // TODO: not thread safe for now
@@ -56,6 +62,11 @@ pub fn start() {
     use stdweb::serde::Serde;
     use stdweb::unstable::TryFrom;
 
+    // has to match the server before any ticks are converted
+    time::set_ticks_per_sim_second(
+        u32::try_from(js! { return window.cbSimulationSettings.ticksPerSimSecond; }).unwrap(),
+    );
+
     let mut system = kay::ActorSystem::new(kay::Networking::new(
         1,
         vec![format!("{}:{}", server_host, 9999), "ws-client".to_owned()],
@@ -70,9 +81,14 @@ pub fn start() {
     planning_browser::setup(&mut system);
     transport_browser::setup(&mut system);
     time_browser::setup(&mut system);
+    treasury_browser::setup(&mut system);
+    statistics_browser::setup(&mut system);
+    heatmaps_browser::setup(&mut system);
     land_use_browser::setup(&mut system);
     households_browser::setup(&mut system);
     vegetation_browser::setup(&mut system);
+    construction_browser::setup(&mut system);
+    water_browser::setup(&mut system);
 
     js! {
         window.cbTypeIdMapping = @{Serde(system.get_actor_type_id_to_name_mapping())}
@@ -84,9 +100,15 @@ pub fn start() {
     planning_browser::spawn(&mut system.world());
     transport_browser::spawn(&mut system.world());
     time_browser::spawn(&mut system.world());
+    treasury_browser::spawn(&mut system.world());
+    statistics_browser::spawn(&mut system.world());
+    heatmaps_browser::spawn(&mut system.world());
     land_use_browser::spawn(&mut system.world());
     households_browser::spawn(&mut system.world());
     vegetation_browser::spawn(&mut system.world());
+    construction_browser::spawn(&mut system.world());
+    terrain_browser::spawn(&mut system.world());
+    water_browser::spawn(&mut system.world());
 
     system.process_all_messages();
 
@@ -5,6 +5,7 @@ import uuid from '../uuid';
 import { solidColorShader } from 'monet';
 import * as PlanningMenu from './PlanningMenu';
 export const Tools = PlanningMenu.Tools;
+export const Windows = PlanningMenu.Windows;
 import React from 'react'
 import { RenderLayer, Interactive3DShape } from '../browser_utils/Utils';
 import { vec2 } from 'gl-matrix';
@@ -18,6 +19,13 @@ const LAND_USES = [
     "Official",
 ];
 
+function persistedSessionId() {
+    if (!localStorage["cb-planning-session-id"]) {
+        localStorage["cb-planning-session-id"] = uuid();
+    }
+    return localStorage["cb-planning-session-id"];
+}
+
 export const initialState = {
     planningMode: null,
     rendering: {
@@ -37,6 +45,21 @@ export const initialState = {
     },
     projects: {
     },
+    // identifies the projects started from this browser, kept across reloads
+    // so that open projects can be continued
+    sessionId: persistedSessionId(),
+    // colliding gestures by project, for projects that couldn't be implemented,
+    // and whether rebasing can resolve them
+    conflicts: {},
+    importSettings: {
+        show: false,
+        offsetX: 0,
+        offsetY: 0,
+        rotationDegrees: 0,
+    },
+    showTimeline: false,
+    // implemented projects planned by people, oldest first
+    implementedProjects: [],
     currentProject: null,
     hoveredControlPoint: {},
     hoveredInsertPoint: null,
@@ -169,8 +192,8 @@ function splitGesture(projectId, gestureId, splitAt, doneSplitting) {
     });
 }
 
-function setNLanes(projectId, gestureId, nLanesForward, nLanesBackward, doneChanging) {
-    cbRustBrowser.set_n_lanes(projectId, gestureId, nLanesForward, nLanesBackward, doneChanging);
+function setNLanes(projectId, gestureId, nLanesForward, nLanesBackward, isBridge, doneChanging) {
+    cbRustBrowser.set_n_lanes(projectId, gestureId, nLanesForward, nLanesBackward, isBridge, doneChanging);
 
     return oldState => update(oldState, {
         planning: {
@@ -246,14 +269,18 @@ export function ShapesAndLayers(props) {
         ).reduce((coll, gestures) => Object.assign(coll, gestures), {});
 
         let { gestureId: hoveredGestureId, pointIdx: hoveredPointIdx } = state.planning.hoveredControlPoint;
+        const collidingGestures = (state.planning.conflicts[state.planning.currentProject] || { gestures: [] }).gestures;
+        const roadInfos = state.planning.rendering.roadInfos;
 
         for (let gestureId of Object.keys(gestures)) {
             const gesture = gestures[gestureId];
+            if (gesture.deleted) continue;
 
             for (let [pointIdx, point] of gesture.points.entries()) {
 
                 let isRelevant = (gesture.intent.Road && state.planning.planningMode === "roads")
-                    || (gesture.intent.Zone && state.planning.planningMode === "zoning");
+                    || (gesture.intent.Zone && state.planning.planningMode === "zoning")
+                    || (gesture.intent.Water && state.planning.planningMode === "water");
 
                 if (isRelevant) {
                     let isHovered = gestureId == hoveredGestureId && pointIdx == hoveredPointIdx;
@@ -266,7 +293,11 @@ export function ShapesAndLayers(props) {
                         1.0, 0.0,
                         ...(isHovered
                             ? colors.controlPointHover
-                            : (gesture.fromMaster ? colors.controlPointMaster : colors.controlPointCurrentProject))
+                            : (collidingGestures.includes(gestureId)
+                                ? colors.controlPointConflict
+                                : (roadInfos[gestureId] && roadInfos[gestureId].rejection
+                                    ? colors.controlPointRejected
+                                    : (gesture.fromMaster ? colors.controlPointMaster : colors.controlPointCurrentProject))))
                     ]);
 
                     controlPointsInteractables.push(<Interactive3DShape
@@ -330,7 +361,7 @@ export function ShapesAndLayers(props) {
 
     if (state.planning.planningMode === "roads") {
         for (let gestureId of Object.keys(state.planning.rendering.roadInfos)) {
-            let { centerLine, outline, nLanesForward, nLanesBackward } = state.planning.rendering.roadInfos[gestureId];
+            let { centerLine, outline, nLanesForward, nLanesBackward, isBridge } = state.planning.rendering.roadInfos[gestureId];
 
             roadCenterInteractables.push(<Interactive3DShape
                 id={gestureId + "insert"}
@@ -438,7 +469,7 @@ export function ShapesAndLayers(props) {
                                 newNLanesBackward = Math.max(0.0, Math.round(-orthogonalDistance / 3.0));
                             }
 
-                            setState(setNLanes(state.planning.currentProject, gestureId, newNLanesForward, newNLanesBackward, e.drag.end ? true : false));
+                            setState(setNLanes(state.planning.currentProject, gestureId, newNLanesForward, newNLanesBackward, isBridge, e.drag.end ? true : false));
                         }
                     }
                     if (e.hover) {
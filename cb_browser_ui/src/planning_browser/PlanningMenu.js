@@ -1,6 +1,6 @@
 import colors, { toCSS, fromLinFloat } from '../colors';
 import React from 'react';
-import { Button, Select, Divider, Icon } from 'antd';
+import { Button, Select, Divider, Icon, InputNumber } from 'antd';
 const Option = Select.Option;
 import uuid from '../uuid';
 
@@ -30,7 +30,7 @@ function implementProject(oldState) {
 
 function startNewProject(oldState) {
     const projectId = uuid();
-    cbRustBrowser.start_new_project(projectId);
+    cbRustBrowser.start_new_project(projectId, oldState.planning.sessionId);
     return update(oldState, {
         planning: {
             currentProject: { $set: projectId },
@@ -38,6 +38,81 @@ function startNewProject(oldState) {
     });
 }
 
+function rebaseProject(oldState) {
+    if (oldState.planning.currentProject) {
+        cbRustBrowser.rebase_project(oldState.planning.currentProject);
+    }
+    return oldState;
+}
+
+// resolves all gestures that were changed both in the project and by others
+function resolveConflicts(keepOwn) {
+    return oldState => {
+        const projectId = oldState.planning.currentProject;
+        const conflicts = projectId && oldState.planning.conflicts[projectId];
+        if (conflicts) {
+            for (let gestureId of conflicts.gestures) {
+                cbRustBrowser.resolve_conflict(projectId, gestureId, keepOwn);
+            }
+        }
+        return oldState;
+    }
+}
+
+// projects set up on the server (like imports) are open to everyone
+function isOwnProject(state, projectId) {
+    const project = state.planning.projects[projectId];
+    return project && (project.author === "Server" || project.author.Session === state.planning.sessionId);
+}
+
+function exportPlan(oldState) {
+    if (oldState.planning.currentProject) {
+        cbRustBrowser.export_project(oldState.planning.currentProject);
+    } else {
+        cbRustBrowser.export_master_plan();
+    }
+    return oldState;
+}
+
+function importPlan(source) {
+    return oldState => {
+        const projectId = uuid();
+        const { offsetX, offsetY, rotationDegrees } = oldState.planning.importSettings;
+        cbRustBrowser.import_project(projectId, oldState.planning.sessionId, source, {
+            translation: [offsetX, offsetY],
+            rotation: rotationDegrees * Math.PI / 180,
+        });
+        return update(oldState, {
+            planning: {
+                currentProject: { $set: projectId },
+                importSettings: { show: { $set: false } },
+            }
+        });
+    }
+}
+
+function toggleTimeline(oldState) {
+    if (!oldState.planning.showTimeline) {
+        cbRustBrowser.get_implemented_projects();
+    }
+    return update(oldState, {
+        planning: { showTimeline: { $apply: s => !s } }
+    });
+}
+
+function revertImplementedProject(implementedProjectId) {
+    return oldState => {
+        const projectId = uuid();
+        cbRustBrowser.revert_implemented_project(implementedProjectId, projectId, oldState.planning.sessionId);
+        return update(oldState, {
+            planning: {
+                currentProject: { $set: projectId },
+                showTimeline: { $set: false },
+            }
+        });
+    }
+}
+
 function undo(oldState) {
     if (oldState.planning.currentProject) {
         cbRustBrowser.undo(oldState.planning.currentProject);
@@ -63,15 +138,37 @@ function redo(oldState) {
     return oldState
 }
 
+// TODO: share constants with Rust somehow
+const MAX_ROAD_CUT_OR_FILL = 6.0;
+
+function describeRoadRejection({ rejection, elevationProfile }) {
+    if (rejection == "TooSteep") {
+        const maxCutOrFill = Math.max(...elevationProfile.map(sample =>
+            Math.abs(sample.roadHeight - sample.terrainHeight)
+        ));
+        return `too steep: would need ${maxCutOrFill.toFixed(1)}m of cut or fill (at most ${MAX_ROAD_CUT_OR_FILL}m)`;
+    } else if (rejection == "CrossesWater") {
+        return "crosses water: use the Bridge tool instead";
+    } else {
+        return rejection;
+    }
+}
+
 export function Tools(props) {
     const { state, setState } = props;
+    const ownProjectIds = Object.keys(state.planning.projects).filter(projectId => isOwnProject(state, projectId));
+    const conflicts = state.planning.conflicts[state.planning.currentProject] || { gestures: [], canRebase: true };
+    const collidingGestures = conflicts.gestures;
+    const rejectedRoads = Object.values(state.planning.rendering.roadInfos).filter(roadInfo => roadInfo.rejection);
     return [
         <Toolbar id="main-toolbar"
-            options={{ inspection: { description: "Inspection" }, planning: { description: "Planning" } }}
+            options={window.cbAccess.spectator
+                ? { inspection: { description: "Inspection" } }
+                : { inspection: { description: "Inspection" }, planning: { description: "Planning" } }}
             value={state.uiMode}
             onChange={newMode => setState({ uiMode: newMode })} />,
         state.uiMode == 'planning' && [
-            (state.planning.currentProject || Object.keys(state.planning.projects).length > 0)
+            (state.planning.currentProject || ownProjectIds.length > 0)
                 ? <Select
                     style={{ width: 180 }}
                     showSearch={true}
@@ -89,14 +186,40 @@ export function Tools(props) {
                             {menu}
                         </div>
                     )}
-                >{Object.keys(state.planning.projects).map(projectId =>
+                >{ownProjectIds.map(projectId =>
                     <Option value={projectId}>Project '{projectId.slice(0, 3).toUpperCase()}'</Option>
                 )}</Select>
                 : <Button type="primary" onClick={() => setState(startNewProject)}>Start new project</Button>,
+            <Button onClick={() => setState(oldState => update(oldState, {
+                planning: { importSettings: { show: { $apply: s => !s } } }
+            }))}>Import...</Button>,
+            <Button onClick={() => setState(toggleTimeline)}>Timeline</Button>,
+            !state.planning.currentProject &&
+            <Button onClick={() => setState(exportPlan)}>Export master plan</Button>,
             state.planning.currentProject && [
-                <Button type="primary"
-                    onClick={() => setState(implementProject)}
-                >Implement</Button>,
+                collidingGestures.length > 0
+                    ? (conflicts.canRebase
+                        ? <Button type="danger"
+                            title={`${collidingGestures.length} gestures collide with changes made by others since this project was started`}
+                            onClick={() => setState(rebaseProject)}
+                        >Rebase ({collidingGestures.length} conflicts)</Button>
+                        : [
+                            <Button type="danger"
+                                title={`Overwrite the changes others made to ${collidingGestures.length} gestures`}
+                                onClick={() => setState(resolveConflicts(true))}
+                            >Keep mine ({collidingGestures.length} conflicts)</Button>,
+                            <Button
+                                title={`Discard your changes to ${collidingGestures.length} gestures that others changed as well`}
+                                onClick={() => setState(resolveConflicts(false))}
+                            >Take theirs</Button>
+                        ])
+                    : <Button type="primary"
+                        onClick={() => setState(implementProject)}
+                    >Implement</Button>,
+                rejectedRoads.length > 0 &&
+                <Button type="dashed" disabled={true}
+                    title={`These roads won't be built:\n${rejectedRoads.map(describeRoadRejection).join("\n")}`}
+                >{rejectedRoads.length} roads can't be built</Button>,
                 <Toolbar id="planning-history-toolbar"
                     options={{
                         undo: { description: "Undo", disabled: !state.planning.projects[state.planning.currentProject] || !state.planning.projects[state.planning.currentProject].undoable_history.length },
@@ -104,14 +227,40 @@ export function Tools(props) {
                     }}
                     onChange={value => value == "undo" ? setState(undo) : setState(redo)}
                 />,
+                <Button onClick={() => setState(exportPlan)}>Export</Button>,
                 state.planning.currentProject &&
                 <Toolbar id="planning-toolbar"
-                    options={{ roads: { description: "Roads" }, zoning: { description: "Zoning" } }}
+                    options={{ roads: { description: "Roads" }, zoning: { description: "Zoning" }, water: { description: "Water" } }}
                     value={state.planning.planningMode}
                     onChange={(value) => setState(oldState => update(oldState, {
                         planning: {
                             planningMode: { $set: value },
-                            canvasMode: { intent: { $set: value == "roads" ? { Road: { n_lanes_forward: 1, n_lanes_backward: 1 } } : null } }
+                            canvasMode: { intent: { $set: value == "roads" ? { Road: { n_lanes_forward: 1, n_lanes_backward: 1, is_bridge: false } } : null } }
+                        }
+                    }))} />,
+                state.planning.currentProject && state.planning.planningMode == "roads" &&
+                <Toolbar id="road-toolbar"
+                    options={{ road: { description: "Road" }, bridge: { description: "Bridge" } }}
+                    value={state.planning.canvasMode.intent && state.planning.canvasMode.intent.Road && (state.planning.canvasMode.intent.Road.is_bridge ? "bridge" : "road")}
+                    onChange={value => setState(oldState => update(oldState, {
+                        planning: {
+                            canvasMode: {
+                                intent: { $set: { Road: { n_lanes_forward: 1, n_lanes_backward: 1, is_bridge: value == "bridge" } } }
+                            }
+                        }
+                    }))} />,
+                state.planning.currentProject && state.planning.planningMode == "water" &&
+                <Toolbar id="water-toolbar"
+                    options={{
+                        Lake: { description: "Lake", color: toCSS(fromLinFloat(colors.water)) },
+                        River: { description: "River", color: toCSS(fromLinFloat(colors.water)) }
+                    }}
+                    value={state.planning.canvasMode.intent && state.planning.canvasMode.intent.Water && (state.planning.canvasMode.intent.Water.River ? "River" : "Lake")}
+                    onChange={value => setState(oldState => update(oldState, {
+                        planning: {
+                            canvasMode: {
+                                intent: { $set: { Water: value == "River" ? { River: { width: 20.0 } } : "Lake" } }
+                            }
                         }
                     }))} />,
                 state.planning.currentProject && state.planning.planningMode == "zoning" &&
@@ -138,6 +287,8 @@ export function Tools(props) {
 }
 
 export function bindInputs(state, setState) {
+    if (window.cbAccess.spectator) return;
+
     const inputActions = {
         "implementProject": () => setState(implementProject),
         "undo": () => setState(undo),
@@ -147,4 +298,61 @@ export function bindInputs(state, setState) {
     Mousetrap.bind(state.settings.planning.implementProjectKey.key, inputActions["implementProject"]);
     Mousetrap.bind(state.settings.planning.undoKey.key, inputActions["undo"]);
     Mousetrap.bind(state.settings.planning.redoKey.key, inputActions["redo"]);
-}
\ No newline at end of file
+}
+
+function ImportWindow(props) {
+    const { state, setState } = props;
+    const settings = state.planning.importSettings;
+
+    const setSetting = (key, value) => setState(oldState => update(oldState, {
+        planning: { importSettings: { [key]: { $set: value } } }
+    }));
+
+    const onFileChosen = e => {
+        const file = e.target.files[0];
+        if (file) {
+            const reader = new FileReader();
+            reader.onload = () => setState(importPlan(reader.result));
+            reader.readAsText(file);
+        }
+    };
+
+    return state.uiMode == 'planning' && settings.show && <div key="planImport" className="window plan-import">
+        <h1>Import Plan</h1>
+        <div>
+            Offset X <InputNumber value={settings.offsetX} step={10.0}
+                onChange={value => setSetting("offsetX", value)} />
+            Offset Y <InputNumber value={settings.offsetY} step={10.0}
+                onChange={value => setSetting("offsetY", value)} />
+        </div>
+        <div>
+            Rotation (°) <InputNumber value={settings.rotationDegrees} step={15.0}
+                onChange={value => setSetting("rotationDegrees", value)} />
+        </div>
+        <input type="file" accept=".ron" onChange={onFileChosen} />
+    </div>;
+}
+function TimelineWindow(props) {
+    const { state, setState } = props;
+
+    return state.uiMode == 'planning' && state.planning.showTimeline && <div key="planTimeline" className="window plan-timeline">
+        <a className="close-window" onClick={() => setState(toggleTimeline)}>×</a>
+        <h1>Implemented Projects</h1>
+        {state.planning.implementedProjects.length == 0
+            ? <p>No projects implemented yet</p>
+            : <ol>{state.planning.implementedProjects.map(info =>
+                <li key={info.project_id}>
+                    Project '{info.project_id.slice(0, 3).toUpperCase()}'
+                    {" "}({info.n_gestures} gestures{info.author.Session === state.planning.sessionId ? ", by you" : ""})
+                    {" "}<Button size="small" onClick={() => setState(revertImplementedProject(info.project_id))}>Revert</Button>
+                </li>
+            )}</ol>}
+    </div>;
+}
+
+export function Windows(props) {
+    return [
+        <ImportWindow key="import" {...props} />,
+        <TimelineWindow key="timeline" {...props} />,
+    ];
+}
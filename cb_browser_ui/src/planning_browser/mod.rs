@@ -1,6 +1,6 @@
 use stdweb::serde::Serde;
 use kay::{World, Actor, External, ActorSystem, TypedID};
-use compact::{CHashMap};
+use compact::{CHashMap, CVec, COption, CString};
 use std::collections::HashMap;
 use descartes::LinePath;
 use michelangelo::{MeshGrouper};
@@ -8,7 +8,8 @@ use planning::{ProjectID, Project, GestureID, PrototypeID, PlanHistory, PlanResu
 PlanHistoryUpdate, ProjectUpdate, PlanResultUpdate, ActionGroups};
 use ::land_use::zone_planning::{LandUse, LAND_USES};
 use planning::ui::{PlanningUI, PlanningUIID};
-use browser_utils::{updated_groups_to_js, to_js_mesh, FrameListener, FrameListenerID};
+use planning::timeline::ImplementedProjectInfo;
+use browser_utils::{updated_groups_to_js, to_js_mesh, FrameListener, FrameListenerID, editor_token};
 
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 use stdweb::js_export;
@@ -30,6 +31,7 @@ pub fn move_gesture_point(
         point_idx,
         new_position.0,
         done_moving,
+        editor_token(),
         world,
     );
 }
@@ -48,6 +50,7 @@ pub fn start_new_gesture(
         gesture_id.0,
         intent.0,
         start.0,
+        editor_token(),
         world,
     )
 }
@@ -68,6 +71,7 @@ pub fn add_control_point(
         new_point.0,
         add_to_end,
         done_adding,
+        editor_token(),
         world,
     )
 }
@@ -86,6 +90,7 @@ pub fn insert_control_point(
         gesture_id.0,
         new_point.0,
         done_inserting,
+        editor_token(),
         world,
     )
 }
@@ -104,6 +109,7 @@ pub fn split_gesture(
         gesture_id.0,
         split_at.0,
         done_inserting,
+        editor_token(),
         world,
     )
 }
@@ -114,6 +120,7 @@ pub fn set_n_lanes(
     gesture_id: Serde<::planning::GestureID>,
     n_lanes_forward: usize,
     n_lanes_backward: usize,
+    is_bridge: bool,
     done_changing: bool,
 ) {
     let system = unsafe { &mut *SYSTEM };
@@ -124,8 +131,10 @@ pub fn set_n_lanes(
         ::planning::GestureIntent::Road(::transport::transport_planning::RoadIntent {
             n_lanes_forward: n_lanes_forward as u8,
             n_lanes_backward: n_lanes_backward as u8,
+            is_bridge,
         }),
         done_changing,
+        editor_token(),
         world,
     )
 }
@@ -134,28 +143,127 @@ pub fn set_n_lanes(
 pub fn undo(project_id: Serde<::planning::ProjectID>) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    ::planning::PlanManagerID::global_first(world).undo(project_id.0, world)
+    ::planning::PlanManagerID::global_first(world).undo(project_id.0, editor_token(), world)
 }
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn redo(project_id: Serde<::planning::ProjectID>) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    ::planning::PlanManagerID::global_first(world).redo(project_id.0, world)
+    ::planning::PlanManagerID::global_first(world).redo(project_id.0, editor_token(), world)
 }
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn implement_project(project_id: Serde<::planning::ProjectID>) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    ::planning::PlanManagerID::global_first(world).implement(project_id.0, world);
+    ::planning::PlanManagerID::global_first(world).implement(project_id.0, editor_token(), world);
 }
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
-pub fn start_new_project(project_id: Serde<::planning::ProjectID>) {
+pub fn start_new_project(
+    project_id: Serde<::planning::ProjectID>,
+    session_id: Serde<::planning::SessionID>,
+) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::planning::PlanManagerID::global_first(world).start_new_project(
+        project_id.0,
+        session_id.0,
+        editor_token(),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn rebase_project(project_id: Serde<::planning::ProjectID>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::planning::PlanManagerID::global_first(world).rebase_project(
+        project_id.0,
+        editor_token(),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn resolve_conflict(
+    project_id: Serde<::planning::ProjectID>,
+    gesture_id: Serde<::planning::GestureID>,
+    keep_own: bool,
+) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::planning::PlanManagerID::global_first(world).resolve_conflict(
+        project_id.0,
+        gesture_id.0,
+        keep_own,
+        editor_token(),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn export_master_plan() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::planning::PlanManagerID::global_first(world)
+        .export_master_plan(BrowserPlanningUIID::local_first(world).into(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn export_project(project_id: Serde<::planning::ProjectID>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::planning::PlanManagerID::global_first(world).export_project(
+        project_id.0,
+        BrowserPlanningUIID::local_first(world).into(),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn import_project(
+    project_id: Serde<::planning::ProjectID>,
+    session_id: Serde<::planning::SessionID>,
+    source: String,
+    transform: Serde<::planning::exchange::PlanTransform>,
+) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::planning::PlanManagerID::global_first(world).import_project(
+        project_id.0,
+        session_id.0,
+        source.into(),
+        transform.0,
+        editor_token(),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_implemented_projects() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::planning::PlanManagerID::global_first(world)
+        .get_implemented_projects(BrowserPlanningUIID::local_first(world).into(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn revert_implemented_project(
+    implemented_project_id: Serde<::planning::ProjectID>,
+    revert_project_id: Serde<::planning::ProjectID>,
+    session_id: Serde<::planning::SessionID>,
+) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    ::planning::PlanManagerID::global_first(world).start_new_project(project_id.0, world);
+    ::planning::PlanManagerID::global_first(world).revert_implemented_project(
+        implemented_project_id.0,
+        revert_project_id.0,
+        session_id.0,
+        editor_token(),
+        world,
+    );
 }
 
 #[derive(Compact, Clone)]
@@ -378,7 +486,8 @@ impl PlanningUI for BrowserPlanningUI {
     ) {
         use ::planning::PrototypeKind;
         use ::transport::transport_planning::{RoadPrototype, LanePrototype,
-SwitchLanePrototype, IntersectionPrototype};
+SwitchLanePrototype, IntersectionPrototype, ElevationSample, RoadRejection,
+road_elevation_profile, road_rejection};
         use ::transport::ui::{lane_mesh, marker_mesh, switch_marker_gap_mesh};
         use ::land_use::zone_planning::{LotPrototype, LotOccupancy};
         use ::michelangelo::Mesh;
@@ -599,12 +708,22 @@ SwitchLanePrototype, IntersectionPrototype};
             outline: LinePath,
             n_lanes_forward: usize,
             n_lanes_backward: usize,
+            is_bridge: bool,
+            elevation_profile: Vec<ElevationSample>,
+            rejection: Option<RoadRejection>,
         }
 
+        // roads are checked against the water of the updated result
+        self.result_preview.apply_update(result_update);
+        let water_areas = ::environment::water::water_areas(&self.result_preview);
+
         let road_infos: HashMap<GestureID, RoadInfo> =
             ::transport::transport_planning::gesture_intent_smooth_paths(effective_history)
                 .into_iter()
                 .map(|(gesture_id, _, road_intent, path)| {
+                    let elevation_profile = road_elevation_profile(&path);
+                    let rejection =
+                        road_rejection(&road_intent, &path, &elevation_profile, &water_areas);
                     (
                         gesture_id,
                         RoadInfo {
@@ -622,6 +741,9 @@ SwitchLanePrototype, IntersectionPrototype};
                             center_line: path,
                             n_lanes_forward: road_intent.n_lanes_forward as usize,
                             n_lanes_backward: road_intent.n_lanes_backward as usize,
+                            is_bridge: road_intent.is_bridge,
+                            rejection,
+                            elevation_profile,
                         },
                     )
                 })
@@ -659,10 +781,73 @@ SwitchLanePrototype, IntersectionPrototype};
             }));
         }
 
-        self.result_preview.apply_update(result_update);
         self.actions_preview = new_actions.clone();
         self.awaiting_preview_update = false;
     }
+
+    fn on_project_conflicts(
+        &mut self,
+        project_id: ProjectID,
+        colliding_gestures: &CVec<GestureID>,
+        can_rebase: bool,
+        _world: &mut World,
+    ) {
+        js! {
+            const projectId = @{Serde(project_id)};
+            const collidingGestures = @{Serde(colliding_gestures)};
+            const canRebase = @{can_rebase};
+            window.cbReactApp.boundSetState(oldState => {
+                const project = oldState.planning.projects[projectId];
+                const isOwnProject = project
+                    && project.author.Session === oldState.planning.sessionId;
+                // reopen own projects that failed to be implemented
+                const reopen = isOwnProject && collidingGestures.length > 0
+                    ? {currentProject: {"$set": projectId}}
+                    : {};
+                return update(oldState, {
+                    planning: Object.assign({
+                        conflicts: {[projectId]: {"$set": {gestures: collidingGestures, canRebase}}}
+                    }, reopen)
+                });
+            });
+        }
+    }
+
+    fn on_plan_exported(
+        &mut self,
+        project_id: &COption<ProjectID>,
+        contents: &CString,
+        _world: &mut World,
+    ) {
+        let file_name = match project_id.0 {
+            Some(project_id) => format!("project_{}.plan.ron", &project_id.0.to_string()[..8]),
+            None => "master_plan.plan.ron".to_owned(),
+        };
+        let contents: &str = contents;
+
+        js! {
+            const blob = new Blob([@{contents}], {type: "text/plain"});
+            const link = document.createElement("a");
+            link.href = URL.createObjectURL(blob);
+            link.download = @{file_name};
+            document.body.appendChild(link);
+            link.click();
+            document.body.removeChild(link);
+            URL.revokeObjectURL(link.href);
+        }
+    }
+
+    fn on_implemented_projects(
+        &mut self,
+        projects: &CVec<ImplementedProjectInfo>,
+        _world: &mut World,
+    ) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                planning: {implementedProjects: {"$set": @{Serde(projects)}}}
+            }));
+        }
+    }
 }
 
 mod kay_auto;
@@ -1,8 +1,11 @@
 let i = 0;
 
 export default {
+    terrain: i++,
+    water: i++,
     deletedGestures: i++,
     buildingGround: i++,
+    heatmap: i++,
     addedGesturesZones: i++,
     addedGesturesZonesStipple: i++,
     addedGesturesZonesOutlines: i++,
@@ -15,6 +18,7 @@ export default {
     buildingOutlines: i++,
     building3D: i++,
     vegetation: i++,
+    constructionSites: i++,
     gestureInteractables: i++,
     cars: i++
 };
\ No newline at end of file
@@ -0,0 +1,94 @@
+import React from 'react';
+
+export const initialState = {
+    show: false,
+    report: null
+}
+
+export const settingsSpec = {
+    toggleStatisticsWindowKey: { default: { key: ',' }, description: "Toggle Economy Statistics Window" }
+}
+
+let refreshInterval = null;
+
+function Sparkline(props) {
+    const { values, width = 120, height = 24 } = props;
+    if (values.length < 2) return <svg width={width} height={height} />;
+
+    const max = Math.max(...values, 0.0001);
+    const points = values.map((value, i) =>
+        `${(i / (values.length - 1) * width).toFixed(1)},${(height - value / max * height).toFixed(1)}`
+    ).join(" ");
+
+    return <svg className="sparkline" width={width} height={height}>
+        <polyline points={points} />
+    </svg>
+}
+
+function latest(samples) {
+    return samples[samples.length - 1];
+}
+
+export function Windows(props) {
+    const { state } = props;
+
+    if (state.statistics.show) {
+        if (!refreshInterval) {
+            cbRustBrowser.get_economic_statistics();
+            refreshInterval = setInterval(() => cbRustBrowser.get_economic_statistics(), 2000);
+        }
+    } else {
+        if (refreshInterval) {
+            clearInterval(refreshInterval);
+            refreshInterval = null;
+        }
+    }
+
+    const report = state.statistics.report;
+
+    return state.statistics.show && <div key="statistics" className="window statistics">
+        <h1>Economy</h1>
+        {!report || report.sample_instants.length === 0
+            ? <p>(no samples yet)</p>
+            : [
+                <h2 key="householdsTitle">Households</h2>,
+                <table key="households"><tbody>
+                    <tr><th></th><th>Population</th><th></th><th>Deals</th><th>Unmet searches</th></tr>
+                    {report.household_kinds.filter(({ samples }) => samples.length > 0).map(({ kind, samples }) =>
+                        <tr key={kind}>
+                            <td>{kind}</td>
+                            <td><Sparkline values={samples.map(s => s.population)} /></td>
+                            <td>{latest(samples).population} ({latest(samples).n_households})</td>
+                            <td>{latest(samples).deals_completed}</td>
+                            <td>{latest(samples).unmet_searches}</td>
+                        </tr>
+                    )}
+                </tbody></table>,
+                <h2 key="resourcesTitle">Resources</h2>,
+                <table key="resources"><tbody>
+                    <tr><th></th><th>Stock</th><th></th><th>Price</th><th></th><th>Deals</th><th>Unmet searches</th></tr>
+                    {report.resources.filter(({ samples }) => samples.length > 0).map(({ resource, samples }) =>
+                        <tr key={resource}>
+                            <td>{resource}</td>
+                            <td><Sparkline values={samples.map(s => s.total_stock)} /></td>
+                            <td>{latest(samples).total_stock.toFixed(0)}</td>
+                            <td><Sparkline values={samples.map(s => s.average_price)} /></td>
+                            <td>{latest(samples).average_price.toFixed(2)}</td>
+                            <td>{latest(samples).deals_completed}</td>
+                            <td>{latest(samples).unmet_searches}</td>
+                        </tr>
+                    )}
+                </tbody></table>
+            ]}
+    </div>;
+}
+
+export function bindInputs(state, setState) {
+    const inputActions = {
+        "toggleStatisticsView": () => setState(oldState => update(oldState, {
+            statistics: { show: { $apply: b => !b } }
+        })),
+    }
+
+    Mousetrap.bind(state.settings.statistics.toggleStatisticsWindowKey.key, inputActions["toggleStatisticsView"]);
+}
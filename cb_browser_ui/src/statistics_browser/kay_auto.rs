@@ -0,0 +1,66 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for BrowserEconomicStatisticsUI {
+    type ID = BrowserEconomicStatisticsUIID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct BrowserEconomicStatisticsUIID {
+    _raw_id: RawID
+}
+
+impl TypedID for BrowserEconomicStatisticsUIID {
+    type Target = BrowserEconomicStatisticsUI;
+
+    fn from_raw(id: RawID) -> Self {
+        BrowserEconomicStatisticsUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BrowserEconomicStatisticsUIID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = BrowserEconomicStatisticsUIID::from_raw(world.allocate_instance_id::<BrowserEconomicStatisticsUI>());
+        let swarm = world.local_broadcast::<BrowserEconomicStatisticsUI>();
+        world.send(swarm, MSG_BrowserEconomicStatisticsUI_spawn(id, ));
+        id
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserEconomicStatisticsUI_spawn(pub BrowserEconomicStatisticsUIID, );
+
+impl Into<EconomicStatisticsUIID> for BrowserEconomicStatisticsUIID {
+    fn into(self) -> EconomicStatisticsUIID {
+        EconomicStatisticsUIID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    EconomicStatisticsUIID::register_implementor::<BrowserEconomicStatisticsUI>(system);
+    system.add_spawner::<BrowserEconomicStatisticsUI, _, _>(
+        |&MSG_BrowserEconomicStatisticsUI_spawn(id, ), world| {
+            BrowserEconomicStatisticsUI::spawn(id, world)
+        }, false
+    );
+}
\ No newline at end of file
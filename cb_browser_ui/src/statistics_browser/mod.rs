@@ -0,0 +1,52 @@
+use kay::{World, ActorSystem, TypedID};
+use stdweb::serde::Serde;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+use stdweb::js_export;
+use SYSTEM;
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_economic_statistics() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::economy::statistics::EconomicStatisticsID::global_first(world).get_report(
+        BrowserEconomicStatisticsUIID::local_first(world).into(),
+        world,
+    );
+}
+
+#[derive(Compact, Clone)]
+pub struct BrowserEconomicStatisticsUI {
+    id: BrowserEconomicStatisticsUIID,
+}
+
+impl BrowserEconomicStatisticsUI {
+    pub fn spawn(id: BrowserEconomicStatisticsUIID, _: &mut World) -> BrowserEconomicStatisticsUI {
+        BrowserEconomicStatisticsUI { id }
+    }
+}
+
+use economy::statistics::{EconomicStatisticsUI, EconomicStatisticsUIID, EconomicStatisticsReport};
+
+impl EconomicStatisticsUI for BrowserEconomicStatisticsUI {
+    fn on_economic_statistics(&mut self, report: &EconomicStatisticsReport, _world: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                statistics: {
+                    report: {"$set": @{Serde(report)}}
+                }
+            }));
+        }
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<BrowserEconomicStatisticsUI>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    BrowserEconomicStatisticsUIID::spawn(world);
+}
@@ -0,0 +1,64 @@
+import { RenderLayer } from "../browser_utils/Utils";
+import colors from '../colors';
+import renderOrder from '../renderOrder';
+import React from 'react';
+
+export const initialState = {
+    cellSize: 0,
+    cells: []
+}
+
+// a unit square, scaled to the cell size by the instance direction
+const cellMesh = {
+    vertices: new Float32Array([
+        -0.5, -0.5, 0.0,
+        0.5, -0.5, 0.0,
+        0.5, 0.5, 0.0,
+        -0.5, 0.5, 0.0,
+    ]),
+    indices: new Uint16Array([
+        0, 1, 2, 0, 2, 3
+    ])
+}
+
+// the terrain never changes, so instances only need to be rebuilt for new colors
+let cachedFor = {};
+let cachedInstances = new Float32Array([]);
+
+function cellInstances(cells, cellSize, lowColor, highColor) {
+    if (cachedFor.cells !== cells || cachedFor.lowColor !== lowColor || cachedFor.highColor !== highColor) {
+        const nCells = cells.length / 4;
+        const instances = new Float32Array(nCells * 8);
+
+        for (let i = 0; i < nCells; i++) {
+            const [x, y, relativeHeight, shading] = cells.slice(i * 4, i * 4 + 4);
+            // flat terrain has a shading of about 0.73 and should look unshaded
+            const brightness = 0.7 + 0.4 * shading;
+            instances.set([
+                x, y, 0.0,
+                cellSize, 0.0,
+                (lowColor[0] * (1 - relativeHeight) + highColor[0] * relativeHeight) * brightness,
+                (lowColor[1] * (1 - relativeHeight) + highColor[1] * relativeHeight) * brightness,
+                (lowColor[2] * (1 - relativeHeight) + highColor[2] * relativeHeight) * brightness,
+            ], i * 8);
+        }
+
+        cachedFor = { cells, lowColor, highColor };
+        cachedInstances = instances;
+    }
+
+    return cachedInstances;
+}
+
+export function Layers(props) {
+    const { cells, cellSize } = props.state.terrain;
+
+    return <RenderLayer
+        key="terrain"
+        decal={true}
+        renderOrder={renderOrder.terrain}
+        batches={[{
+            mesh: cellMesh,
+            instances: cellInstances(cells, cellSize, colors.terrainLow, colors.terrainHigh)
+        }]} />;
+}
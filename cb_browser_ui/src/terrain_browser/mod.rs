@@ -0,0 +1,51 @@
+use kay::World;
+use descartes::P2;
+use environment::terrain::{height_at, gradient_at, MAX_HEIGHT};
+
+/// Size of the square terrain cells that are shaded individually, in meters
+const CELL_SIZE: f32 = 50.0;
+/// Half the side length of the area around the origin that terrain is shown for, in meters
+const SHOWN_EXTENT: f32 = 2500.0;
+
+/// The terrain never changes, so we only send it to the UI once.
+/// Each cell is sent as its center (x, y), its height relative to `MAX_HEIGHT`
+/// and its shading by a light from the north west (0 to 1)
+pub fn spawn(_world: &mut World) {
+    let n_cells_per_side = (2.0 * SHOWN_EXTENT / CELL_SIZE) as usize;
+    let light_direction = {
+        let (x, y, z): (f32, f32, f32) = (-1.0, 1.0, 1.5);
+        let norm = (x * x + y * y + z * z).sqrt();
+        (x / norm, y / norm, z / norm)
+    };
+
+    let mut cells = Vec::with_capacity(n_cells_per_side * n_cells_per_side * 4);
+
+    for x_cell in 0..n_cells_per_side {
+        for y_cell in 0..n_cells_per_side {
+            let center = P2::new(
+                -SHOWN_EXTENT + (x_cell as f32 + 0.5) * CELL_SIZE,
+                -SHOWN_EXTENT + (y_cell as f32 + 0.5) * CELL_SIZE,
+            );
+            let gradient = gradient_at(center);
+            let normal_norm = (gradient.norm_squared() + 1.0).sqrt();
+            let shading = ((-gradient.x * light_direction.0 - gradient.y * light_direction.1
+                + light_direction.2)
+                / normal_norm)
+                .max(0.0)
+                .min(1.0);
+
+            cells.extend_from_slice(&[center.x, center.y, height_at(center) / MAX_HEIGHT, shading]);
+        }
+    }
+
+    let cells_js: ::stdweb::web::TypedArray<f32> = cells.as_slice().into();
+
+    js! {
+        window.cbReactApp.boundSetState(oldState => update(oldState, {
+            terrain: {
+                cellSize: {"$set": @{CELL_SIZE}},
+                cells: {"$set": @{cells_js}}
+            }
+        }));
+    }
+}
@@ -1,21 +1,69 @@
 import React from 'react';
-import { Slider } from 'antd';
+import { Slider, Button, InputNumber } from 'antd';
 import update from 'immutability-helper';
 
 export const initialState = {
     ticks: 0,
     time: [0, 0],
-    speed: 1
+    speed: 1,
+    control: "Running",
+    stepTicks: 1,
+    runUntil: { hours: 7, minutes: 0 }
+}
+
+function describeControl(control) {
+    if (control == "Paused") {
+        return "Paused";
+    } else if (control.Stepping) {
+        return `Stepping, ${control.Stepping} ticks left`;
+    } else if (control.RunningUntil) {
+        return "Fast-forwarding";
+    } else {
+        return null;
+    }
+}
+
+function Controls(props) {
+    const { state, setState } = props;
+    const setTimeState = (key, value) =>
+        setState(oldState => update(oldState, { time: { [key]: { $set: value } } }));
+
+    return <div className="sim-time-controls">
+        {state.time.control == "Running"
+            ? <Button size="small" onClick={() => cbRustBrowser.pause_sim()}>Pause</Button>
+            : <Button size="small" onClick={() => cbRustBrowser.resume_sim()}>Resume</Button>}
+        <div className="sim-time-control-row">
+            <Button size="small" onClick={() => cbRustBrowser.step_sim(state.time.stepTicks)}>Step</Button>
+            <InputNumber size="small" min={1} max={100000} value={state.time.stepTicks}
+                onChange={ticks => ticks && setTimeState("stepTicks", Math.round(ticks))} />
+            ticks
+        </div>
+        <div className="sim-time-control-row">
+            <Button size="small"
+                onClick={() => cbRustBrowser.run_sim_until_time_of_day(
+                    state.time.runUntil.hours, state.time.runUntil.minutes
+                )}>Run until</Button>
+            <InputNumber size="small" min={0} max={23} value={state.time.runUntil.hours}
+                onChange={hours => Number.isInteger(hours)
+                    && setTimeState("runUntil", Object.assign({}, state.time.runUntil, { hours }))} />
+            :
+            <InputNumber size="small" min={0} max={59} value={state.time.runUntil.minutes}
+                onChange={minutes => Number.isInteger(minutes)
+                    && setTimeState("runUntil", Object.assign({}, state.time.runUntil, { minutes }))} />
+        </div>
+    </div>
 }
 
 export function Windows(props) {
     const { state, setState } = props;
+    const controlDescription = describeControl(state.time.control);
 
     return <div className="sim-time">
         {(state.time.time[0] + "").padStart(2, "0")}
         <span className="sim-time-colon">:</span>
         {(state.time.time[1] + "").padStart(2, "0")}
         <Slider className="sim-speed"
+            disabled={window.cbAccess.spectator}
             value={state.time.speed == 0 ? 0 : Math.log2(state.time.speed) + 1}
             min={0} max={6}
             marks={{ 0: "||", 1: "1x", 3: "4x", 6: "32x" }}
@@ -26,5 +74,7 @@ export function Windows(props) {
             }}
             tipFormatter={speed => speed ? `Speed: ${Math.pow(2, speed - 1)}x` : "Pause"}
         />
+        {controlDescription && <div className="sim-time-control-state">{controlDescription}</div>}
+        {!window.cbAccess.spectator && <Controls state={state} setState={setState} />}
     </div>
-}
\ No newline at end of file
+}
@@ -3,13 +3,45 @@ use stdweb::serde::Serde;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 use stdweb::js_export;
 use SYSTEM;
-use browser_utils::{FrameListener, FrameListenerID};
+use browser_utils::{FrameListener, FrameListenerID, editor_token};
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn set_sim_speed(new_speed: u16) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    ::time::TimeID::global_first(world).set_speed(new_speed, world);
+    ::time::TimeID::global_first(world).set_speed(new_speed, editor_token(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn pause_sim() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::time::TimeID::global_first(world).pause(editor_token(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn resume_sim() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::time::TimeID::global_first(world).resume(editor_token(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn step_sim(n_ticks: u32) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::time::TimeID::global_first(world).step(n_ticks, editor_token(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn run_sim_until_time_of_day(hours: u32, minutes: u32) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::time::TimeID::global_first(world).run_until_time_of_day(
+        ::time::TimeOfDay::new(hours as usize, minutes as usize),
+        editor_token(),
+        world,
+    );
 }
 
 #[derive(Compact, Clone)]
@@ -32,7 +64,13 @@ impl FrameListener for BrowserTimeUI {
 use time::ui::{TimeUI, TimeUIID};
 
 impl TimeUI for BrowserTimeUI {
-    fn on_time_info(&mut self, current_instant: ::time::Instant, speed: u16, _world: &mut World) {
+    fn on_time_info(
+        &mut self,
+        current_instant: ::time::Instant,
+        speed: u16,
+        control: ::time::TimeControl,
+        _world: &mut World,
+    ) {
         js! {
             window.cbReactApp.boundSetState(oldState => update(oldState, {
                 time: {
@@ -40,7 +78,8 @@ impl TimeUI for BrowserTimeUI {
                     time: {"$set": @{
                         Serde(::time::TimeOfDay::from(current_instant).hours_minutes())
                     }},
-                    speed: {"$set": @{speed}}
+                    speed: {"$set": @{speed}},
+                    control: {"$set": @{Serde(control)}}
                 }
             }))
         }
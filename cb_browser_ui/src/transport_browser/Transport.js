@@ -13,7 +13,9 @@ export const initialState = {
         laneMarkerGroups: new Map(),
         laneMarkerGapGroups: new Map(),
         carInstances: []
-    }
+    },
+    // only filled while the debug panel is picking actors to inspect
+    lanePaths: {}
 };
 
 const asphaltInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.asphalt]);
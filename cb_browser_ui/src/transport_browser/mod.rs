@@ -2,6 +2,7 @@ use kay::{World, ActorSystem, Actor, RawID, External, TypedID};
 use compact::CVec;
 use std::collections::HashMap;
 use descartes::LinePath;
+use stdweb::serde::Serde;
 use michelangelo::{MeshGrouper, Instance};
 use browser_utils::{FrameListener, FrameListenerID, flatten_instances, updated_groups_to_js};
 
@@ -33,6 +34,10 @@ pub struct BrowserTransportUINonPersistedState {
     asphalt_grouper: MeshGrouper<RawID>,
     lane_marker_grouper: MeshGrouper<RawID>,
     lane_marker_gaps_grouper: MeshGrouper<RawID>,
+
+    // only sent to the debug panel while it is picking actors to inspect
+    lane_paths: HashMap<RawID, LinePath>,
+    lane_paths_sent: bool,
 }
 
 impl BrowserTransportUI {
@@ -51,6 +56,8 @@ impl BrowserTransportUI {
                 asphalt_grouper: MeshGrouper::new(2000),
                 lane_marker_grouper: MeshGrouper::new(2000),
                 lane_marker_gaps_grouper: MeshGrouper::new(2000),
+                lane_paths: HashMap::new(),
+                lane_paths_sent: false,
             }),
         }
     }
@@ -80,6 +87,32 @@ impl FrameListener for BrowserTransportUI {
 
         use ::stdweb::unstable::TryInto;
 
+        let picking: bool = js! {
+            return window.cbReactApp.state.debug.show && window.cbReactApp.state.debug.picking;
+        }
+        .try_into()
+        .unwrap();
+
+        if picking && !self.lane_paths_sent {
+            js! {
+                const lanePaths = {};
+                for (const [id, path] of @{Serde(self.lane_paths.iter().collect::<Vec<_>>())}) {
+                    lanePaths[id] = path;
+                }
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    transport: { lanePaths: { "$set": lanePaths } }
+                }));
+            }
+            self.lane_paths_sent = true;
+        } else if !picking && self.lane_paths_sent {
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    transport: { lanePaths: { "$set": {} } }
+                }));
+            }
+            self.lane_paths_sent = false;
+        }
+
         let car_color_vals: Vec<::stdweb::Value> = js! {
             return require("../../../src/colors").default.carColors;
         }
@@ -108,6 +141,10 @@ impl TransportUI for BrowserTransportUI {
         _world: &mut World,
     ) {
         use ::transport::ui::{lane_mesh, marker_mesh, switch_marker_gap_mesh};
+
+        self.lane_paths.insert(id, lane_path.clone());
+        self.lane_paths_sent = false;
+
         if is_switch {
             let updated_lane_marker_gaps_groups = self
                 .lane_marker_gaps_grouper
@@ -172,6 +209,9 @@ impl TransportUI for BrowserTransportUI {
         on_intersection: bool,
         _world: &mut World,
     ) {
+        self.lane_paths.remove(&id);
+        self.lane_paths_sent = false;
+
         if is_switch {
             let updated_lane_marker_gaps_groups =
                 self.lane_marker_gaps_grouper.update(Some(id), None);
@@ -0,0 +1,25 @@
+import React from 'react';
+import { Tooltip } from 'antd';
+
+export const initialState = {
+    report: null
+}
+
+export function Windows(props) {
+    const { report } = props.state.treasury;
+
+    if (!report) return null;
+
+    const details = <div>
+        <p>Tax income: ${report.total_tax_income.toFixed(0)}</p>
+        <p>Construction: -${report.total_construction_expenses.toFixed(0)}</p>
+        <p>Upkeep: -${report.total_upkeep_expenses.toFixed(0)} ({(report.maintained_lane_length / 1000).toFixed(1)}km of lanes)</p>
+        <p>Unaffordable projects: {report.n_rejected_projects}</p>
+    </div>;
+
+    return <Tooltip title={details} placement="bottomLeft">
+        <div className={"treasury-balance" + (report.balance < 0 ? " in-debt" : "")}>
+            ${report.balance.toFixed(0)}
+        </div>
+    </Tooltip>
+}
@@ -0,0 +1,73 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for BrowserTreasuryUI {
+    type ID = BrowserTreasuryUIID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct BrowserTreasuryUIID {
+    _raw_id: RawID
+}
+
+impl TypedID for BrowserTreasuryUIID {
+    type Target = BrowserTreasuryUI;
+
+    fn from_raw(id: RawID) -> Self {
+        BrowserTreasuryUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BrowserTreasuryUIID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = BrowserTreasuryUIID::from_raw(world.allocate_instance_id::<BrowserTreasuryUI>());
+        let swarm = world.local_broadcast::<BrowserTreasuryUI>();
+        world.send(swarm, MSG_BrowserTreasuryUI_spawn(id, ));
+        id
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserTreasuryUI_spawn(pub BrowserTreasuryUIID, );
+
+impl Into<FrameListenerID> for BrowserTreasuryUIID {
+    fn into(self) -> FrameListenerID {
+        FrameListenerID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TreasuryUIID> for BrowserTreasuryUIID {
+    fn into(self) -> TreasuryUIID {
+        TreasuryUIID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    FrameListenerID::register_implementor::<BrowserTreasuryUI>(system);
+    TreasuryUIID::register_implementor::<BrowserTreasuryUI>(system);
+    system.add_spawner::<BrowserTreasuryUI, _, _>(
+        |&MSG_BrowserTreasuryUI_spawn(id, ), world| {
+            BrowserTreasuryUI::spawn(id, world)
+        }, false
+    );
+}
\ No newline at end of file
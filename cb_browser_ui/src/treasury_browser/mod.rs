@@ -0,0 +1,46 @@
+use kay::{World, ActorSystem, Actor, TypedID};
+use stdweb::serde::Serde;
+use browser_utils::{FrameListener, FrameListenerID};
+
+#[derive(Compact, Clone)]
+pub struct BrowserTreasuryUI {
+    id: BrowserTreasuryUIID,
+}
+
+impl BrowserTreasuryUI {
+    pub fn spawn(id: BrowserTreasuryUIID, _: &mut World) -> BrowserTreasuryUI {
+        BrowserTreasuryUI { id }
+    }
+}
+
+impl FrameListener for BrowserTreasuryUI {
+    fn on_frame(&mut self, world: &mut World) {
+        ::economy::treasury::TreasuryID::global_first(world).get_report(self.id_as(), world);
+    }
+}
+
+use economy::treasury::{TreasuryUI, TreasuryUIID, TreasuryReport};
+
+impl TreasuryUI for BrowserTreasuryUI {
+    fn on_treasury_report(&mut self, report: &TreasuryReport, _world: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                treasury: {
+                    report: {"$set": @{Serde(*report)}}
+                }
+            }))
+        }
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<BrowserTreasuryUI>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    BrowserTreasuryUIID::spawn(world);
+}
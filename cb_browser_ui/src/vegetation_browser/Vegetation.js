@@ -6,7 +6,9 @@ export const initialState = {
     trunkInstances: [],
     smallCanopyInstances: [],
     mediumCanopyInstances: [],
-    largeCanopyInstances: []
+    largeCanopyInstances: [],
+    // only filled while the debug panel is picking actors to inspect
+    plantPositions: {}
 }
 
 import React from 'react';
@@ -5,6 +5,7 @@ use browser_utils::to_js_mesh;
 use descartes::{P2, LinePath, ClosedLinePath, PrimitiveArea};
 use michelangelo::{Sculpture, FlatSurface, Instance};
 use std::collections::HashMap;
+use stdweb::serde::Serde;
 use browser_utils::{FrameListener, FrameListenerID, flatten_instances};
 
 #[derive(Compact, Clone)]
@@ -32,6 +33,8 @@ pub struct BrowserVegetationUINonPersistedState {
     instances_current: bool,
     trunk_color: [f32; 3],
     canopy_color: [f32; 3],
+    // only sent to the debug panel while it is picking actors to inspect
+    plant_positions_sent: bool,
 }
 
 impl BrowserVegetationUI {
@@ -121,6 +124,7 @@ impl BrowserVegetationUI {
                 instances_current: true,
                 trunk_color: [0.0, 0.0, 0.0],
                 canopy_color: [0.0, 0.0, 0.0],
+                plant_positions_sent: false,
             }),
         }
     }
@@ -285,6 +289,38 @@ impl FrameListener for BrowserVegetationUI {
 
             self.instances_current = true;
         }
+
+        let picking: bool = js! {
+            return window.cbReactApp.state.debug.show && window.cbReactApp.state.debug.picking;
+        }
+        .try_into()
+        .unwrap();
+
+        if picking && !self.plant_positions_sent {
+            let plant_positions = self
+                .tree_protos
+                .iter()
+                .map(|(raw_id, proto)| (*raw_id, proto.position))
+                .collect::<Vec<_>>();
+
+            js! {
+                const plantPositions = {};
+                for (const [id, position] of @{Serde(plant_positions)}) {
+                    plantPositions[id] = position;
+                }
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    vegetation: { plantPositions: { "$set": plantPositions } }
+                }));
+            }
+            self.plant_positions_sent = true;
+        } else if !picking && self.plant_positions_sent {
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    vegetation: { plantPositions: { "$set": {} } }
+                }));
+            }
+            self.plant_positions_sent = false;
+        }
     }
 }
 
@@ -292,11 +328,13 @@ impl VegetationUI for BrowserVegetationUI {
     fn on_plant_spawned(&mut self, id: PlantID, proto: &PlantPrototype, _: &mut World) {
         self.state.tree_protos.insert(id.as_raw(), proto.clone());
         self.instances_current = false;
+        self.plant_positions_sent = false;
     }
 
     fn on_plant_destroyed(&mut self, id: PlantID, _: &mut World) {
         self.state.tree_protos.remove(&id.as_raw());
         self.instances_current = false;
+        self.plant_positions_sent = false;
     }
 }
 
@@ -0,0 +1,22 @@
+import { RenderLayer } from "../browser_utils/Utils";
+import colors from '../colors';
+import renderOrder from '../renderOrder';
+import React from 'react';
+
+export const initialState = {
+    meshes: {}
+}
+
+export function Layers(props) {
+    const { state } = props;
+    const waterInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.water]);
+
+    return <RenderLayer
+        key="water"
+        decal={true}
+        renderOrder={renderOrder.water}
+        batches={Object.values(state.water.meshes).map(mesh => ({
+            mesh,
+            instances: waterInstance
+        }))} />;
+}
@@ -0,0 +1,55 @@
+use kay::{World, ActorSystem, TypedID};
+use descartes::Area;
+use michelangelo::Mesh;
+use browser_utils::to_js_mesh;
+use environment::water::WaterBodyID;
+use environment::water::ui::{WaterUI, WaterUIID};
+
+#[derive(Compact, Clone)]
+pub struct BrowserWaterUI {
+    id: BrowserWaterUIID,
+}
+
+impl BrowserWaterUI {
+    pub fn spawn(id: BrowserWaterUIID, world: &mut World) -> BrowserWaterUI {
+        WaterBodyID::global_broadcast(world).get_render_info(id.into(), world);
+
+        BrowserWaterUI { id }
+    }
+}
+
+impl WaterUI for BrowserWaterUI {
+    fn on_water_body_spawned(&mut self, id: WaterBodyID, area: &Area, _world: &mut World) {
+        let mesh = Mesh::from_area(area);
+
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                water: {
+                    meshes: {[@{id.as_raw_string()}]: {"$set": @{to_js_mesh(&mesh)}}}
+                }
+            }));
+        }
+    }
+
+    fn on_water_body_destroyed(&mut self, id: WaterBodyID, _world: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                water: {
+                    meshes: {"$unset": [@{id.as_raw_string()}]}
+                }
+            }));
+        }
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<BrowserWaterUI>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    BrowserWaterUIID::spawn(world);
+}
@@ -0,0 +1,184 @@
+extern crate rouille;
+extern crate serde_json;
+use self::rouille::{Request, Response};
+use cb_simulation::api::{ApiCall, ApiRequest, ApiResponse, LogFilter};
+use cb_simulation::log::LogLevel;
+use cb_simulation::time::Duration as SimDuration;
+use std::io::Read;
+use std::sync::Mutex;
+use std::sync::mpsc::{channel, Sender};
+use std::time::Duration;
+
+/// How long to wait for the simulation to answer before giving up,
+/// it usually answers within two frames
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+const DEFAULT_MAX_LOG_ENTRIES: u32 = 100;
+/// Followed by the id of a single household
+const HOUSEHOLD_PREFIX: &str = "/api/households/";
+
+fn param<T: ::std::str::FromStr>(request: &Request, name: &str) -> Result<Option<T>, Response> {
+    match request.get_param(name) {
+        Some(value) => value.parse().map(Some).map_err(|_| {
+            Response::text(format!("Invalid value for {}: {}", name, value)).with_status_code(400)
+        }),
+        None => Ok(None),
+    }
+}
+
+fn body_text(request: &Request) -> Result<String, Response> {
+    let mut text = String::new();
+    match request.data() {
+        Some(mut data) => data.read_to_string(&mut text).map(|_| text).map_err(|err| {
+            Response::text(format!("Couldn't read body: {}", err)).with_status_code(400)
+        }),
+        None => Err(Response::text("Body was already read").with_status_code(500)),
+    }
+}
+
+fn log_filter(request: &Request) -> Result<LogFilter, Response> {
+    let min_level = match request.get_param("level") {
+        Some(level) => Some(match level.as_str() {
+            "debug" => LogLevel::Debug,
+            "info" => LogLevel::Info,
+            "warning" => LogLevel::Warning,
+            "error" => LogLevel::Error,
+            _ => {
+                return Err(
+                    Response::text(format!("Invalid log level: {}", level)).with_status_code(400)
+                )
+            }
+        }),
+        None => None,
+    };
+
+    // a sender id exactly as it appears in log entries
+    let from = match request.get_param("from") {
+        Some(from) => Some(serde_json::from_str(&from).map_err(|err| {
+            Response::text(format!("Invalid sender id: {}", err)).with_status_code(400)
+        })?),
+        None => None,
+    };
+
+    Ok(LogFilter {
+        min_level,
+        topic: request.get_param("topic"),
+        from,
+        within: param::<usize>(request, "within_minutes")?.map(SimDuration::from_minutes),
+    })
+}
+
+/// The API is served on the same address as the UI, so requests that change the city
+/// or the server need the editor token (if the server has one) in an
+/// `Authorization: Bearer <token>` header
+fn require_editor_token(request: &Request, editor_token: &Option<String>) -> Result<(), Response> {
+    match *editor_token {
+        None => Ok(()),
+        Some(ref token) => {
+            let presented = request
+                .header("Authorization")
+                .and_then(|header| header.trim().splitn(2, ' ').nth(1))
+                .map(str::trim);
+            if presented == Some(token.as_str()) {
+                Ok(())
+            } else {
+                Err(Response::text("This endpoint needs the editor token")
+                    .with_status_code(401)
+                    .with_unique_header("WWW-Authenticate", "Bearer"))
+            }
+        }
+    }
+}
+
+fn parse_request(request: &Request, editor_token: &Option<String>) -> Result<ApiRequest, Response> {
+    let url = request.url();
+
+    // the id is a household id exactly as it appears in the household list, URL-encoded
+    if request.method() == "GET" && url.starts_with(HOUSEHOLD_PREFIX) {
+        return serde_json::from_str(&url[HOUSEHOLD_PREFIX.len()..])
+            .map(ApiRequest::GetHousehold)
+            .map_err(|err| {
+                Response::text(format!("Invalid household id: {}", err)).with_status_code(400)
+            });
+    }
+
+    match (request.method(), url.as_str()) {
+        ("GET", "/api/time") => Ok(ApiRequest::GetTime),
+        ("POST", "/api/time/speed") => {
+            require_editor_token(request, editor_token)?;
+            match param::<u16>(request, "speed")? {
+                Some(speed) => Ok(ApiRequest::SetSpeed(speed)),
+                None => Err(Response::text("Missing speed").with_status_code(400)),
+            }
+        }
+        ("GET", "/api/buildings") => Ok(ApiRequest::ListBuildings),
+        ("GET", "/api/households") => Ok(ApiRequest::ListHouseholds),
+        ("GET", "/api/counters") => Ok(ApiRequest::GetCounters),
+        ("POST", "/api/shutdown") => {
+            require_editor_token(request, editor_token)?;
+            Ok(ApiRequest::Shutdown)
+        }
+        ("GET", "/api/log") => Ok(ApiRequest::GetLog {
+            after: param(request, "after")?.unwrap_or(0),
+            max: param(request, "max")?.unwrap_or(DEFAULT_MAX_LOG_ENTRIES),
+            filter: log_filter(request)?,
+        }),
+        // the body is a plan file, as exported from the planning UI
+        ("POST", "/api/projects") => {
+            require_editor_token(request, editor_token)?;
+            Ok(ApiRequest::SubmitProject {
+                source: body_text(request)?,
+                implement: param(request, "implement")?.unwrap_or(false),
+            })
+        }
+        _ => Err(Response::text(format!(
+            "Unknown API endpoint: {} {}",
+            request.method(),
+            request.url()
+        ))
+        .with_status_code(404)),
+    }
+}
+
+fn to_http_response(response: ApiResponse) -> Response {
+    match response {
+        ApiResponse::Time(time_info) => Response::json(&time_info),
+        ApiResponse::Buildings(buildings) => Response::json(&buildings),
+        ApiResponse::Households(households) => Response::json(&households),
+        ApiResponse::Household(core) => Response::json(&core),
+        ApiResponse::Log(entries) => Response::json(&entries),
+        ApiResponse::SubmittedProject(project_info) => Response::json(&project_info),
+        ApiResponse::Counters(counters) => Response::json(&counters),
+        ApiResponse::Snapshot(snapshot) => Response::json(&snapshot),
+        ApiResponse::Done => Response::empty_204(),
+        ApiResponse::NotFound(message) => Response::text(message).with_status_code(404),
+        ApiResponse::BadRequest(message) => Response::text(message).with_status_code(400),
+    }
+}
+
+/// Forwards a request below `/api/` to the simulation and waits for its answer
+pub fn handle_api_request(
+    request: &Request,
+    editor_token: &Option<String>,
+    api_calls: &Mutex<Sender<ApiCall>>,
+) -> Response {
+    let api_request = match parse_request(request, editor_token) {
+        Ok(api_request) => api_request,
+        Err(error_response) => return error_response,
+    };
+
+    let (response_to, response) = channel();
+
+    if api_calls
+        .lock()
+        .expect("API call sender should not be poisoned")
+        .send((api_request, response_to))
+        .is_err()
+    {
+        return Response::text("Simulation is not running").with_status_code(503);
+    }
+
+    match response.recv_timeout(RESPONSE_TIMEOUT) {
+        Ok(api_response) => to_http_response(api_response),
+        Err(_) => Response::text("Simulation didn't respond in time").with_status_code(503),
+    }
+}
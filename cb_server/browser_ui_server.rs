@@ -1,13 +1,30 @@
 extern crate rouille;
 use self::rouille::{Response, extension_to_mime};
+use cb_simulation::api::ApiCall;
+use cb_simulation::parameters::SimulationParameters;
+use std::sync::Mutex;
+use std::sync::mpsc::Sender;
+use metrics::SharedMetrics;
 
 #[derive(RustEmbed)]
 #[folder = "cb_browser_ui/dist/"]
 struct Asset;
 
-pub fn start_browser_ui_server(version: &'static str, network_config: ::init::NetworkConfig) {
+pub fn start_browser_ui_server(
+    version: &'static str,
+    network_config: ::init::NetworkConfig,
+    parameters: SimulationParameters,
+    api_calls: Sender<ApiCall>,
+    metrics: SharedMetrics,
+) {
+    let api_calls = Mutex::new(api_calls);
+
     rouille::start_server(network_config.serve_host_port.clone(), move |request| {
-        if request.raw_url() == "/" {
+        if request.url() == "/metrics" {
+            ::metrics::handle_metrics_request(&metrics, &api_calls)
+        } else if request.url().starts_with("/api/") {
+            ::api_server::handle_api_request(request, &network_config.editor_token, &api_calls)
+        } else if request.raw_url() == "/" {
             println!("{:?} loaded page", request.remote_addr());
 
             let template = ::std::str::from_utf8(
@@ -29,6 +46,14 @@ pub fn start_browser_ui_server(version: &'static str, network_config: ::init::Ne
                 .replace(
                     "CB_SKIP_TURNS_PER_TURN_AHEAD",
                     &format!("{}", network_config.skip_ratio),
+                )
+                .replace(
+                    "CB_EDITOR_TOKEN_REQUIRED",
+                    &format!("{}", network_config.editor_token.is_some()),
+                )
+                .replace(
+                    "CB_TICKS_PER_SIM_SECOND",
+                    &format!("{}", parameters.ticks_per_sim_second),
                 );
 
             Response::html(rendered)
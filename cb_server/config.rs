@@ -0,0 +1,70 @@
+extern crate toml;
+use cb_simulation::parameters::SimulationParameters;
+
+/// Contents of a TOML file passed with `--config`. All settings are optional,
+/// command-line flags take precedence over them.
+///
+/// ```toml
+/// [network]
+/// mode = "lan"
+/// bind = "0.0.0.0:1234"
+/// editor_token = "only-for-teachers"
+///
+/// [server]
+/// max_fps = 60
+/// business_kinds = "my_business_kinds.ron"
+/// snapshot_dir = "saves/my_city"
+/// crash_report_dir = "/var/log/citybound"
+/// log_rotation_dir = "/var/log/citybound"
+///
+/// [simulation]
+/// ticks_per_sim_second = 3
+/// immigration_pace_seconds = 5
+/// microtraffic_unrealistic_slowdown = 4.0
+/// construction_crew_capacity = 3
+/// ```
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    pub network: NetworkSection,
+    pub server: ServerSection,
+    pub simulation: SimulationParameters,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkSection {
+    pub mode: Option<String>,
+    pub bind: Option<String>,
+    pub bind_sim: Option<String>,
+    pub batch_msg_bytes: Option<usize>,
+    pub ok_turn_dist: Option<usize>,
+    pub skip_ratio: Option<usize>,
+    /// Clients need to present this to change anything, all others are spectators
+    pub editor_token: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSection {
+    pub max_fps: Option<usize>,
+    pub import_osm: Option<String>,
+    /// RON file with the kinds of businesses, `business_kinds.ron` by default
+    pub business_kinds: Option<String>,
+    /// Directory the snapshot of the world is saved to when shutting down
+    pub snapshot_dir: Option<String>,
+    /// Directory crash reports are written to, the system's temp dir by default
+    pub crash_report_dir: Option<String>,
+    pub open_crash_report: Option<bool>,
+    /// How many of the newest log entries are kept in memory
+    pub log_capacity: Option<u32>,
+    /// Older log entries are written to files in this directory instead of being dropped,
+    /// all remaining ones when shutting down
+    pub log_rotation_dir: Option<String>,
+}
+
+pub fn load_config_file(path: &str) -> Result<ConfigFile, String> {
+    let source = ::std::fs::read_to_string(path)
+        .map_err(|err| format!("Couldn't read config file {}: {}", path, err))?;
+    toml::from_str(&source).map_err(|err| format!("Invalid config file {}: {}", path, err))
+}
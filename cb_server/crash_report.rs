@@ -0,0 +1,218 @@
+extern crate open;
+extern crate backtrace;
+extern crate serde_json;
+
+use self::backtrace::Backtrace;
+use cb_simulation::api::{ApiCall, ApiRequest, ApiResponse, TimeInfo, LogEntryInfo, LogFilter};
+use std::collections::VecDeque;
+use std::panic::{set_hook, PanicInfo};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How many of the newest log entries are included in a crash report
+const CRASH_REPORT_LOG_ENTRIES: u32 = 50;
+
+/// What the simulation was doing recently. Actors can't be asked anymore once
+/// something panicked, so this is kept up to date while the simulation runs.
+#[derive(Default)]
+pub struct CrashContext {
+    time: Option<TimeInfo>,
+    recent_log: VecDeque<LogEntryInfo>,
+}
+
+pub type SharedCrashContext = Arc<Mutex<CrashContext>>;
+
+impl CrashContext {
+    pub fn new_shared() -> SharedCrashContext {
+        Arc::new(Mutex::new(CrashContext::default()))
+    }
+}
+
+#[derive(Serialize)]
+struct CrashLocation {
+    file: String,
+    line: u32,
+}
+
+#[derive(Serialize)]
+struct CrashReport<'a> {
+    version: &'a str,
+    message: String,
+    location: Option<CrashLocation>,
+    backtrace: String,
+    time: Option<TimeInfo>,
+    recent_log: Vec<LogEntryInfo>,
+}
+
+/// Asks the simulation for the current time and new log entries through the API bridge
+/// and puts the answers into the crash context as soon as they arrive
+pub struct CrashContextUpdater {
+    context: SharedCrashContext,
+    pending_time: Option<Receiver<ApiResponse>>,
+    pending_log: Option<Receiver<ApiResponse>>,
+    next_log_index: u32,
+}
+
+impl CrashContextUpdater {
+    pub fn new(context: SharedCrashContext) -> CrashContextUpdater {
+        CrashContextUpdater {
+            context,
+            pending_time: None,
+            pending_log: None,
+            next_log_index: 0,
+        }
+    }
+
+    /// Called once per frame, before the API bridge processes its requests
+    pub fn update(&mut self, api_calls: &Sender<ApiCall>) {
+        if let Some(ApiResponse::Time(time_info)) = poll(&mut self.pending_time) {
+            self.context
+                .lock()
+                .expect("Crash context should not be poisoned")
+                .time = Some(time_info);
+        }
+
+        if let Some(ApiResponse::Log(entries)) = poll(&mut self.pending_log) {
+            if let Some(last_entry) = entries.last() {
+                self.next_log_index = last_entry.index + 1;
+            }
+
+            let mut context = self
+                .context
+                .lock()
+                .expect("Crash context should not be poisoned");
+            context.recent_log.extend(entries);
+            while context.recent_log.len() > CRASH_REPORT_LOG_ENTRIES as usize {
+                context.recent_log.pop_front();
+            }
+        }
+
+        if self.pending_time.is_none() {
+            self.pending_time = request(api_calls, ApiRequest::GetTime);
+        }
+
+        if self.pending_log.is_none() {
+            self.pending_log = request(
+                api_calls,
+                ApiRequest::GetLog {
+                    after: self.next_log_index,
+                    max: CRASH_REPORT_LOG_ENTRIES,
+                    filter: LogFilter::default(),
+                },
+            );
+        }
+    }
+}
+
+fn request(api_calls: &Sender<ApiCall>, api_request: ApiRequest) -> Option<Receiver<ApiResponse>> {
+    let (response_to, response) = channel();
+    api_calls.send((api_request, response_to)).ok()?;
+    Some(response)
+}
+
+/// Takes the response if it arrived, forgets about the request if it will never be answered
+fn poll(pending: &mut Option<Receiver<ApiResponse>>) -> Option<ApiResponse> {
+    let result = match *pending {
+        Some(ref response) => response.try_recv(),
+        None => return None,
+    };
+
+    match result {
+        Ok(api_response) => {
+            *pending = None;
+            Some(api_response)
+        }
+        Err(TryRecvError::Empty) => None,
+        Err(TryRecvError::Disconnected) => {
+            *pending = None;
+            None
+        }
+    }
+}
+
+/// Replaces the default panic output with a JSON crash report written to `report_dir`,
+/// which is only opened in a viewer if `open_report` is set (never on headless servers)
+pub fn set_error_hook(
+    version: &'static str,
+    report_dir: PathBuf,
+    open_report: bool,
+    context: SharedCrashContext,
+) {
+    set_hook(Box::new(move |panic_info: &PanicInfo| {
+        let title = "SIMULATION BROKE :(";
+
+        let message = match panic_info.payload().downcast_ref::<String>() {
+            Some(string) => string.clone(),
+            None => match panic_info.payload().downcast_ref::<&'static str>() {
+                Some(static_str) => (*static_str).to_string(),
+                None => "Weird error type".to_string(),
+            },
+        };
+
+        let location = panic_info.location().map(|location| CrashLocation {
+            file: location.file().to_owned(),
+            line: location.line(),
+        });
+
+        let backtrace = format!("{:?}", Backtrace::new());
+
+        // the panic might have happened while the context was being updated
+        let (time, recent_log) = match context.try_lock() {
+            Ok(context) => (context.time, context.recent_log.iter().cloned().collect()),
+            Err(_) => (None, Vec::new()),
+        };
+
+        let report = CrashReport {
+            version: version.trim(),
+            message,
+            location,
+            backtrace,
+            time,
+            recent_log,
+        };
+
+        let report_guide = "HOW TO REPORT \
+                            BUGS:\nhttps://github.\
+                            com/citybound/citybound/blob/master/CONTRIBUTING.md#reporting-bugs";
+
+        println!(
+            "{}\n\nWHAT HAPPENED:\n{}\n\nWHERE IT HAPPENED:\n{}\n\nWHERE EXACTLY:\n{}\n\n{}",
+            title,
+            report.message,
+            report
+                .location
+                .as_ref()
+                .map(|location| format!("at {}, line {}", location.file, location.line))
+                .unwrap_or_else(|| "unknown".to_owned()),
+            report.backtrace,
+            report_guide
+        );
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        let report_path = report_dir.join(format!("cb_crash_{}.json", timestamp));
+
+        // nothing in here may panic again, that would abort without any report
+        let written = ::std::fs::create_dir_all(&report_dir)
+            .map_err(|err| err.to_string())
+            .and_then(|_| serde_json::to_string_pretty(&report).map_err(|err| err.to_string()))
+            .and_then(|json| ::std::fs::write(&report_path, json).map_err(|err| err.to_string()));
+
+        match written {
+            Ok(()) => {
+                println!("CRASH REPORT WRITTEN TO {:?}", report_path);
+
+                if open_report {
+                    if let Err(err) = open::that(&report_path) {
+                        println!("Couldn't open crash report: {}", err);
+                    }
+                }
+            }
+            Err(err) => println!("Couldn't write crash report to {:?}: {}", report_path, err),
+        }
+    }));
+}
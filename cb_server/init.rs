@@ -1,8 +1,13 @@
-extern crate open;
-extern crate backtrace;
 extern crate clap;
+extern crate ctrlc;
 
 use std::time::{Instant, Duration};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use cb_simulation::parameters::SimulationParameters;
+use cb_simulation::log::DEFAULT_LOG_CAPACITY;
+use config::{ConfigFile, load_config_file};
 
 pub fn print_start_message(version: &str, network_config: &NetworkConfig) {
     let my_host = format!(
@@ -34,83 +39,196 @@ pub struct NetworkConfig {
     pub batch_msg_bytes: usize,
     pub ok_turn_dist: usize,
     pub skip_ratio: usize,
+    /// If set, only clients that open the UI with `?token=<editor_token>`
+    /// can plan and control time, everybody else is a spectator.
+    /// Changes through the HTTP API need it as `Authorization: Bearer <editor_token>`
+    pub editor_token: Option<String>,
 }
 
-pub fn match_cmd_line_args(version: &str) -> NetworkConfig {
+/// Server settings that aren't about networking
+pub struct ServerConfig {
+    pub max_fps: usize,
+    pub osm_import_path: Option<String>,
+    pub business_kinds_path: String,
+    pub snapshot_dir: String,
+    pub crash_report_dir: PathBuf,
+    pub open_crash_report: bool,
+    pub log_capacity: u32,
+    pub log_rotation_dir: Option<String>,
+    pub simulation: SimulationParameters,
+}
+
+/// Merges command line args, the config file passed with `--config` and the defaults
+/// of the chosen mode, in this order of precedence
+pub fn match_cmd_line_args(version: &str) -> (NetworkConfig, ServerConfig) {
     use self::clap::{Arg, App};
     let matches = App::new("citybound")
         .version(version.trim())
         .author("ae play (Anselm Eickhoff)")
         .about("The city is us.")
+        .arg(
+            Arg::with_name("config")
+                .long("config")
+                .value_name("file.toml")
+                .display_order(0)
+                .help("TOML file with [network], [server] and [simulation] settings"),
+        )
         .arg(
             Arg::with_name("mode")
                 .long("mode")
                 .value_name("local/lan/internet")
-                .display_order(0)
+                .display_order(1)
                 .possible_values(&["local", "lan", "internet"])
-                .default_value("local")
                 .help("Where to expose the simulation. Sets defaults other settings."),
         )
         .arg(
             Arg::with_name("bind")
                 .long("bind")
                 .value_name("host:port")
-                .default_value_ifs(&[
-                    ("mode", Some("local"), "localhost:1234"),
-                    ("mode", Some("lan"), "0.0.0.0:1234"),
-                    ("mode", Some("internet"), "0.0.0.0:1234"),
-                ])
                 .help("Address and port to serve the browser UI from"),
         )
         .arg(
             Arg::with_name("bind-sim")
                 .long("bind-sim")
                 .value_name("host:port")
-                .default_value_ifs(&[
-                    ("mode", Some("local"), "localhost:9999"),
-                    ("mode", Some("lan"), "0.0.0.0:9999"),
-                    ("mode", Some("internet"), "0.0.0.0:9999"),
-                ])
                 .help("Address and port to accept connections to the simulation from"),
         )
         .arg(
             Arg::with_name("batch-msg-b")
                 .long("batch-msg-bytes")
                 .value_name("n-bytes")
-                .default_value("5000")
-                .help("How many bytes of simulation messages to batch"),
+                .help("How many bytes of simulation messages to batch [default: 5000]"),
         )
         .arg(
             Arg::with_name("ok-turn-dist")
                 .long("ok-turn-dist")
                 .value_name("n-turns")
-                .default_value_ifs(&[
-                    ("mode", Some("local"), "2"),
-                    ("mode", Some("lan"), "10"),
-                    ("mode", Some("internet"), "30"),
-                ])
                 .help("How many network turns client/server can be behind before skipping"),
         )
         .arg(
             Arg::with_name("skip-ratio")
                 .long("skip-ratio")
                 .value_name("n-turns")
-                .default_value("5")
-                .help("How many network turns to skip if server/client are ahead"),
+                .help("How many network turns to skip if server/client are ahead [default: 5]"),
+        )
+        .arg(
+            Arg::with_name("editor-token")
+                .long("editor-token")
+                .value_name("token")
+                .help(
+                    "Only clients opening the UI with ?token=<token> (or sending it as a \
+                     bearer token to the API) may make changes",
+                ),
+        )
+        .arg(
+            Arg::with_name("import-osm")
+                .long("import-osm")
+                .value_name("file.osm")
+                .help("OpenStreetMap XML extract to import as a new project on startup"),
+        )
+        .arg(
+            Arg::with_name("open-crash-report")
+                .long("open-crash-report")
+                .help("Open crash reports in the default viewer after writing them"),
         )
         .get_matches();
 
-    NetworkConfig {
-        serve_host_port: matches.value_of("bind").unwrap().to_owned(),
-        bind_sim: matches.value_of("bind-sim").unwrap().to_owned(),
-        mode: matches.value_of("mode").unwrap().to_owned(),
-        batch_msg_bytes: matches.value_of("batch-msg-b").unwrap().parse().unwrap(),
-        ok_turn_dist: matches.value_of("ok-turn-dist").unwrap().parse().unwrap(),
-        skip_ratio: matches.value_of("skip-ratio").unwrap().parse().unwrap(),
-    }
+    let config_file = match matches.value_of("config") {
+        Some(path) => load_config_file(path).unwrap_or_else(|err| {
+            println!("{}", err);
+            ::std::process::exit(1)
+        }),
+        None => ConfigFile::default(),
+    };
+
+    let mode = matches
+        .value_of("mode")
+        .map(|mode| mode.to_owned())
+        .or(config_file.network.mode)
+        .unwrap_or_else(|| "local".to_owned());
+
+    let (default_host, default_ok_turn_dist) = match mode.as_str() {
+        "local" => ("localhost", 2),
+        "lan" => ("0.0.0.0", 10),
+        "internet" => ("0.0.0.0", 30),
+        _ => {
+            println!(
+                "Invalid mode {} in config file, use local, lan or internet",
+                mode
+            );
+            ::std::process::exit(1)
+        }
+    };
+
+    let parsed_arg = |name: &str| {
+        matches.value_of(name).map(|value| {
+            value.parse::<usize>().unwrap_or_else(|_| {
+                println!("--{} expects a number, got {}", name, value);
+                ::std::process::exit(1)
+            })
+        })
+    };
+
+    let network_config = NetworkConfig {
+        serve_host_port: matches
+            .value_of("bind")
+            .map(|bind| bind.to_owned())
+            .or(config_file.network.bind)
+            .unwrap_or_else(|| format!("{}:1234", default_host)),
+        bind_sim: matches
+            .value_of("bind-sim")
+            .map(|bind_sim| bind_sim.to_owned())
+            .or(config_file.network.bind_sim)
+            .unwrap_or_else(|| format!("{}:9999", default_host)),
+        batch_msg_bytes: parsed_arg("batch-msg-b")
+            .or(config_file.network.batch_msg_bytes)
+            .unwrap_or(5000),
+        ok_turn_dist: parsed_arg("ok-turn-dist")
+            .or(config_file.network.ok_turn_dist)
+            .unwrap_or(default_ok_turn_dist),
+        skip_ratio: parsed_arg("skip-ratio")
+            .or(config_file.network.skip_ratio)
+            .unwrap_or(5),
+        editor_token: matches
+            .value_of("editor-token")
+            .map(|token| token.to_owned())
+            .or(config_file.network.editor_token),
+        mode,
+    };
+
+    let server_config = ServerConfig {
+        max_fps: config_file.server.max_fps.unwrap_or(120),
+        osm_import_path: matches
+            .value_of("import-osm")
+            .map(|path| path.to_owned())
+            .or(config_file.server.import_osm),
+        business_kinds_path: config_file
+            .server
+            .business_kinds
+            .unwrap_or_else(|| "business_kinds.ron".to_owned()),
+        snapshot_dir: config_file
+            .server
+            .snapshot_dir
+            .unwrap_or_else(|| "snapshot".to_owned()),
+        crash_report_dir: config_file
+            .server
+            .crash_report_dir
+            .map(PathBuf::from)
+            .unwrap_or_else(::std::env::temp_dir),
+        open_crash_report: matches.is_present("open-crash-report")
+            || config_file.server.open_crash_report.unwrap_or(false),
+        log_capacity: config_file
+            .server
+            .log_capacity
+            .unwrap_or(DEFAULT_LOG_CAPACITY),
+        log_rotation_dir: config_file.server.log_rotation_dir,
+        simulation: config_file.simulation,
+    };
+
+    (network_config, server_config)
 }
 
-pub fn ensure_crossplatform_proper_thread<F: Fn() -> () + Send + 'static>(callback: F) {
+pub fn ensure_crossplatform_proper_thread<F: FnOnce() -> () + Send + 'static>(callback: F) {
     // Makes sure that:
     // a) on Windows we use a dummy thread with manually set stack size
     // b) on Mac/Linux we use the main thread, because we have to create the UI there
@@ -126,61 +244,19 @@ pub fn ensure_crossplatform_proper_thread<F: Fn() -> () + Send + 'static>(callba
     }
 }
 
-use std::panic::{set_hook, PanicInfo};
-use self::backtrace::Backtrace;
-use std::fs::File;
-use std::io::Write;
-
-pub fn set_error_hook() {
-    let callback: Box<FnMut(&PanicInfo)> = Box::new(move |panic_info| {
-        let title = "SIMULATION BROKE :(";
-
-        let message = match panic_info.payload().downcast_ref::<String>() {
-            Some(string) => (string.clone()),
-            None => match panic_info.payload().downcast_ref::<&'static str>() {
-                Some(static_str) => (*static_str).to_string(),
-                None => "Weird error type".to_string(),
-            },
-        };
-
-        let backtrace = Backtrace::new();
-        let location = format!(
-            "at {}, line {}",
-            panic_info.location().map(|l| l.file()).unwrap_or("unknown"),
-            panic_info.location().map(|l| l.line()).unwrap_or(0)
-        );
-
-        let body = format!(
-            "WHAT HAPPENED:\n{}\n\nWHERE IT HAPPENED:\n{}\n\nWHERE EXACTLY:\n{:?}",
-            message, location, backtrace
-        );
-
-        let report_guide = "HOW TO REPORT \
-                            BUGS:\nhttps://github.\
-                            com/citybound/citybound/blob/master/CONTRIBUTING.md#reporting-bugs";
-
-        let mut error_file_path = ::std::env::temp_dir();
-        error_file_path.push("cb_last_error.txt");
-
-        println!(
-            "{}\n\n{}\n\nALSO SEE {:?} (AUTO-OPENED)",
-            title, body, error_file_path
-        );
-
-        {
-            if let Ok(mut file) = File::create(&error_file_path) {
-                let file_content = format!("{}\n\n{}\n\n{}", title, report_guide, body);
-                let file_content = file_content.replace("\n", "\r\n");
-
-                file.write_all(file_content.as_bytes())
-                    .expect("Error writing error file, lol");
-            };
-        }
+/// Returns a flag that is set on Ctrl-C or when the process is asked to terminate,
+/// so the simulation can shut down gracefully instead of being killed
+pub fn handle_shutdown_signals() -> Arc<AtomicBool> {
+    let shutdown_requested = Arc::new(AtomicBool::new(false));
+    let shutdown_requested_2 = shutdown_requested.clone();
 
-        open::that(error_file_path).expect("Couldn't open error file");
-    });
+    ctrlc::set_handler(move || {
+        println!("Shutting down after this turn...");
+        shutdown_requested_2.store(true, Ordering::SeqCst);
+    })
+    .expect("Couldn't set up handling of shutdown signals");
 
-    set_hook(unsafe { ::std::mem::transmute(callback) });
+    shutdown_requested
 }
 
 pub struct FrameCounter {
@@ -197,8 +273,7 @@ impl FrameCounter {
     }
 
     pub fn start_frame(&mut self) {
-        let elapsed_ms = self.last_frame.elapsed().as_secs() as f32 * 1000.0
-            + self.last_frame.elapsed().subsec_nanos() as f32 / 10.0E5;
+        let elapsed_ms = self.current_frame_ms();
 
         self.elapsed_ms_collected.push(elapsed_ms);
 
@@ -209,6 +284,13 @@ impl FrameCounter {
         self.last_frame = Instant::now();
     }
 
+    /// Time spent since the start of the current frame, before sleeping this is
+    /// how long the simulation took for the frame
+    pub fn current_frame_ms(&self) -> f32 {
+        let elapsed = self.last_frame.elapsed();
+        elapsed.as_secs() as f32 * 1000.0 + elapsed.subsec_nanos() as f32 / 10.0E5
+    }
+
     pub fn sleep_if_faster_than(&self, fps: usize) {
         let ideal_frame_duration = Duration::from_millis((1000.0 / (fps as f32)) as u64);
 
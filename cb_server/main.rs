@@ -1,24 +1,50 @@
 extern crate cb_simulation;
 use cb_simulation::*;
+use cb_simulation::economy::households::household_kinds::generic_business::{
+    load_business_kinds, set_business_kinds,
+};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[macro_use]
 extern crate rust_embed_flag;
+#[macro_use]
+extern crate serde_derive;
 
 const VERSION: &str = include_str!("../.version");
 
 mod init;
+mod config;
 mod browser_ui_server;
+mod api_server;
+mod metrics;
+mod snapshot;
+mod crash_report;
 
 fn main() {
-    let network_config = init::match_cmd_line_args(VERSION);
+    let (network_config, server_config) = init::match_cmd_line_args(VERSION);
 
     init::print_start_message(VERSION, &network_config);
 
     let network_config_2 = network_config.clone();
+    let parameters = server_config.simulation;
+    time::set_ticks_per_sim_second(parameters.ticks_per_sim_second);
+    let (api_calls, api_calls_receiver) = ::std::sync::mpsc::channel();
+    let metrics = metrics::Metrics::new_shared();
+    let metrics_2 = metrics.clone();
+    let api_calls_2 = api_calls.clone();
     ::std::thread::spawn(move || {
-        browser_ui_server::start_browser_ui_server(VERSION, network_config_2);
+        browser_ui_server::start_browser_ui_server(
+            VERSION,
+            network_config_2,
+            parameters,
+            api_calls_2,
+            metrics_2,
+        );
     });
 
+    let shutdown_requested = init::handle_shutdown_signals();
+
     init::ensure_crossplatform_proper_thread(move || {
         let mut system = Box::new(kay::ActorSystem::new(kay::Networking::new(
             0,
@@ -27,23 +53,63 @@ fn main() {
             network_config.ok_turn_dist,
             network_config.skip_ratio,
         )));
-        init::set_error_hook();
+        let crash_context = crash_report::CrashContext::new_shared();
+        crash_report::set_error_hook(
+            VERSION,
+            server_config.crash_report_dir.clone(),
+            server_config.open_crash_report,
+            crash_context.clone(),
+        );
 
         setup_common(&mut system);
         system.networking_connect();
 
         let world = &mut system.world();
+        let parameters = &server_config.simulation;
+        let access = access::AccessControl::new(network_config.editor_token.clone());
+
+        match load_business_kinds(&server_config.business_kinds_path) {
+            Ok(business_kinds) => set_business_kinds(business_kinds),
+            Err(err) => {
+                println!("{}", err);
+                ::std::process::exit(1)
+            }
+        }
 
-        log::spawn(world);
-        let time = time::spawn(world);
-        let plan_manager = planning::spawn(world);
-        construction::spawn(world);
+        log::spawn(
+            world,
+            server_config.log_capacity,
+            server_config.log_rotation_dir.clone(),
+        );
+        let fast_forwarding = Arc::new(AtomicBool::new(false));
+        let time = time::spawn(world, parameters, &access, fast_forwarding.clone());
+        let plan_manager = planning::spawn(world, &access);
+        construction::spawn(world, parameters);
         transport::spawn(world, time);
-        economy::spawn(world, time, plan_manager);
+        economy::spawn(world, time, plan_manager, parameters);
         environment::vegetation::spawn(world, plan_manager);
+        environment::water::spawn(world, plan_manager);
+        heatmaps::spawn(world);
+        let api_bridge = api::spawn(
+            world,
+            api_calls_receiver,
+            shutdown_requested.clone(),
+            time,
+            plan_manager,
+            &access,
+        );
         system.process_all_messages();
 
+        if let Some(ref osm_import_path) = server_config.osm_import_path {
+            match ::std::fs::read_to_string(osm_import_path) {
+                Ok(source) => plan_manager.import_osm(source.into(), world),
+                Err(err) => println!("Couldn't read {}: {}", osm_import_path, err),
+            }
+            system.process_all_messages();
+        }
+
         let mut frame_counter = init::FrameCounter::new();
+        let mut crash_context_updater = crash_report::CrashContextUpdater::new(crash_context);
         let mut skip_turns = 0;
 
         loop {
@@ -51,10 +117,15 @@ fn main() {
 
             system.process_all_messages();
 
-            if system.shutting_down {
+            // the last turn is finished, no further turns are exchanged with clients
+            if system.shutting_down || shutdown_requested.load(Ordering::SeqCst) {
                 break;
             }
 
+            crash_context_updater.update(&api_calls);
+            api_bridge.process_requests(world);
+            system.process_all_messages();
+
             if skip_turns == 0 {
                 time.progress(world);
                 system.process_all_messages();
@@ -63,16 +134,47 @@ fn main() {
             system.networking_send_and_receive();
             system.process_all_messages();
 
+            let mut newly_skipped_turns = 0;
+
             if skip_turns > 0 {
                 skip_turns -= 1;
             } else {
                 let maybe_should_skip = system.networking_finish_turn();
                 if let Some(should_skip) = maybe_should_skip {
                     skip_turns = should_skip.min(100);
+                    newly_skipped_turns = skip_turns;
                 }
             }
 
-            frame_counter.sleep_if_faster_than(120);
+            metrics
+                .lock()
+                .expect("Metrics should not be poisoned")
+                .record_frame(
+                    &mut system,
+                    frame_counter.current_frame_ms(),
+                    newly_skipped_turns,
+                );
+
+            // while running until an instant, the simulation goes as fast as it can
+            if !fast_forwarding.load(Ordering::SeqCst) {
+                frame_counter.sleep_if_faster_than(server_config.max_fps);
+            }
+        }
+
+        println!("Saving snapshot to {}...", server_config.snapshot_dir);
+        match snapshot::save_snapshot(
+            &mut system,
+            api_bridge,
+            &api_calls,
+            &server_config.snapshot_dir,
+        ) {
+            Ok(()) => println!("Saved."),
+            Err(err) => println!("Couldn't save snapshot: {}", err),
         }
+
+        // entries not rotated out yet would be lost otherwise
+        log::LogID::local_first(world).flush(world);
+        system.process_all_messages();
+        println!("Bye!");
     });
 }
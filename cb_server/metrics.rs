@@ -0,0 +1,269 @@
+extern crate rouille;
+use self::rouille::Response;
+use cb_simulation::kay::ActorSystem;
+use cb_simulation::api::{ApiCall, ApiRequest, ApiResponse, SimulationCounters};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, Sender};
+use std::time::Duration;
+
+/// Percentiles of frame durations are calculated over this many recent frames
+const FRAME_DURATION_WINDOW: usize = 1000;
+const COUNTERS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Health of the simulation loop, updated by it every frame and
+/// rendered in the Prometheus text format on every scrape
+#[derive(Default)]
+pub struct Metrics {
+    recent_frame_durations_ms: VecDeque<f32>,
+    frame_duration_ms_sum: f64,
+    n_frames: u64,
+    n_messages_last_turn: usize,
+    n_messages_total: u64,
+    instance_counts: HashMap<String, usize>,
+    networking_turns: Vec<i64>,
+    n_skipped_turns_total: u64,
+}
+
+pub type SharedMetrics = Arc<Mutex<Metrics>>;
+
+impl Metrics {
+    pub fn new_shared() -> SharedMetrics {
+        Arc::new(Mutex::new(Metrics::default()))
+    }
+
+    /// Records the message statistics of the turn and resets them for the next one
+    pub fn record_frame(
+        &mut self,
+        system: &mut ActorSystem,
+        frame_duration_ms: f32,
+        n_skipped_turns: usize,
+    ) {
+        self.recent_frame_durations_ms.push_back(frame_duration_ms);
+        if self.recent_frame_durations_ms.len() > FRAME_DURATION_WINDOW {
+            self.recent_frame_durations_ms.pop_front();
+        }
+        self.frame_duration_ms_sum += f64::from(frame_duration_ms);
+        self.n_frames += 1;
+
+        self.n_messages_last_turn = system.get_message_statistics().values().sum();
+        self.n_messages_total += self.n_messages_last_turn as u64;
+        system.reset_message_statistics();
+
+        self.instance_counts = system.get_instance_counts();
+        self.networking_turns = system
+            .networking_debug_all_n_turns()
+            .iter()
+            .map(|n_turns| *n_turns as i64)
+            .collect();
+        self.n_skipped_turns_total += n_skipped_turns as u64;
+    }
+
+    fn frame_duration_percentile_ms(&self, percentile: f32) -> f32 {
+        let mut sorted = self
+            .recent_frame_durations_ms
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        sorted.sort_by(|a, b| {
+            a.partial_cmp(b)
+                .expect("Frame durations should be comparable")
+        });
+
+        if sorted.is_empty() {
+            0.0
+        } else {
+            sorted[((sorted.len() - 1) as f32 * percentile).round() as usize]
+        }
+    }
+
+    fn render(&self, maybe_counters: Option<SimulationCounters>) -> String {
+        let mut out = String::new();
+
+        write_metric(
+            &mut out,
+            "frame_duration_seconds",
+            "summary",
+            "Time the simulation loop spent per frame, without sleeping",
+            [0.5, 0.9, 0.99]
+                .iter()
+                .map(|percentile| {
+                    (
+                        format!("{{quantile=\"{}\"}}", percentile),
+                        format!(
+                            "{}",
+                            self.frame_duration_percentile_ms(*percentile) / 1000.0
+                        ),
+                    )
+                })
+                .chain(vec![
+                    (
+                        "_sum".to_owned(),
+                        format!("{}", self.frame_duration_ms_sum / 1000.0),
+                    ),
+                    ("_count".to_owned(), format!("{}", self.n_frames)),
+                ])
+                .collect(),
+        );
+
+        write_metric(
+            &mut out,
+            "messages_last_turn",
+            "gauge",
+            "Messages processed during the last turn",
+            vec![(String::new(), format!("{}", self.n_messages_last_turn))],
+        );
+
+        write_metric(
+            &mut out,
+            "messages_total",
+            "counter",
+            "Messages processed since the server started",
+            vec![(String::new(), format!("{}", self.n_messages_total))],
+        );
+
+        let mut instance_counts = self.instance_counts.iter().collect::<Vec<_>>();
+        instance_counts.sort();
+        write_metric(
+            &mut out,
+            "actor_instances",
+            "gauge",
+            "Actor instances per registered actor type",
+            instance_counts
+                .into_iter()
+                .map(|(actor_type, count)| {
+                    (
+                        format!("{{type=\"{}\"}}", escape_label_value(actor_type)),
+                        format!("{}", count),
+                    )
+                })
+                .collect(),
+        );
+
+        write_metric(
+            &mut out,
+            "networking_turn",
+            "gauge",
+            "Turn each connected machine is at, as known to the server",
+            self.networking_turns
+                .iter()
+                .enumerate()
+                .map(|(machine, n_turns)| {
+                    (
+                        format!("{{machine=\"{}\"}}", machine),
+                        format!("{}", n_turns),
+                    )
+                })
+                .collect(),
+        );
+
+        let own_turn = self.networking_turns.get(0).cloned().unwrap_or(0);
+        write_metric(
+            &mut out,
+            "networking_turn_distance",
+            "gauge",
+            "How many turns the server is ahead of the slowest connected machine",
+            vec![(
+                String::new(),
+                format!(
+                    "{}",
+                    self.networking_turns
+                        .iter()
+                        .skip(1)
+                        .filter(|n_turns| **n_turns > 0)
+                        .map(|n_turns| own_turn - n_turns)
+                        .max()
+                        .unwrap_or(0)
+                ),
+            )],
+        );
+
+        write_metric(
+            &mut out,
+            "networking_skipped_turns_total",
+            "counter",
+            "Turns skipped to let slower machines catch up",
+            vec![(String::new(), format!("{}", self.n_skipped_turns_total))],
+        );
+
+        if let Some(counters) = maybe_counters {
+            for (name, help, value) in &[
+                (
+                    "households",
+                    "Households living or working in buildings",
+                    counters.n_households,
+                ),
+                ("cars", "Cars on lanes", counters.n_cars),
+                (
+                    "active_trips",
+                    "Trips that currently have a car on a lane",
+                    counters.n_active_trips,
+                ),
+            ] {
+                write_metric(
+                    &mut out,
+                    name,
+                    "gauge",
+                    help,
+                    vec![(String::new(), format!("{}", value))],
+                );
+            }
+        }
+
+        out
+    }
+}
+
+/// Samples are pairs of labels (or a suffix like `_sum`) and values
+fn write_metric(
+    out: &mut String,
+    name: &str,
+    kind: &str,
+    help: &str,
+    samples: Vec<(String, String)>,
+) {
+    writeln!(out, "# HELP citybound_{} {}", name, help).unwrap();
+    writeln!(out, "# TYPE citybound_{} {}", name, kind).unwrap();
+    for (labels_or_suffix, value) in samples {
+        writeln!(out, "citybound_{}{} {}", name, labels_or_suffix, value).unwrap();
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Renders the loop metrics together with domain counters queried from the simulation.
+/// If the simulation doesn't answer in time, the domain counters are left out.
+pub fn handle_metrics_request(
+    metrics: &SharedMetrics,
+    api_calls: &Mutex<Sender<ApiCall>>,
+) -> Response {
+    let (response_to, response) = channel();
+
+    let sent = api_calls
+        .lock()
+        .expect("API call sender should not be poisoned")
+        .send((ApiRequest::GetCounters, response_to))
+        .is_ok();
+
+    let maybe_counters = if sent {
+        match response.recv_timeout(COUNTERS_TIMEOUT) {
+            Ok(ApiResponse::Counters(counters)) => Some(counters),
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    let rendered = metrics
+        .lock()
+        .expect("Metrics should not be poisoned")
+        .render(maybe_counters);
+
+    Response::from_data("text/plain; version=0.0.4", rendered)
+}
@@ -0,0 +1,46 @@
+extern crate serde_json;
+use cb_simulation::kay::ActorSystem;
+use cb_simulation::api::{ApiBridgeID, ApiCall, ApiRequest, ApiResponse};
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc::{channel, Sender};
+
+/// Asks the simulation for a snapshot of the whole world and writes it to `dir`: the master plan
+/// as a plan file, which can be submitted as a project to rebuild the city, and the state of all
+/// actors, the time and the whole log as JSON
+pub fn save_snapshot(
+    system: &mut ActorSystem,
+    api_bridge: ApiBridgeID,
+    api_calls: &Sender<ApiCall>,
+    dir: &str,
+) -> Result<(), String> {
+    let (response_to, response) = channel();
+    api_calls
+        .send((ApiRequest::GetSnapshot, response_to))
+        .map_err(|_| "API bridge is gone".to_owned())?;
+
+    // the first round sends out the queries, the second one responds with their answers
+    for _ in 0..2 {
+        let world = &mut system.world();
+        api_bridge.process_requests(world);
+        system.process_all_messages();
+    }
+
+    let snapshot = match response.try_recv() {
+        Ok(ApiResponse::Snapshot(snapshot)) => snapshot,
+        Ok(ApiResponse::NotFound(message)) => return Err(message),
+        _ => return Err("Simulation didn't respond with a snapshot".to_owned()),
+    };
+
+    let dir = Path::new(dir);
+    fs::create_dir_all(dir).map_err(|err| format!("Couldn't create {:?}: {}", dir, err))?;
+
+    let master_plan_path = dir.join("master_plan.ron");
+    fs::write(&master_plan_path, &snapshot.master_plan)
+        .map_err(|err| format!("Couldn't write {:?}: {}", master_plan_path, err))?;
+
+    let snapshot_path = dir.join("snapshot.json");
+    let json = serde_json::to_string_pretty(&snapshot).expect("Snapshot should be serializable");
+    fs::write(&snapshot_path, json)
+        .map_err(|err| format!("Couldn't write {:?}: {}", snapshot_path, err))
+}
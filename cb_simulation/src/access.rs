@@ -0,0 +1,59 @@
+use compact::{COption, CString};
+use util::random::{thread_rng, RngCore};
+
+/// Presented by clients with every message that changes the city or the simulation,
+/// spectators don't have one
+pub type EditorToken = COption<CString>;
+
+/// Decides which clients may change things, based on an editor token set on the server.
+/// Messages don't say which client sent them, so spectators are simply the clients
+/// that can't present the right token.
+#[derive(Compact, Clone)]
+pub struct AccessControl {
+    editor_token: EditorToken,
+}
+
+impl AccessControl {
+    /// Without an editor token, every client may change things
+    pub fn new(editor_token: Option<String>) -> AccessControl {
+        AccessControl {
+            editor_token: COption(editor_token.map(CString::from)),
+        }
+    }
+
+    /// For messages sent on behalf of the server itself
+    pub fn editor_token(&self) -> EditorToken {
+        self.editor_token.clone()
+    }
+
+    pub fn permits(&self, presented: &EditorToken) -> bool {
+        match self.editor_token.0 {
+            None => true,
+            Some(ref token) => presented
+                .0
+                .as_ref()
+                .map(|presented| **presented == **token)
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// Proves that a message was sent by the simulation itself, for messages that no client
+/// should send, like paying taxes. Every process draws its own key, so clients never
+/// know the one of the server.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct InternalKey(u64);
+
+thread_local! {
+    static INTERNAL_KEY: InternalKey = InternalKey(thread_rng().next_u64());
+}
+
+impl InternalKey {
+    pub fn get() -> InternalKey {
+        INTERNAL_KEY.with(|key| *key)
+    }
+
+    pub fn is_valid(self) -> bool {
+        self == InternalKey::get()
+    }
+}
@@ -0,0 +1,139 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for ApiBridge {
+    type ID = ApiBridgeID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct ApiBridgeID {
+    _raw_id: RawID
+}
+
+impl TypedID for ApiBridgeID {
+    type Target = ApiBridge;
+
+    fn from_raw(id: RawID) -> Self {
+        ApiBridgeID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl ApiBridgeID {
+    pub fn spawn(calls: External < Receiver < ApiCall > >, shutdown_requested: External < Arc < AtomicBool > >, time: TimeID, plan_manager: PlanManagerID, access: AccessControl, world: &mut World) -> Self {
+        let id = ApiBridgeID::from_raw(world.allocate_instance_id::<ApiBridge>());
+        let swarm = world.local_broadcast::<ApiBridge>();
+        world.send(swarm, MSG_ApiBridge_spawn(id, calls, shutdown_requested, time, plan_manager, access));
+        id
+    }
+    
+    pub fn process_requests(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_ApiBridge_process_requests());
+    }
+    
+    pub fn request_shutdown(self, token: EditorToken, world: &mut World) {
+        world.send(self.as_raw(), MSG_ApiBridge_request_shutdown(token));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ApiBridge_spawn(pub ApiBridgeID, pub External < Receiver < ApiCall > >, pub External < Arc < AtomicBool > >, pub TimeID, pub PlanManagerID, pub AccessControl);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ApiBridge_process_requests();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ApiBridge_request_shutdown(pub EditorToken);
+
+impl Into<TimeUIID> for ApiBridgeID {
+    fn into(self) -> TimeUIID {
+        TimeUIID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<LandUseUIID> for ApiBridgeID {
+    fn into(self) -> LandUseUIID {
+        LandUseUIID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<HouseholdUIID> for ApiBridgeID {
+    fn into(self) -> HouseholdUIID {
+        HouseholdUIID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TransportUIID> for ApiBridgeID {
+    fn into(self) -> TransportUIID {
+        TransportUIID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TreasuryUIID> for ApiBridgeID {
+    fn into(self) -> TreasuryUIID {
+        TreasuryUIID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<InspectorID> for ApiBridgeID {
+    fn into(self) -> InspectorID {
+        InspectorID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<PlanningUIID> for ApiBridgeID {
+    fn into(self) -> PlanningUIID {
+        PlanningUIID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<LogRecipientID> for ApiBridgeID {
+    fn into(self) -> LogRecipientID {
+        LogRecipientID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    TimeUIID::register_implementor::<ApiBridge>(system);
+    LandUseUIID::register_implementor::<ApiBridge>(system);
+    HouseholdUIID::register_implementor::<ApiBridge>(system);
+    TransportUIID::register_implementor::<ApiBridge>(system);
+    TreasuryUIID::register_implementor::<ApiBridge>(system);
+    InspectorID::register_implementor::<ApiBridge>(system);
+    PlanningUIID::register_implementor::<ApiBridge>(system);
+    LogRecipientID::register_implementor::<ApiBridge>(system);
+    system.add_spawner::<ApiBridge, _, _>(
+        |&MSG_ApiBridge_spawn(id, ref calls, ref shutdown_requested, time, plan_manager, ref access), world| {
+            ApiBridge::spawn(id, calls, shutdown_requested, time, plan_manager, access, world)
+        }, false
+    );
+    
+    system.add_handler::<ApiBridge, _, _>(
+        |&MSG_ApiBridge_process_requests(), instance, world| {
+            instance.process_requests(world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ApiBridge, _, _>(
+        |&MSG_ApiBridge_request_shutdown(ref token), instance, world| {
+            instance.request_shutdown(token, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,677 @@
+use kay::{World, ActorSystem, RawID, External};
+use compact::{CVec, CString, CHashMap, COption};
+use descartes::{V2, LinePath};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, Sender};
+use time::{TimeID, Instant, TimeOfDay, Duration, TimeControl};
+use time::ui::{TimeUI, TimeUIID};
+use transport::lane::{LaneID, SwitchLaneID};
+use transport::ui::{TransportUI, TransportUIID, CarRenderInfo};
+use transport::pathfinding::trip::TripID;
+use log::{LogID, LogLevel, LogQuery, LogRecipient, LogRecipientID, Entry};
+use land_use::buildings::{BuildingID, BuildingStyle};
+use land_use::zone_planning::Lot;
+use land_use::ui::{LandUseUI, LandUseUIID};
+use economy::households::{HouseholdID, HouseholdCore};
+use economy::households::ui::{HouseholdUI, HouseholdUIID};
+use economy::treasury::{TreasuryID, TreasuryReport, TreasuryUI, TreasuryUIID};
+use inspection::{InspectableID, Inspector, InspectorID, StateSummary};
+use planning::{PlanManagerID, ProjectID, SessionID, PlanHistory, PlanHistoryUpdate, ProjectUpdate,
+PlanResultUpdate, ActionGroups, GestureID};
+use planning::ui::{PlanningUI, PlanningUIID};
+use planning::timeline::ImplementedProjectInfo;
+use planning::exchange::{PlanFile, PlanTransform};
+use access::{AccessControl, EditorToken};
+
+use log::{info, warn};
+const LOG_T: &str = "API";
+
+// Requests reach the simulation from the thread of the HTTP server through a channel.
+// Answers to the queries sent out for them are collected while the simulation
+// processes its messages and are responded with at the beginning of the next frame.
+
+pub enum ApiRequest {
+    GetTime,
+    SetSpeed(u16),
+    ListBuildings,
+    ListHouseholds,
+    GetHousehold(HouseholdID),
+    /// Entries matching the filter with at least index `after`, but at most the newest `max`
+    GetLog {
+        after: u32,
+        max: u32,
+        filter: LogFilter,
+    },
+    /// A plan file in the same format as exported by the planning UI
+    SubmitProject {
+        source: String,
+        implement: bool,
+    },
+    GetCounters,
+    /// Everything that is saved when the server shuts down
+    GetSnapshot,
+    Shutdown,
+}
+
+#[derive(Copy, Clone, Serialize)]
+pub struct TimeInfo {
+    pub ticks: u32,
+    pub hours: u8,
+    pub minutes: u8,
+    pub speed: u16,
+    pub control: TimeControl,
+}
+
+#[derive(Clone, Serialize)]
+pub struct BuildingInfo {
+    pub id: BuildingID,
+    pub style: BuildingStyle,
+    pub households: Vec<HouseholdID>,
+}
+
+#[derive(Copy, Clone, Serialize)]
+pub struct HouseholdInfo {
+    pub id: HouseholdID,
+    pub building: BuildingID,
+}
+
+/// The same as the conditions of a `LogQuery`, but can be sent between threads
+#[derive(Clone, Default)]
+pub struct LogFilter {
+    pub min_level: Option<LogLevel>,
+    pub topic: Option<String>,
+    pub from: Option<RawID>,
+    pub within: Option<Duration>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct LogEntryInfo {
+    pub index: u32,
+    pub ticks: u32,
+    pub from: Option<RawID>,
+    pub level: LogLevel,
+    pub topic: String,
+    pub message: String,
+}
+
+#[derive(Copy, Clone, Serialize)]
+pub struct SimulationCounters {
+    pub n_households: usize,
+    pub n_cars: usize,
+    /// Trips that currently have a car on a lane
+    pub n_active_trips: usize,
+}
+
+#[derive(Copy, Clone, Serialize)]
+pub struct SubmittedProjectInfo {
+    pub project_id: ProjectID,
+    pub implemented: bool,
+}
+
+#[derive(Clone, Serialize)]
+pub struct HouseholdSnapshot {
+    pub id: HouseholdID,
+    pub core: HouseholdCore,
+}
+
+#[derive(Copy, Clone, Serialize)]
+pub struct CarSnapshot {
+    pub lane: RawID,
+    pub position: [f32; 2],
+    pub direction: [f32; 2],
+    pub trip: TripID,
+}
+
+#[derive(Clone, Serialize)]
+pub struct ActorSnapshot {
+    pub id: RawID,
+    pub summary: StateSummary,
+}
+
+/// The state of the whole world at one instant. The master plan can be submitted as a project
+/// again to rebuild the city, the state of all actors is recorded for looking into it afterwards:
+/// households and the treasury completely, cars with their trips, and all inspectable actors
+/// (lanes, buildings, lots, plants, trips, construction) by their state summaries
+#[derive(Clone, Serialize)]
+pub struct WorldSnapshot {
+    pub time: Option<TimeInfo>,
+    /// A plan file, saved to its own file
+    #[serde(skip)]
+    pub master_plan: String,
+    pub treasury: Option<TreasuryReport>,
+    pub households: Vec<HouseholdSnapshot>,
+    pub cars: Vec<CarSnapshot>,
+    pub actors: Vec<ActorSnapshot>,
+    pub log: Vec<LogEntryInfo>,
+}
+
+pub enum ApiResponse {
+    Time(TimeInfo),
+    Buildings(Vec<BuildingInfo>),
+    Households(Vec<HouseholdInfo>),
+    Household(HouseholdCore),
+    Log(Vec<LogEntryInfo>),
+    SubmittedProject(SubmittedProjectInfo),
+    Counters(SimulationCounters),
+    Snapshot(WorldSnapshot),
+    Done,
+    NotFound(String),
+    BadRequest(String),
+}
+
+pub type ApiCall = (ApiRequest, Sender<ApiResponse>);
+
+#[derive(Compact, Clone)]
+pub struct ApiBridge {
+    id: ApiBridgeID,
+    time: TimeID,
+    plan_manager: PlanManagerID,
+    /// The HTTP server only lets requests that change things through if they
+    /// present the editor token, so the bridge acts with it
+    access: AccessControl,
+    calls: External<Receiver<ApiCall>>,
+    /// Checked by the simulation loop, which then finishes the current turn and shuts down
+    shutdown_requested: External<Arc<AtomicBool>>,
+    state: External<ApiBridgeNonPersistedState>,
+}
+
+impl ::std::ops::Deref for ApiBridge {
+    type Target = ApiBridgeNonPersistedState;
+
+    fn deref(&self) -> &ApiBridgeNonPersistedState {
+        &self.state
+    }
+}
+
+impl ::std::ops::DerefMut for ApiBridge {
+    fn deref_mut(&mut self) -> &mut ApiBridgeNonPersistedState {
+        &mut self.state
+    }
+}
+
+pub struct ApiBridgeNonPersistedState {
+    /// All projects submitted through the API are authored by this session
+    session_id: SessionID,
+    waiting_for_time: Vec<Sender<ApiResponse>>,
+    waiting_for_buildings: Vec<(ApiRequest, Sender<ApiResponse>)>,
+    waiting_for_households: Vec<(HouseholdID, Sender<ApiResponse>)>,
+    /// Each log request and snapshot gets its own query, answered under this id
+    waiting_for_log: Vec<(u32, Sender<ApiResponse>)>,
+    waiting_for_counters: Vec<Sender<ApiResponse>>,
+    waiting_for_snapshot: Vec<(u32, Sender<ApiResponse>)>,
+    next_log_query_id: u32,
+    received_time: Option<TimeInfo>,
+    received_buildings: Vec<BuildingInfo>,
+    received_households: HashMap<HouseholdID, HouseholdCore>,
+    received_log: HashMap<u32, Vec<LogEntryInfo>>,
+    received_n_cars: usize,
+    received_trips: HashSet<TripID>,
+    received_cars: Vec<CarSnapshot>,
+    received_master_plan: Option<String>,
+    received_treasury: Option<TreasuryReport>,
+    received_summaries: Vec<ActorSnapshot>,
+}
+
+impl ApiBridge {
+    pub fn spawn(
+        id: ApiBridgeID,
+        calls: &External<Receiver<ApiCall>>,
+        shutdown_requested: &External<Arc<AtomicBool>>,
+        time: TimeID,
+        plan_manager: PlanManagerID,
+        access: &AccessControl,
+        _: &mut World,
+    ) -> ApiBridge {
+        ApiBridge {
+            id,
+            time,
+            plan_manager,
+            access: access.clone(),
+            calls: calls.steal(),
+            shutdown_requested: shutdown_requested.steal(),
+            state: External::new(ApiBridgeNonPersistedState {
+                session_id: SessionID::new(),
+                waiting_for_time: Vec::new(),
+                waiting_for_buildings: Vec::new(),
+                waiting_for_households: Vec::new(),
+                waiting_for_log: Vec::new(),
+                waiting_for_counters: Vec::new(),
+                waiting_for_snapshot: Vec::new(),
+                next_log_query_id: 0,
+                received_time: None,
+                received_buildings: Vec::new(),
+                received_households: HashMap::new(),
+                received_log: HashMap::new(),
+                received_n_cars: 0,
+                received_trips: HashSet::new(),
+                received_cars: Vec::new(),
+                received_master_plan: None,
+                received_treasury: None,
+                received_summaries: Vec::new(),
+            }),
+        }
+    }
+
+    /// Called once per frame: responds to the requests of the last frame
+    /// and sends out queries for all newly arrived requests
+    pub fn process_requests(&mut self, world: &mut World) {
+        self.respond_to_waiting();
+
+        let new_calls = self.calls.try_iter().collect::<Vec<_>>();
+
+        for (request, response_to) in new_calls {
+            match request {
+                ApiRequest::GetTime => {
+                    self.time.get_info(self.id.into(), world);
+                    self.waiting_for_time.push(response_to);
+                }
+                ApiRequest::SetSpeed(speed) => {
+                    self.time
+                        .set_speed(speed, self.access.editor_token(), world);
+                    let _ = response_to.send(ApiResponse::Done);
+                }
+                request @ ApiRequest::ListBuildings | request @ ApiRequest::ListHouseholds => {
+                    self.query_buildings_if_not_yet(world);
+                    self.waiting_for_buildings.push((request, response_to));
+                }
+                ApiRequest::GetHousehold(household_id) => {
+                    household_id.get_ui_info(self.id.into(), world);
+                    self.waiting_for_households
+                        .push((household_id, response_to));
+                }
+                ApiRequest::GetLog { after, max, filter } => {
+                    let query = LogQuery {
+                        min_level: filter.min_level.unwrap_or(LogLevel::Debug),
+                        topic: COption(filter.topic.map(CString::from)),
+                        from: filter.from,
+                        within: filter.within,
+                        after,
+                        max,
+                    };
+                    let query_id = self.query_log(query, world);
+                    self.waiting_for_log.push((query_id, response_to));
+                }
+                ApiRequest::SubmitProject { source, implement } => {
+                    let response = self.submit_project(source, implement, world);
+                    let _ = response_to.send(response);
+                }
+                ApiRequest::GetCounters => {
+                    self.query_buildings_if_not_yet(world);
+                    self.query_cars_if_not_yet(world);
+                    self.waiting_for_counters.push(response_to);
+                }
+                ApiRequest::GetSnapshot => {
+                    if self.waiting_for_snapshot.is_empty() {
+                        self.query_cars_if_not_yet(world);
+                        self.time.get_info(self.id.into(), world);
+                        self.plan_manager.export_master_plan(self.id.into(), world);
+                        TreasuryID::global_first(world).get_report(self.id.into(), world);
+                        HouseholdID::global_broadcast(world).get_ui_info(self.id.into(), world);
+                        InspectableID::global_broadcast(world).inspect(self.id.into(), world);
+                    }
+                    // snapshots contain the whole log
+                    let query_id = self.query_log(LogQuery::everything(), world);
+                    self.waiting_for_snapshot.push((query_id, response_to));
+                }
+                ApiRequest::Shutdown => {
+                    let token = self.access.editor_token();
+                    self.request_shutdown(&token, world);
+                    let _ = response_to.send(ApiResponse::Done);
+                }
+            }
+        }
+    }
+
+    fn query_log(&mut self, query: LogQuery, world: &mut World) -> u32 {
+        let query_id = self.next_log_query_id;
+        self.next_log_query_id = self.next_log_query_id.wrapping_add(1);
+        LogID::local_first(world).query(query, query_id, self.id.into(), world);
+        query_id
+    }
+
+    /// Can also be sent by clients, to shut down the server they are connected to
+    pub fn request_shutdown(&mut self, token: &EditorToken, world: &mut World) {
+        if !self.access.permits(token) {
+            warn(
+                LOG_T,
+                "Spectators can't shut down the server",
+                self.id,
+                world,
+            );
+        } else if !self.shutdown_requested.swap(true, Ordering::SeqCst) {
+            info(LOG_T, "Shutdown requested", self.id, world);
+        }
+    }
+
+    fn query_cars_if_not_yet(&mut self, world: &mut World) {
+        if self.waiting_for_counters.is_empty() && self.waiting_for_snapshot.is_empty() {
+            LaneID::global_broadcast(world).get_car_info(self.id.into(), world);
+            SwitchLaneID::global_broadcast(world).get_car_info(self.id.into(), world);
+        }
+    }
+
+    fn query_buildings_if_not_yet(&mut self, world: &mut World) {
+        if self.waiting_for_buildings.is_empty() && self.waiting_for_counters.is_empty() {
+            BuildingID::global_broadcast(world).get_ui_info(self.id.into(), world);
+        }
+    }
+
+    fn respond_to_waiting(&mut self) {
+        let state = &mut *self.state;
+
+        for response_to in state.waiting_for_time.drain(..) {
+            let _ = response_to.send(match state.received_time {
+                Some(time_info) => ApiResponse::Time(time_info),
+                None => ApiResponse::NotFound("No time info received".to_owned()),
+            });
+        }
+
+        let buildings = ::std::mem::replace(&mut state.received_buildings, Vec::new());
+
+        let counters = SimulationCounters {
+            n_households: buildings
+                .iter()
+                .map(|building| building.households.len())
+                .sum(),
+            n_cars: state.received_n_cars,
+            n_active_trips: state.received_trips.len(),
+        };
+        for response_to in state.waiting_for_counters.drain(..) {
+            let _ = response_to.send(ApiResponse::Counters(counters));
+        }
+        state.received_n_cars = 0;
+        state.received_trips.clear();
+        let cars = ::std::mem::replace(&mut state.received_cars, Vec::new());
+
+        for (request, response_to) in state.waiting_for_buildings.drain(..) {
+            let _ = response_to.send(if let ApiRequest::ListHouseholds = request {
+                ApiResponse::Households(
+                    buildings
+                        .iter()
+                        .flat_map(|building| {
+                            building
+                                .households
+                                .iter()
+                                .map(move |household_id| HouseholdInfo {
+                                    id: *household_id,
+                                    building: building.id,
+                                })
+                        })
+                        .collect(),
+                )
+            } else {
+                ApiResponse::Buildings(buildings.clone())
+            });
+        }
+
+        for (household_id, response_to) in state.waiting_for_households.drain(..) {
+            let _ = response_to.send(match state.received_households.get(&household_id) {
+                Some(core) => ApiResponse::Household(core.clone()),
+                None => ApiResponse::NotFound(format!("Unknown household {:?}", household_id)),
+            });
+        }
+
+        for (query_id, response_to) in state.waiting_for_log.drain(..) {
+            let _ = response_to.send(ApiResponse::Log(
+                state.received_log.remove(&query_id).unwrap_or_default(),
+            ));
+        }
+
+        let households = state
+            .received_households
+            .drain()
+            .map(|(id, core)| HouseholdSnapshot { id, core })
+            .collect::<Vec<_>>();
+        let summaries = ::std::mem::replace(&mut state.received_summaries, Vec::new());
+
+        for (query_id, response_to) in state.waiting_for_snapshot.drain(..) {
+            let _ = response_to.send(match state.received_master_plan {
+                Some(ref master_plan) => ApiResponse::Snapshot(WorldSnapshot {
+                    time: state.received_time,
+                    master_plan: master_plan.clone(),
+                    treasury: state.received_treasury,
+                    households: households.clone(),
+                    cars: cars.clone(),
+                    actors: summaries.clone(),
+                    log: state.received_log.remove(&query_id).unwrap_or_default(),
+                }),
+                None => ApiResponse::NotFound("No master plan received".to_owned()),
+            });
+        }
+        state.received_master_plan = None;
+        state.received_treasury = None;
+        state.received_log.clear();
+    }
+
+    fn submit_project(
+        &mut self,
+        source: String,
+        implement: bool,
+        world: &mut World,
+    ) -> ApiResponse {
+        if let Err(err) = PlanFile::from_ron(&source) {
+            return ApiResponse::BadRequest(format!("Invalid plan file: {}", err));
+        }
+
+        let project_id = ProjectID::new();
+        let transform = PlanTransform {
+            translation: V2::new(0.0, 0.0),
+            rotation: 0.0,
+        };
+        self.plan_manager.import_project(
+            project_id,
+            self.session_id,
+            source.into(),
+            transform,
+            self.access.editor_token(),
+            world,
+        );
+
+        if implement {
+            self.plan_manager
+                .implement(project_id, self.access.editor_token(), world);
+        }
+
+        ApiResponse::SubmittedProject(SubmittedProjectInfo {
+            project_id,
+            implemented: implement,
+        })
+    }
+}
+
+impl TimeUI for ApiBridge {
+    fn on_time_info(
+        &mut self,
+        current_instant: Instant,
+        speed: u16,
+        control: TimeControl,
+        _: &mut World,
+    ) {
+        let (hours, minutes) = TimeOfDay::from(current_instant).hours_minutes();
+        self.received_time = Some(TimeInfo {
+            ticks: current_instant.ticks() as u32,
+            hours: hours as u8,
+            minutes: minutes as u8,
+            speed,
+            control,
+        });
+    }
+}
+
+impl LandUseUI for ApiBridge {
+    fn on_building_constructed(
+        &mut self,
+        _id: BuildingID,
+        _lot: &Lot,
+        _households: &CVec<HouseholdID>,
+        _style: BuildingStyle,
+        _: &mut World,
+    ) {
+    }
+
+    fn on_building_destructed(&mut self, _id: BuildingID, _: &mut World) {}
+
+    fn on_building_ui_info(
+        &mut self,
+        id: BuildingID,
+        style: BuildingStyle,
+        households: &CVec<HouseholdID>,
+        _: &mut World,
+    ) {
+        self.received_buildings.push(BuildingInfo {
+            id,
+            style,
+            households: households.to_vec(),
+        });
+    }
+}
+
+impl TransportUI for ApiBridge {
+    fn on_lane_constructed(
+        &mut self,
+        _id: RawID,
+        _lane_path: &LinePath,
+        _is_switch: bool,
+        _on_intersection: bool,
+        _: &mut World,
+    ) {
+    }
+
+    fn on_lane_destructed(
+        &mut self,
+        _id: RawID,
+        _is_switch: bool,
+        _on_intersection: bool,
+        _: &mut World,
+    ) {
+    }
+
+    fn on_car_info(&mut self, from_lane: RawID, infos: &CVec<CarRenderInfo>, _: &mut World) {
+        self.received_n_cars += infos.len();
+        self.received_trips
+            .extend(infos.iter().map(|car_info| car_info.trip));
+        if !self.waiting_for_snapshot.is_empty() {
+            self.received_cars
+                .extend(infos.iter().map(|car_info| CarSnapshot {
+                    lane: from_lane,
+                    position: car_info.position,
+                    direction: car_info.direction,
+                    trip: car_info.trip,
+                }));
+        }
+    }
+}
+
+impl HouseholdUI for ApiBridge {
+    fn on_household_ui_info(&mut self, id: HouseholdID, core: &HouseholdCore, _: &mut World) {
+        self.received_households.insert(id, core.clone());
+    }
+}
+
+impl TreasuryUI for ApiBridge {
+    fn on_treasury_report(&mut self, report: &TreasuryReport, _: &mut World) {
+        self.received_treasury = Some(*report);
+    }
+}
+
+impl Inspector for ApiBridge {
+    fn on_inspected(&mut self, id: RawID, summary: &StateSummary, _: &mut World) {
+        self.received_summaries.push(ActorSnapshot {
+            id,
+            summary: summary.clone(),
+        });
+    }
+}
+
+impl PlanningUI for ApiBridge {
+    fn on_plans_update(
+        &mut self,
+        _master_update: &PlanHistoryUpdate,
+        _project_updates: &CHashMap<ProjectID, ProjectUpdate>,
+        _: &mut World,
+    ) {
+    }
+
+    fn on_project_preview_update(
+        &mut self,
+        _project_id: ProjectID,
+        _effective_history: &PlanHistory,
+        _result_update: &PlanResultUpdate,
+        _new_actions: &ActionGroups,
+        _: &mut World,
+    ) {
+    }
+
+    fn on_project_conflicts(
+        &mut self,
+        _project_id: ProjectID,
+        _colliding_gestures: &CVec<GestureID>,
+        _can_rebase: bool,
+        _: &mut World,
+    ) {
+    }
+
+    fn on_plan_exported(
+        &mut self,
+        project_id: &COption<ProjectID>,
+        contents: &CString,
+        _: &mut World,
+    ) {
+        if project_id.0.is_none() {
+            self.received_master_plan = Some(contents.to_string());
+        }
+    }
+
+    fn on_implemented_projects(&mut self, _projects: &CVec<ImplementedProjectInfo>, _: &mut World) {
+    }
+}
+
+impl LogRecipient for ApiBridge {
+    fn receive_newest_logs(&mut self, _entries: &CVec<Entry>, _effective_last: u32, _: &mut World) {
+    }
+
+    fn receive_log_query_result(&mut self, query_id: u32, entries: &CVec<Entry>, _: &mut World) {
+        self.received_log.insert(
+            query_id,
+            entries
+                .iter()
+                .map(|entry| LogEntryInfo {
+                    index: entry.index,
+                    ticks: entry.instant.ticks() as u32,
+                    from: entry.from,
+                    level: entry.level,
+                    topic: entry.topic.to_string(),
+                    message: entry.message.to_string(),
+                })
+                .collect(),
+        );
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<ApiBridge>();
+    auto_setup(system);
+}
+
+pub fn spawn(
+    world: &mut World,
+    calls: Receiver<ApiCall>,
+    shutdown_requested: Arc<AtomicBool>,
+    time: TimeID,
+    plan_manager: PlanManagerID,
+    access: &AccessControl,
+) -> ApiBridgeID {
+    ApiBridgeID::spawn(
+        External::new(calls),
+        External::new(shutdown_requested),
+        time,
+        plan_manager,
+        access.clone(),
+        world,
+    )
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
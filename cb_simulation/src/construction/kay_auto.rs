@@ -38,11 +38,16 @@ impl ConstructableID {
     pub fn destruct(self, report_to: ConstructionID, world: &mut World) {
         world.send(self.as_raw(), MSG_Constructable_destruct(report_to));
     }
+    
+    pub fn close_for_construction(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_Constructable_close_for_construction());
+    }
 
     pub fn register_trait(system: &mut ActorSystem) {
         system.register_trait::<ConstructableRepresentative>();
         system.register_trait_message::<MSG_Constructable_morph>();
         system.register_trait_message::<MSG_Constructable_destruct>();
+        system.register_trait_message::<MSG_Constructable_close_for_construction>();
     }
 
     pub fn register_implementor<A: Actor + Constructable>(system: &mut ActorSystem) {
@@ -58,6 +63,12 @@ impl ConstructableID {
                 instance.destruct(report_to, world)
             }, false
         );
+        
+        system.add_handler::<A, _, _>(
+            |&MSG_Constructable_close_for_construction(), instance, world| {
+                instance.close_for_construction(world); Fate::Live
+            }, false
+        );
     }
 }
 
@@ -65,6 +76,56 @@ impl ConstructableID {
 struct MSG_Constructable_morph(pub Prototype, pub ConstructionID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Constructable_destruct(pub ConstructionID);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Constructable_close_for_construction();
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct ConstructionUIID {
+    _raw_id: RawID
+}
+
+pub struct ConstructionUIRepresentative;
+
+impl ActorOrActorTrait for ConstructionUIRepresentative {
+    type ID = ConstructionUIID;
+}
+
+impl TypedID for ConstructionUIID {
+    type Target = ConstructionUIRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        ConstructionUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<A: Actor + ConstructionUI> TraitIDFrom<A> for ConstructionUIID {}
+
+impl ConstructionUIID {
+    pub fn on_construction_sites(self, sites: CVec < ConstructionSiteInfo >, world: &mut World) {
+        world.send(self.as_raw(), MSG_ConstructionUI_on_construction_sites(sites));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<ConstructionUIRepresentative>();
+        system.register_trait_message::<MSG_ConstructionUI_on_construction_sites>();
+    }
+
+    pub fn register_implementor<A: Actor + ConstructionUI>(system: &mut ActorSystem) {
+        system.register_implementor::<A, ConstructionUIRepresentative>();
+        system.add_handler::<A, _, _>(
+            |&MSG_ConstructionUI_on_construction_sites(ref sites), instance, world| {
+                instance.on_construction_sites(sites, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ConstructionUI_on_construction_sites(pub CVec < ConstructionSiteInfo >);
 
 impl Actor for Construction {
     type ID = ConstructionID;
@@ -95,10 +156,10 @@ impl TypedID for ConstructionID {
 }
 
 impl ConstructionID {
-    pub fn spawn(world: &mut World) -> Self {
+    pub fn spawn(parameters: SimulationParameters, world: &mut World) -> Self {
         let id = ConstructionID::from_raw(world.allocate_instance_id::<Construction>());
         let swarm = world.local_broadcast::<Construction>();
-        world.send(swarm, MSG_Construction_spawn(id, ));
+        world.send(swarm, MSG_Construction_spawn(id, parameters));
         id
     }
     
@@ -106,16 +167,22 @@ impl ConstructionID {
         world.send(self.as_raw(), MSG_Construction_action_done(id));
     }
     
+    pub fn get_construction_sites(self, requester: ConstructionUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Construction_get_construction_sites(requester));
+    }
+    
     pub fn implement(self, actions_to_implement: ActionGroups, new_prototypes: CVec < Prototype >, world: &mut World) {
         world.send(self.as_raw(), MSG_Construction_implement(actions_to_implement, new_prototypes));
     }
 }
 
-#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Construction_spawn(pub ConstructionID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Construction_spawn(pub ConstructionID, pub SimulationParameters);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Construction_action_done(pub ConstructableID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Construction_get_construction_sites(pub ConstructionUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Construction_implement(pub ActionGroups, pub CVec < Prototype >);
 
 impl Into<TemporalID> for ConstructionID {
@@ -124,14 +191,22 @@ impl Into<TemporalID> for ConstructionID {
     }
 }
 
+impl Into<InspectableID> for ConstructionID {
+    fn into(self) -> InspectableID {
+        InspectableID::from_raw(self.as_raw())
+    }
+}
+
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     ConstructableID::register_trait(system);
+    ConstructionUIID::register_trait(system);
     TemporalID::register_implementor::<Construction>(system);
+    InspectableID::register_implementor::<Construction>(system);
     system.add_spawner::<Construction, _, _>(
-        |&MSG_Construction_spawn(id, ), world| {
-            Construction::spawn(id, world)
+        |&MSG_Construction_spawn(id, parameters), world| {
+            Construction::spawn(id, parameters, world)
         }, false
     );
     
@@ -141,6 +216,12 @@ pub fn auto_setup(system: &mut ActorSystem) {
         }, false
     );
     
+    system.add_handler::<Construction, _, _>(
+        |&MSG_Construction_get_construction_sites(requester), instance, world| {
+            instance.get_construction_sites(requester, world); Fate::Live
+        }, false
+    );
+    
     system.add_handler::<Construction, _, _>(
         |&MSG_Construction_implement(ref actions_to_implement, ref new_prototypes), instance, world| {
             instance.implement(actions_to_implement, new_prototypes, world); Fate::Live
@@ -1,13 +1,85 @@
-use kay::{World, Fate, ActorSystem};
+use kay::{World, Fate, ActorSystem, TypedID};
 use compact::{CVec, CHashMap};
+use descartes::{N, P2};
 use planning::{PrototypeID, Prototype, PrototypeKind, Action, ActionGroups};
+use transport::transport_planning::{RoadPrototype, LanePrototype, SwitchLanePrototype,
+IntersectionPrototype};
+use land_use::zone_planning::LotOccupancy;
+use land_use::buildings::architecture::footprint_dimensions;
 use time::{Temporal, TemporalID, Instant};
+use parameters::SimulationParameters;
 use log::debug;
+use inspection::{Inspectable, InspectableID, InspectorID, StateSummary};
 const LOG_T: &str = "Construction";
 
+// Work is measured in crew-seconds: what one crew gets done in one simulated second
+const WORK_PER_LANE_METER: N = 2.0;
+const WORK_PER_SWITCH_LANE_METER: N = 0.5;
+const WORK_PER_FOOTPRINT_SQUARE_METER: N = 3.0;
+/// Morphing reuses most of what is already there
+const MORPH_WORK_FACTOR: N = 0.3;
+/// Tearing something down is a lot quicker than building it
+const DESTRUCT_WORK_FACTOR: N = 0.2;
+
 pub trait Constructable {
     fn morph(&mut self, new_prototype: &Prototype, report_to: ConstructionID, world: &mut World);
     fn destruct(&mut self, report_to: ConstructionID, world: &mut World) -> Fate;
+    /// Work started on a construction site that will morph or destruct this
+    fn close_for_construction(&mut self, world: &mut World);
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum ConstructionSiteKind {
+    Construct,
+    Morph,
+    Destruct,
+}
+
+#[derive(Copy, Clone, Serialize, Deserialize, Debug)]
+pub struct ConstructionSiteInfo {
+    pub prototype_id: PrototypeID,
+    pub kind: ConstructionSiteKind,
+    pub position: P2,
+    /// From 0.0 (just started) to 1.0 (done)
+    pub progress: f32,
+    /// With limited crew capacity, sites might have to wait for a free crew
+    pub is_worked_on: bool,
+}
+
+pub trait ConstructionUI {
+    fn on_construction_sites(&mut self, sites: &CVec<ConstructionSiteInfo>, world: &mut World);
+}
+
+pub fn prototype_work(prototype: &Prototype) -> N {
+    match prototype.kind {
+        PrototypeKind::Road(RoadPrototype::Lane(LanePrototype(ref path, _))) => {
+            path.length() * WORK_PER_LANE_METER
+        }
+        PrototypeKind::Road(RoadPrototype::SwitchLane(SwitchLanePrototype(ref path))) => {
+            path.length() * WORK_PER_SWITCH_LANE_METER
+        }
+        PrototypeKind::Road(RoadPrototype::Intersection(IntersectionPrototype {
+            ref connecting_lanes,
+            ..
+        })) => {
+            connecting_lanes
+                .values()
+                .flat_map(|group| group.iter())
+                .map(|&LanePrototype(ref path, _)| path.length())
+                .sum::<N>()
+                * WORK_PER_LANE_METER
+        }
+        PrototypeKind::Road(RoadPrototype::PavedArea(_)) => 0.0,
+        PrototypeKind::Lot(ref lot_prototype) => match lot_prototype.occupancy {
+            LotOccupancy::Vacant => 0.0,
+            LotOccupancy::Occupied(building_style) => {
+                let (width, depth) = footprint_dimensions(building_style);
+                width * depth * WORK_PER_FOOTPRINT_SQUARE_METER
+            }
+        },
+        // vegetation and water are part of the landscape, not built by construction crews
+        PrototypeKind::Plant(_) | PrototypeKind::Water(_) => 0.0,
+    }
 }
 
 impl Prototype {
@@ -20,6 +92,9 @@ impl Prototype {
             PrototypeKind::Plant(ref plant_prototype) => {
                 plant_prototype.construct(self.id, report_to, world)
             }
+            PrototypeKind::Water(ref water_prototype) => {
+                water_prototype.construct(self.id, report_to, world)
+            }
         }
     }
 
@@ -34,34 +109,153 @@ impl Prototype {
             (&PrototypeKind::Plant(ref self_plant), &PrototypeKind::Plant(ref other_plant)) => {
                 self_plant.morphable_from(other_plant)
             }
+            (&PrototypeKind::Water(ref self_water), &PrototypeKind::Water(ref other_water)) => {
+                self_water.morphable_from(other_water)
+            }
             _ => false,
         }
     }
 }
 
+#[derive(Compact, Clone)]
+struct ConstructionSite {
+    action: Action,
+    position: P2,
+    work_needed: N,
+    work_done: N,
+}
+
+impl ConstructionSite {
+    fn info(&self, is_worked_on: bool) -> ConstructionSiteInfo {
+        let (prototype_id, kind) = match self.action {
+            Action::Construct(prototype_id) => (prototype_id, ConstructionSiteKind::Construct),
+            Action::Morph(_, new_prototype_id) => (new_prototype_id, ConstructionSiteKind::Morph),
+            Action::Destruct(prototype_id) => (prototype_id, ConstructionSiteKind::Destruct),
+        };
+
+        ConstructionSiteInfo {
+            prototype_id,
+            kind,
+            position: self.position,
+            progress: if self.work_needed > 0.0 {
+                (self.work_done / self.work_needed).min(1.0)
+            } else {
+                1.0
+            },
+            is_worked_on,
+        }
+    }
+}
+
 // #[derive(Compact, Clone)]
 #[derive(Clone)]
 pub struct Construction {
     id: ConstructionID,
     constructed: CHashMap<PrototypeID, CVec<ConstructableID>>,
+    constructed_prototypes: CHashMap<PrototypeID, Prototype>,
     pending_constructables: CVec<ConstructableID>,
     queued_action_groups: ActionGroups,
     new_prototypes: CHashMap<PrototypeID, Prototype>,
+    sites: CVec<ConstructionSite>,
+    /// How many sites can be worked on at the same time, unlimited if `None`
+    crew_capacity: Option<u32>,
 }
 
 mod compact_workaround;
 
 impl Construction {
-    pub fn spawn(id: ConstructionID, _world: &mut World) -> Construction {
+    pub fn spawn(
+        id: ConstructionID,
+        parameters: SimulationParameters,
+        _world: &mut World,
+    ) -> Construction {
         Construction {
             id,
             constructed: CHashMap::new(),
+            constructed_prototypes: CHashMap::new(),
             pending_constructables: CVec::new(),
             queued_action_groups: ActionGroups(CVec::new()),
             new_prototypes: CHashMap::new(),
+            sites: CVec::new(),
+            crew_capacity: parameters.construction_crew_capacity(),
         }
     }
 
+    pub fn get_construction_sites(&mut self, requester: ConstructionUIID, world: &mut World) {
+        let n_sites_worked_on = self.n_sites_worked_on();
+        let sites = self
+            .sites
+            .iter()
+            .enumerate()
+            .map(|(i, site)| site.info(i < n_sites_worked_on))
+            .collect();
+        requester.on_construction_sites(sites, world);
+    }
+
+    fn n_sites_worked_on(&self) -> usize {
+        match self.crew_capacity {
+            Some(n_crews) => (n_crews as usize).min(self.sites.len()),
+            None => self.sites.len(),
+        }
+    }
+
+    fn start_site(&mut self, action: Action, world: &mut World) {
+        let (prototype, work_factor) = match action {
+            Action::Construct(prototype_id) => (
+                self.new_prototypes
+                    .get(prototype_id)
+                    .expect("Should have prototype to be constructed"),
+                1.0,
+            ),
+            Action::Morph(old_prototype_id, new_prototype_id) => {
+                for id in self
+                    .constructed
+                    .get(old_prototype_id)
+                    .expect("Tried to morph non-constructed prototype")
+                {
+                    id.close_for_construction(world);
+                }
+                (
+                    self.new_prototypes
+                        .get(new_prototype_id)
+                        .expect("Should have prototype to be morphed to"),
+                    MORPH_WORK_FACTOR,
+                )
+            }
+            Action::Destruct(prototype_id) => {
+                for id in self
+                    .constructed
+                    .get(prototype_id)
+                    .expect("Tried to destruct non-constructed prototype")
+                {
+                    id.close_for_construction(world);
+                }
+                (
+                    self.constructed_prototypes
+                        .get(prototype_id)
+                        .expect("Should know constructed prototype"),
+                    DESTRUCT_WORK_FACTOR,
+                )
+            }
+        };
+
+        let work_needed = prototype_work(prototype) * work_factor;
+
+        // happens right away, without taking up a crew
+        if work_needed <= 0.0 {
+            self.start_action(&action, world);
+            return;
+        }
+
+        let site = ConstructionSite {
+            position: prototype.representative_position,
+            work_needed,
+            work_done: 0.0,
+            action,
+        };
+        self.sites.push(site);
+    }
+
     pub fn action_done(&mut self, id: ConstructableID, _world: &mut World) {
         self.pending_constructables
             .retain(|pending_constructable| *pending_constructable != id);
@@ -77,6 +271,8 @@ impl Construction {
                     .expect("Should have prototype to be constructed");
                 let ids = new_prototype.construct(self.id, world);
                 self.constructed.insert(prototype_id, ids.clone());
+                self.constructed_prototypes
+                    .insert(prototype_id, new_prototype);
                 ids
             }
             Action::Morph(old_protoype_id, new_prototype_id) => {
@@ -93,6 +289,9 @@ impl Construction {
                     id.morph(new_prototype.clone(), self.id, world);
                 }
                 self.constructed.insert(new_prototype_id, ids.clone());
+                self.constructed_prototypes.remove(old_protoype_id);
+                self.constructed_prototypes
+                    .insert(new_prototype_id, new_prototype);
                 ids
             }
             Action::Destruct(prototype_id) => {
@@ -104,6 +303,7 @@ impl Construction {
                 for id in &ids {
                     id.destruct(self.id, world);
                 }
+                self.constructed_prototypes.remove(prototype_id);
                 ids
             }
         };
@@ -129,21 +329,50 @@ impl Construction {
 }
 
 impl Temporal for Construction {
-    fn tick(&mut self, _dt: f32, _current_instant: Instant, world: &mut World) {
-        if self.pending_constructables.is_empty() {
+    fn tick(
+        &mut self,
+        dt: f32,
+        _current_instant: Instant,
+        _: &SimulationParameters,
+        world: &mut World,
+    ) {
+        let n_sites_worked_on = self.n_sites_worked_on();
+        for site in self.sites.iter_mut().take(n_sites_worked_on) {
+            site.work_done += dt;
+        }
+
+        if self
+            .sites
+            .iter()
+            .any(|site| site.work_done >= site.work_needed)
+        {
+            let (finished_sites, remaining_sites): (Vec<_>, Vec<_>) = self
+                .sites
+                .iter()
+                .cloned()
+                .partition(|site| site.work_done >= site.work_needed);
+            self.sites = remaining_sites.into();
+
+            for site in finished_sites {
+                self.start_action(&site.action, world);
+            }
+        }
+
+        if self.sites.is_empty() && self.pending_constructables.is_empty() {
             if !self.queued_action_groups.0.is_empty() {
                 debug(LOG_T, "Starting construction group:", self.id, world);
                 let next_action_group = self.queued_action_groups.0.remove(0);
                 for action in next_action_group.0 {
-                    self.start_action(&action, world);
+                    self.start_site(action, world);
                 }
-                debug(LOG_T, "Finished construction group:", self.id, world);
+                debug(LOG_T, "Set up construction sites:", self.id, world);
             }
         } else {
             debug(
                 LOG_T,
                 format!(
-                    "Construction pending: {} - queued groups: {}",
+                    "Construction sites: {} - pending: {} - queued groups: {}",
+                    self.sites.len(),
                     self.pending_constructables.len(),
                     self.queued_action_groups.0.len()
                 ),
@@ -154,13 +383,34 @@ impl Temporal for Construction {
     }
 }
 
+impl Inspectable for Construction {
+    fn inspect(&mut self, requester: InspectorID, world: &mut World) {
+        let summary = StateSummary::new("Construction")
+            .field("constructed prototypes", self.constructed.len())
+            .field(
+                "constructables",
+                self.constructed
+                    .values()
+                    .map(|constructables| constructables.len())
+                    .sum::<usize>(),
+            )
+            .field("pending constructables", self.pending_constructables.len())
+            .field("queued action groups", self.queued_action_groups.0.len())
+            .field("new prototypes", self.new_prototypes.len())
+            .field("construction sites", self.sites.len())
+            .field("crew capacity", self.crew_capacity);
+
+        requester.on_inspected(self.id.as_raw(), summary, world);
+    }
+}
+
 pub fn setup(system: &mut ActorSystem) {
     system.register::<Construction>();
     auto_setup(system);
 }
 
-pub fn spawn(world: &mut World) {
-    ConstructionID::spawn(world);
+pub fn spawn(world: &mut World, parameters: &SimulationParameters) {
+    ConstructionID::spawn(*parameters, world);
 }
 
 mod kay_auto;
@@ -3,10 +3,11 @@ use util::random::{seed, Rng};
 
 use time::{TimeOfDay, TimeOfDayRange, Instant, Duration, Ticks, TimeID, Temporal,
 TemporalID};
-use economy::resources::Resource;
+use parameters::SimulationParameters;
+use economy::resources::{Resource, ResourceAmount};
 use economy::resources::Resource::*;
 use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
-use land_use::buildings::BuildingID;
+use land_use::buildings::{BuildingID, UnitType};
 use transport::pathfinding::trip::{TripResult, TripListenerID};
 use transport::pathfinding::RoughLocationID;
 
@@ -137,7 +138,7 @@ impl Household for Family {
         }
     }
 
-    fn importance(resource: Resource, time: TimeOfDay) -> f32 {
+    fn importance(&self, resource: Resource, time: TimeOfDay) -> f32 {
         let hour = time.hours_minutes().0;
 
         let bihourly_importance = match resource {
@@ -153,7 +154,7 @@ impl Household for Family {
             .unwrap_or(0.0)
     }
 
-    fn interesting_resources() -> &'static [Resource] {
+    fn interesting_resources(&self) -> &[Resource] {
         &[
             Wakefulness,
             Satiety, //Entertainment,
@@ -189,6 +190,7 @@ impl Household for Family {
             //     *entertainment -= 0.2 * individuality * dt.as_hours();
             // }
         }
+        self.core.resources.spoil(dt);
         // {
         //     let individuality = seed(self.id).gen_range(0.8, 1.2);
         //     let furniture = self.core.resources.mut_entry_or(Furniture, 0.0);
@@ -206,6 +208,10 @@ impl Household for Family {
         // }
     }
 
+    fn storage_capacity(&self) -> ResourceAmount {
+        UnitType::Dwelling.storage_capacity()
+    }
+
     fn on_destroy(&mut self, world: &mut World) {
         self.home.remove_household(self.id_as(), world);
     }
@@ -214,13 +220,23 @@ impl Household for Family {
         format!("The {} Family", family_name(self.id))
     }
 
+    fn kind_name(&self) -> &str {
+        "Family"
+    }
+
     fn member_name(&self, member: MemberIdx) -> String {
         member_name(self.id, member)
     }
 }
 
 impl Temporal for Family {
-    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
+    fn tick(
+        &mut self,
+        _dt: f32,
+        current_instant: Instant,
+        _: &SimulationParameters,
+        world: &mut World,
+    ) {
         self.on_tick(current_instant, world);
     }
 }
@@ -0,0 +1,101 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for GenericBusiness {
+    type ID = GenericBusinessID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct GenericBusinessID {
+    _raw_id: RawID
+}
+
+impl TypedID for GenericBusinessID {
+    type Target = GenericBusiness;
+
+    fn from_raw(id: RawID) -> Self {
+        GenericBusinessID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl GenericBusinessID {
+    pub fn move_into(kind_idx: BusinessKindIdx, site: BuildingID, time: TimeID, world: &mut World) -> Self {
+        let id = GenericBusinessID::from_raw(world.allocate_instance_id::<GenericBusiness>());
+        let swarm = world.local_broadcast::<GenericBusiness>();
+        world.send(swarm, MSG_GenericBusiness_move_into(id, kind_idx, site, time));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GenericBusiness_move_into(pub GenericBusinessID, pub BusinessKindIdx, pub BuildingID, pub TimeID);
+
+impl Into<HouseholdID> for GenericBusinessID {
+    fn into(self) -> HouseholdID {
+        HouseholdID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TemporalID> for GenericBusinessID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for GenericBusinessID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<EvaluationRequesterID> for GenericBusinessID {
+    fn into(self) -> EvaluationRequesterID {
+        EvaluationRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<RoughLocationID> for GenericBusinessID {
+    fn into(self) -> RoughLocationID {
+        RoughLocationID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TripListenerID> for GenericBusinessID {
+    fn into(self) -> TripListenerID {
+        TripListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    HouseholdID::register_implementor::<GenericBusiness>(system);
+    TemporalID::register_implementor::<GenericBusiness>(system);
+    SleeperID::register_implementor::<GenericBusiness>(system);
+    EvaluationRequesterID::register_implementor::<GenericBusiness>(system);
+    RoughLocationID::register_implementor::<GenericBusiness>(system);
+    TripListenerID::register_implementor::<GenericBusiness>(system);
+    system.add_spawner::<GenericBusiness, _, _>(
+        |&MSG_GenericBusiness_move_into(id, kind_idx, site, time), world| {
+            GenericBusiness::move_into(id, kind_idx, site, time, world)
+        }, false
+    );
+}
\ No newline at end of file
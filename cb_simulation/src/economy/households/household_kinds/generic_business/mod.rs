@@ -0,0 +1,325 @@
+use kay::{ActorSystem, World, TypedID, Actor};
+use compact::{CVec, CString};
+use time::{TimeOfDay, TimeOfDayRange, Duration, TimeID, Ticks};
+use parameters::SimulationParameters;
+use economy::resources::{Resource, ResourceAmount};
+use economy::production::Recipe;
+use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use land_use::buildings::{BuildingID, BuildingStyle, UnitType};
+use std::cell::Cell;
+
+use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
+
+// How many worker-hours worth of recipe inputs a business tries to keep in stock
+const INPUT_BUFFER_WORKER_HOURS: f32 = 8.0;
+
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub struct OfferDefinition {
+    /// start hour, start minute, end hour, end minute
+    pub opening_hours: (u8, u8, u8, u8),
+    pub deal: CVec<(Resource, ResourceAmount)>,
+    pub duration_minutes: u32,
+    pub max_users: u32,
+}
+
+impl OfferDefinition {
+    pub fn to_offer(&self) -> Offer {
+        let (start_h, start_m, end_h, end_m) = self.opening_hours;
+
+        Offer::new(
+            MemberIdx(0),
+            TimeOfDayRange::new(
+                start_h as usize,
+                start_m as usize,
+                end_h as usize,
+                end_m as usize,
+            ),
+            Deal::new(
+                self.deal.iter().cloned(),
+                Duration::from_minutes(self.duration_minutes as usize),
+            ),
+            self.max_users as usize,
+            false,
+        )
+    }
+}
+
+/// Businesses that only differ in what they trade and produce are described by data
+/// instead of code, loaded from a RON file at startup. Add new kinds there to create new
+/// production chains. Units and building styles are still code, since they determine
+/// storage capacity and building geometry, so `unit_type` and `building_style` have to
+/// name existing variants of `UnitType` and `BuildingStyle`
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub struct BusinessKind {
+    pub name: CString,
+    pub member_title: CString,
+    pub unit_type: UnitType,
+    pub building_style: BuildingStyle,
+    /// Relative to a share of 1.0 for families
+    pub immigration_share: f32,
+    pub offers: CVec<OfferDefinition>,
+    /// Bihourly importance of each needed resource, starting at midnight
+    pub importance: CVec<(Resource, [u8; 12])>,
+    /// Staff is split evenly between all recipes
+    pub recipes: CVec<Recipe>,
+    pub interesting_resources: CVec<Resource>,
+}
+
+pub fn load_business_kinds(path: &str) -> Result<Vec<BusinessKind>, String> {
+    let source = ::std::fs::read_to_string(path)
+        .map_err(|err| format!("Couldn't read business kinds {}: {}", path, err))?;
+    ::ron::de::from_str::<Vec<BusinessKind>>(&source)
+        .map_err(|err| format!("Couldn't parse business kinds {}: {}", path, err))
+}
+
+/// Position of a kind in the business kinds set at startup
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct BusinessKindIdx(pub usize);
+
+thread_local! {
+    // all actors live on the simulation thread and the kinds never change after startup,
+    // so businesses only remember the index of their kind
+    static BUSINESS_KINDS: Cell<&'static [BusinessKind]> = Cell::new(&[]);
+}
+
+/// Has to be called on the simulation thread before any businesses are spawned
+pub fn set_business_kinds(kinds: Vec<BusinessKind>) {
+    let kinds: &'static [BusinessKind] = Box::leak(kinds.into_boxed_slice());
+    BUSINESS_KINDS.with(|current| current.set(kinds));
+}
+
+pub fn business_kinds() -> &'static [BusinessKind] {
+    BUSINESS_KINDS.with(Cell::get)
+}
+
+impl BusinessKindIdx {
+    pub fn get(self) -> &'static BusinessKind {
+        &business_kinds()[self.0]
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct GenericBusiness {
+    id: GenericBusinessID,
+    site: BuildingID,
+    kind_idx: BusinessKindIdx,
+    core: HouseholdCore,
+}
+
+impl GenericBusiness {
+    pub fn move_into(
+        id: GenericBusinessID,
+        kind_idx: BusinessKindIdx,
+        site: BuildingID,
+        time: TimeID,
+        world: &mut World,
+    ) -> GenericBusiness {
+        time.wake_up_in(Ticks(0), id.into(), world);
+
+        let kind = kind_idx.get();
+        let mut core = HouseholdCore::new(
+            id.into(),
+            world,
+            1,
+            site.into(),
+            kind.offers
+                .iter()
+                .map(OfferDefinition::to_offer)
+                .collect::<Vec<_>>()
+                .into(),
+        );
+
+        // make sure missing inputs show up as problems
+        for recipe in kind.recipes.iter() {
+            for &(resource, _) in recipe.inputs.iter() {
+                core.resources.mut_entry_or(resource, 0.0);
+            }
+        }
+
+        GenericBusiness {
+            id,
+            site,
+            kind_idx,
+            core,
+        }
+    }
+
+    fn kind(&self) -> &'static BusinessKind {
+        self.kind_idx.get()
+    }
+
+    fn input_buffer(&self, resource: Resource) -> ResourceAmount {
+        self.kind()
+            .recipes
+            .iter()
+            .flat_map(|recipe| recipe.inputs.iter())
+            .filter(|&&(input, _)| input == resource)
+            .map(|&(_, amount)| amount * INPUT_BUFFER_WORKER_HOURS)
+            .sum()
+    }
+}
+
+impl Household for GenericBusiness {
+    fn core(&self) -> &HouseholdCore {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut HouseholdCore {
+        &mut self.core
+    }
+
+    fn site(&self) -> RoughLocationID {
+        self.site.into()
+    }
+
+    fn is_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn supplier_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn importance(&self, resource: Resource, time: TimeOfDay) -> f32 {
+        let hour = time.hours_minutes().0;
+
+        self.kind()
+            .importance
+            .iter()
+            .find(|&&(needed_resource, _)| needed_resource == resource)
+            .map(|&(_, lookup)| lookup[hour / 2] as f32)
+            .unwrap_or(0.0)
+    }
+
+    fn graveness(&self, resource: Resource, amount: ResourceAmount, time: TimeOfDay) -> f32 {
+        (self.input_buffer(resource) - amount) * self.importance(resource, time)
+    }
+
+    fn interesting_resources(&self) -> &[Resource] {
+        &self.kind().interesting_resources
+    }
+
+    fn decay(&mut self, dt: Duration, world: &mut World) {
+        if !self.kind().recipes.is_empty() {
+            let worker_hours_per_recipe = self.core.n_active_workers() as f32 * dt.as_hours()
+                / self.kind().recipes.len() as f32;
+            let storage_capacity = self.storage_capacity();
+
+            for recipe in self.kind().recipes.iter() {
+                recipe.produce(
+                    &mut self.core.resources,
+                    storage_capacity,
+                    worker_hours_per_recipe,
+                );
+            }
+        }
+
+        self.core.resources.spoil(dt);
+        self.core.update_offer_availability(self.id_as(), world);
+    }
+
+    fn storage_capacity(&self) -> ResourceAmount {
+        self.kind().unit_type.storage_capacity()
+    }
+
+    fn on_destroy(&mut self, world: &mut World) {
+        self.site.remove_household(self.id_as(), world);
+    }
+
+    fn household_name(&self) -> String {
+        self.kind().name.to_string()
+    }
+
+    fn kind_name(&self) -> &str {
+        &self.kind().name
+    }
+
+    fn member_name(&self, member: MemberIdx) -> String {
+        format!("{} {}", &*self.kind().member_title, member.0 + 1)
+    }
+}
+
+use time::{Temporal, TemporalID, Sleeper, SleeperID, Instant, ticks_per_sim_second};
+const UPDATE_EVERY_N_SECS: u32 = 4;
+
+impl Temporal for GenericBusiness {
+    fn tick(
+        &mut self,
+        _dt: f32,
+        current_instant: Instant,
+        _: &SimulationParameters,
+        world: &mut World,
+    ) {
+        if (current_instant.ticks() + self.id.as_raw().instance_id as usize)
+            % (UPDATE_EVERY_N_SECS * ticks_per_sim_second()) as usize
+            == 0
+        {
+            self.decay(
+                Duration(UPDATE_EVERY_N_SECS * ticks_per_sim_second()),
+                world,
+            );
+        }
+    }
+}
+
+impl Sleeper for GenericBusiness {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        self.update_core(current_instant, world);
+    }
+}
+
+use economy::households::ResultAspect;
+
+impl EvaluationRequester for GenericBusiness {
+    fn expect_n_results(&mut self, resource: Resource, n: u32, world: &mut World) {
+        self.update_results(resource, &ResultAspect::SetTarget(n), world);
+    }
+
+    fn on_result(&mut self, result: &EvaluatedSearchResult, world: &mut World) {
+        let &EvaluatedSearchResult {
+            resource,
+            ref evaluated_deals,
+            ..
+        } = result;
+        self.update_results(
+            resource,
+            &ResultAspect::AddDeals(evaluated_deals.clone()),
+            world,
+        );
+    }
+}
+
+use transport::pathfinding::{RoughLocationID, RoughLocation, RoughLocationResolve};
+
+impl RoughLocation for GenericBusiness {
+    fn resolve(&self) -> RoughLocationResolve {
+        RoughLocationResolve::SameAs(self.site())
+    }
+}
+
+use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+
+impl TripListener for GenericBusiness {
+    fn trip_created(&mut self, trip: TripID, world: &mut World) {
+        self.on_trip_created(trip, world);
+    }
+
+    fn trip_result(
+        &mut self,
+        trip: TripID,
+        result: TripResult,
+        rough_source: RoughLocationID,
+        rough_destination: RoughLocationID,
+        world: &mut World,
+    ) {
+        self.on_trip_result(trip, result, rough_source, rough_destination, world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<GenericBusiness>();
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -1,10 +1,11 @@
 use kay::{ActorSystem, World, Actor};
 use time::{TimeOfDay, TimeOfDayRange, Duration, Instant, Temporal, TemporalID,
 TimeID, Ticks};
-use economy::resources::Resource;
+use parameters::SimulationParameters;
+use economy::resources::{Resource, ResourceAmount};
 use economy::resources::Resource::*;
 use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
-use land_use::buildings::BuildingID;
+use land_use::buildings::{BuildingID, UnitType};
 use transport::pathfinding::RoughLocationID;
 use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
 
@@ -211,11 +212,11 @@ impl Household for NeighboringTownTrade {
         true
     }
 
-    fn importance(_: Resource, _: TimeOfDay) -> f32 {
+    fn importance(&self, _: Resource, _: TimeOfDay) -> f32 {
         1.0
     }
 
-    fn interesting_resources() -> &'static [Resource] {
+    fn interesting_resources(&self) -> &[Resource] {
         &[
             //Entertainment,
             //Services,
@@ -231,10 +232,18 @@ impl Household for NeighboringTownTrade {
 
     fn decay(&mut self, _dt: Duration, _: &mut World) {}
 
+    fn storage_capacity(&self) -> ResourceAmount {
+        UnitType::NeighboringTownTrade.storage_capacity()
+    }
+
     fn household_name(&self) -> String {
         "Neighboring Town".to_owned()
     }
 
+    fn kind_name(&self) -> &str {
+        "Neighboring Town Trade"
+    }
+
     fn member_name(&self, member: MemberIdx) -> String {
         format!("Neighboring Town Worker {}", member.0 + 1)
     }
@@ -291,7 +300,13 @@ impl TripListener for NeighboringTownTrade {
 }
 
 impl Temporal for NeighboringTownTrade {
-    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
+    fn tick(
+        &mut self,
+        _dt: f32,
+        current_instant: Instant,
+        _: &SimulationParameters,
+        world: &mut World,
+    ) {
         self.on_tick(current_instant, world);
     }
 }
@@ -43,6 +43,10 @@ impl HouseholdID {
         world.send(self.as_raw(), MSG_Household_provide_deal(deal, member));
     }
     
+    pub fn undo_received_deal(self, deal: Deal, member: MemberIdx, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_undo_received_deal(deal, member));
+    }
+    
     pub fn task_succeeded(self, member: MemberIdx, world: &mut World) {
         world.send(self.as_raw(), MSG_Household_task_succeeded(member));
     }
@@ -142,12 +146,21 @@ impl HouseholdID {
     pub fn get_ui_info(self, requester: ui :: HouseholdUIID, world: &mut World) {
         world.send(self.as_raw(), MSG_Household_get_ui_info(requester));
     }
+    
+    pub fn report_statistics(self, collector: EconomicStatisticsID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_report_statistics(collector));
+    }
+    
+    pub fn report_to_heatmaps(self, collector: HeatmapsID, position: P2, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_report_to_heatmaps(collector, position, instant));
+    }
 
     pub fn register_trait(system: &mut ActorSystem) {
         system.register_trait::<HouseholdRepresentative>();
         system.register_trait_message::<MSG_Household_decay>();
         system.register_trait_message::<MSG_Household_receive_deal>();
         system.register_trait_message::<MSG_Household_provide_deal>();
+        system.register_trait_message::<MSG_Household_undo_received_deal>();
         system.register_trait_message::<MSG_Household_task_succeeded>();
         system.register_trait_message::<MSG_Household_task_failed>();
         system.register_trait_message::<MSG_Household_reset_member_task>();
@@ -173,6 +186,8 @@ impl HouseholdID {
         system.register_trait_message::<MSG_Household_stopped_actively_using>();
         system.register_trait_message::<MSG_Household_withdrawal_confirmed>();
         system.register_trait_message::<MSG_Household_get_ui_info>();
+        system.register_trait_message::<MSG_Household_report_statistics>();
+        system.register_trait_message::<MSG_Household_report_to_heatmaps>();
     }
 
     pub fn register_implementor<A: Actor + Household>(system: &mut ActorSystem) {
@@ -195,6 +210,12 @@ impl HouseholdID {
             }, false
         );
         
+        system.add_handler::<A, _, _>(
+            |&MSG_Household_undo_received_deal(ref deal, member), instance, world| {
+                instance.undo_received_deal(deal, member, world); Fate::Live
+            }, false
+        );
+        
         system.add_handler::<A, _, _>(
             |&MSG_Household_task_succeeded(member), instance, world| {
                 instance.task_succeeded(member, world); Fate::Live
@@ -344,6 +365,18 @@ impl HouseholdID {
                 instance.get_ui_info(requester, world); Fate::Live
             }, false
         );
+        
+        system.add_handler::<A, _, _>(
+            |&MSG_Household_report_statistics(collector), instance, world| {
+                instance.report_statistics(collector, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<A, _, _>(
+            |&MSG_Household_report_to_heatmaps(collector, position, instant), instance, world| {
+                instance.report_to_heatmaps(collector, position, instant, world); Fate::Live
+            }, false
+        );
     }
 }
 
@@ -354,6 +387,8 @@ struct MSG_Household_receive_deal(pub Deal, pub MemberIdx);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_provide_deal(pub Deal, pub MemberIdx);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_undo_received_deal(pub Deal, pub MemberIdx);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_task_succeeded(pub MemberIdx);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_task_failed(pub MemberIdx, pub RoughLocationID);
@@ -403,6 +438,10 @@ struct MSG_Household_stopped_actively_using(pub OfferIdx, pub HouseholdID, pub M
 struct MSG_Household_withdrawal_confirmed(pub OfferIdx);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_get_ui_info(pub ui :: HouseholdUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_report_statistics(pub EconomicStatisticsID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_report_to_heatmaps(pub HeatmapsID, pub P2, pub Instant);
 
 
 
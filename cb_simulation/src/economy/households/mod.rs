@@ -1,6 +1,6 @@
 use kay::{ActorSystem, World, Actor, TypedID, Fate};
 use compact::{CVec, CDict, COption};
-use time::{Duration, TimeOfDay, Instant, Ticks, TimeID, TICKS_PER_SIM_SECOND, Sleeper,
+use time::{Duration, TimeOfDay, Instant, Ticks, TimeID, ticks_per_sim_second, Sleeper,
 Temporal};
 use util::async_counter::AsyncCounter;
 use util::random::{seed, Rng};
@@ -31,6 +31,11 @@ impl MemberIdx {
 use super::market::{MarketID, Deal, EvaluatedDeal, EvaluationRequester, EvaluationRequesterID,
 TripCostEstimatorID, EvaluatedSearchResult};
 use super::resources::{Resource, ResourceAmount, ResourceMap, Entry, Inventory};
+use super::treasury::{TreasuryID, TAX_RATE};
+use super::statistics::EconomicStatisticsID;
+use heatmaps::HeatmapsID;
+use access::InternalKey;
+use descartes::P2;
 use transport::pathfinding::{RoughLocationID, RoughLocation};
 use transport::pathfinding::trip::{TripListener, TripID, TripResult, TripFate};
 use self::tasks::{Task, TaskState, TaskEndSchedulerID};
@@ -56,17 +61,43 @@ pub trait Household:
 
     fn is_shared(resource: Resource) -> bool;
     fn supplier_shared(resource: Resource) -> bool;
-    fn importance(resource: Resource, time: TimeOfDay) -> f32;
-    fn graveness(resource: Resource, amount: ResourceAmount, time: TimeOfDay) -> f32 {
-        -amount * Self::importance(resource, time)
+    fn importance(&self, resource: Resource, time: TimeOfDay) -> f32;
+    fn graveness(&self, resource: Resource, amount: ResourceAmount, time: TimeOfDay) -> f32 {
+        -amount * self.importance(resource, time)
     }
-    fn interesting_resources() -> &'static [Resource];
+    fn interesting_resources(&self) -> &[Resource];
     fn decay(&mut self, dt: Duration, world: &mut World);
+    /// How much of all goods taken together the household can keep
+    fn storage_capacity(&self) -> ResourceAmount;
 
     fn household_name(&self) -> String;
+    /// Households of the same kind are aggregated in economic statistics
+    fn kind_name(&self) -> &str;
     fn member_name(&self, member: MemberIdx) -> String;
 
-    fn receive_deal(&mut self, deal: &Deal, member: MemberIdx, _: &mut World) {
+    fn receive_deal(&mut self, deal: &Deal, member: MemberIdx, world: &mut World) {
+        self.add_deal_delta(deal, member);
+        let taxes = taxes_on(deal.money_delta());
+        self.core_mut()
+            .pay_taxes(taxes, member, Self::is_shared(Resource::Money), world);
+    }
+
+    fn provide_deal(&mut self, deal: &Deal, member: MemberIdx, world: &mut World) {
+        self.remove_deal_delta(deal, member);
+        let taxes = taxes_on(-deal.money_delta());
+        self.core_mut()
+            .pay_taxes(taxes, member, Self::is_shared(Resource::Money), world);
+    }
+
+    /// Reverts `receive_deal`, including the taxes paid for it
+    fn undo_received_deal(&mut self, deal: &Deal, member: MemberIdx, world: &mut World) {
+        self.remove_deal_delta(deal, member);
+        let refund = taxes_on(deal.money_delta());
+        self.core_mut()
+            .pay_taxes(-refund, member, Self::is_shared(Resource::Money), world);
+    }
+
+    fn add_deal_delta(&mut self, deal: &Deal, member: MemberIdx) {
         let core = self.core_mut();
         deal.delta.give_to_shared_private(
             &mut core.resources,
@@ -75,7 +106,7 @@ pub trait Household:
         );
     }
 
-    fn provide_deal(&mut self, deal: &Deal, member: MemberIdx, _: &mut World) {
+    fn remove_deal_delta(&mut self, deal: &Deal, member: MemberIdx) {
         let core = self.core_mut();
         let provide_wakefulness =
             deal.delta.len() == 1 && deal.delta.get(Resource::Wakefulness).is_some();
@@ -248,7 +279,7 @@ pub trait Household:
             .iter()
             .chain(self.core().member_resources[member.as_idx()].iter())
             .filter_map(|&Entry(resource, amount)| {
-                let graveness = Self::graveness(resource, amount, time);
+                let graveness = self.graveness(resource, amount, time);
                 if graveness > 0.1 {
                     Some((resource, graveness))
                 } else {
@@ -347,6 +378,7 @@ pub trait Household:
     fn update_results(&mut self, resource: Resource, update: &ResultAspect, world: &mut World) {
         let done = {
             let log_as = self.id();
+            let free_storage = self.storage_capacity() - self.core().resources.stored_goods();
             let core = self.core_mut();
 
             if let DecisionState::Choosing(_, instant, ref top_problems, ref mut entries) =
@@ -371,7 +403,14 @@ pub trait Household:
                                     log_as,
                                     world,
                                 );
-                                if evaluated_deal.opening_hours.contains(instant) {
+                                if evaluated_deal.deal.delta.stored_goods() > free_storage {
+                                    debug(
+                                        LOG_T,
+                                        "Deal rejected: not enough storage",
+                                        log_as,
+                                        world,
+                                    );
+                                } else if evaluated_deal.opening_hours.contains(instant) {
                                     let new_deal_usefulness = Self::deal_usefulness(
                                         top_problems,
                                         evaluated_deal,
@@ -461,6 +500,25 @@ pub trait Household:
         let id_as_sleeper = self.id_as();
         debug(LOG_T, "Choosing deal!", self.id(), world);
 
+        let unmet_searches =
+            if let DecisionState::Choosing(_, _, _, ref entries) = self.core().decision_state {
+                entries
+                    .pairs()
+                    .filter(|(_, entry)| entry.best_deal.0.is_none())
+                    .map(|(resource, _)| *resource)
+                    .collect::<CVec<_>>()
+            } else {
+                CVec::new()
+            };
+
+        if !unmet_searches.is_empty() {
+            EconomicStatisticsID::global_first(world).record_unmet_searches(
+                self.kind_name().to_owned().into(),
+                unmet_searches,
+                world,
+            );
+        }
+
         let maybe_best_info = {
             let core = self.core_mut();
 
@@ -744,10 +802,13 @@ pub trait Household:
 
     fn on_tick(&mut self, current_instant: Instant, world: &mut World) {
         if (current_instant.ticks() + self.id().as_raw().instance_id as usize)
-            % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
+            % (UPDATE_EVERY_N_SECS * ticks_per_sim_second()) as usize
             == 0
         {
-            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
+            self.decay(
+                Duration(UPDATE_EVERY_N_SECS * ticks_per_sim_second()),
+                world,
+            );
         }
     }
 
@@ -769,7 +830,21 @@ pub trait Household:
     ) {
         let offer = self.get_offer(offer_idx);
 
-        if offer
+        if offer.out_of_stock {
+            debug(
+                LOG_T,
+                format!("Out of stock for {}", offer.deal.main_given()),
+                self.id(),
+                world,
+            );
+            requester.on_result(
+                EvaluatedSearchResult {
+                    resource: offer.deal.main_given(),
+                    evaluated_deals: CVec::new(),
+                },
+                world,
+            );
+        } else if offer
             .opening_hours
             .end_after_on_same_day(TimeOfDay::from(instant))
         {
@@ -819,6 +894,18 @@ pub trait Household:
     ) {
         let offer = self.get_offer(offer_idx).clone(); // borrow checker too dumb
         self.provide_deal(&offer.deal, offer.offering_member, world);
+
+        let resource = offer.deal.main_given();
+        let amount = offer.deal.delta.get(resource).cloned().unwrap_or(0.0);
+        let money_given = offer.deal.money_delta();
+        EconomicStatisticsID::global_first(world).record_deal(
+            self.kind_name().to_owned().into(),
+            resource,
+            amount,
+            -money_given,
+            world,
+        );
+
         requester.receive_deal(offer.deal, requester_member, world);
     }
 
@@ -830,8 +917,15 @@ pub trait Household:
         world: &mut World,
     ) {
         let offer = self.get_offer(offer_idx).clone(); // borrow checker too dumb
-        self.receive_deal(&offer.deal, offer.offering_member, world);
-        requester.provide_deal(offer.deal, requester_member, world);
+        self.add_deal_delta(&offer.deal, offer.offering_member);
+        let refund = taxes_on(-offer.deal.money_delta());
+        self.core_mut().pay_taxes(
+            -refund,
+            offer.offering_member,
+            Self::is_shared(Resource::Money),
+            world,
+        );
+        requester.undo_received_deal(offer.deal, requester_member, world);
     }
 
     fn started_using(
@@ -887,7 +981,10 @@ pub trait Household:
                 o_user != user || o_using_member != using_member
             });
 
-            if offer.is_internal
+            // full offers are withdrawn when they're not internal (see `started_using`),
+            // but offers that are out of stock need to stay withdrawn
+            if !offer.is_internal
+                && !offer.out_of_stock
                 && users_before >= offer.max_users as usize
                 && offer.users.len() < offer.max_users as usize
             {
@@ -977,6 +1074,38 @@ pub trait Household:
     fn get_ui_info(&mut self, requester: ui::HouseholdUIID, world: &mut World) {
         requester.on_household_ui_info(self.id_as(), self.core().clone(), world);
     }
+
+    fn report_statistics(&mut self, collector: EconomicStatisticsID, world: &mut World) {
+        let mut stock = self.core().resources.clone();
+        for member_resources in self.core().member_resources.iter() {
+            member_resources.give_to(&mut stock);
+        }
+
+        collector.add_household_sample(
+            self.kind_name().to_owned().into(),
+            self.core().member_resources.len() as u32,
+            stock,
+            world,
+        );
+    }
+
+    /// The position is the one of the building the household lives in
+    fn report_to_heatmaps(
+        &mut self,
+        collector: HeatmapsID,
+        position: P2,
+        instant: Instant,
+        world: &mut World,
+    ) {
+        let time = TimeOfDay::from(instant);
+        let n_members = self.core().member_resources.len();
+        let graveness_sum: f32 = (0..n_members)
+            .flat_map(|member| self.top_problems(MemberIdx::new(member), time))
+            .map(|(_, graveness)| graveness)
+            .sum();
+
+        collector.add_household_sample(position, graveness_sum / n_members.max(1) as f32, world);
+    }
 }
 
 #[derive(Compact, Clone)]
@@ -1016,7 +1145,31 @@ pub struct HouseholdCore {
     pub being_destroyed: bool,
 }
 
+/// Taxes are only paid on money coming in
+fn taxes_on(money_income: ResourceAmount) -> ResourceAmount {
+    money_income.max(0.0) * TAX_RATE
+}
+
 impl HouseholdCore {
+    /// Negative amounts refund taxes paid before
+    pub fn pay_taxes(
+        &mut self,
+        amount: ResourceAmount,
+        member: MemberIdx,
+        money_shared: bool,
+        world: &mut World,
+    ) {
+        if amount != 0.0 {
+            let money = if money_shared {
+                self.resources.mut_entry_or(Resource::Money, 0.0)
+            } else {
+                self.member_resources[member.as_idx()].mut_entry_or(Resource::Money, 0.0)
+            };
+            *money -= amount;
+            TreasuryID::global_first(world).collect_taxes(amount, InternalKey::get(), world);
+        }
+    }
+
     pub fn new(
         owner: HouseholdID,
         world: &mut World,
@@ -1048,18 +1201,56 @@ impl HouseholdCore {
             being_destroyed: false,
         }
     }
+
+    /// Members of other households currently working for this one
+    pub fn n_active_workers(&self) -> usize {
+        self.provided_offers
+            .iter()
+            .filter(|offer| offer.deal.main_given() == Resource::Money)
+            .map(|offer| offer.active_users.len())
+            .sum()
+    }
+
+    /// Withdraws offers of goods that can't be provided from the current stock anymore
+    /// and registers them again once there is enough in stock.
+    pub fn update_offer_availability(&mut self, owner: HouseholdID, world: &mut World) {
+        for (idx, offer) in self.provided_offers.iter_mut().enumerate() {
+            let resource = offer.deal.main_given();
+
+            if offer.is_internal || !resource.is_good() {
+                continue;
+            }
+
+            let needed = offer.deal.delta.get(resource).cloned().unwrap_or(0.0);
+            let in_stock = self.resources.get(resource).cloned().unwrap_or(0.0);
+            let out_of_stock = in_stock < needed;
+
+            if out_of_stock != offer.out_of_stock {
+                offer.out_of_stock = out_of_stock;
+
+                // offers with all users taken are already withdrawn
+                if offer.users.len() < offer.max_users as usize {
+                    let offer_id = OfferID {
+                        household: owner,
+                        idx: OfferIdx(idx as u16),
+                    };
+
+                    if out_of_stock {
+                        MarketID::global_first(world).withdraw(resource, offer_id, world);
+                    } else {
+                        MarketID::global_first(world).register(resource, offer_id, world);
+                    }
+                }
+            }
+        }
+    }
 }
 
 pub fn setup(system: &mut ActorSystem) {
     auto_setup(system);
     tasks::setup(system);
     family::setup(system);
-    grocery_shop::setup(system);
-    grain_farm::setup(system);
-    cow_farm::setup(system);
-    vegetable_farm::setup(system);
-    mill::setup(system);
-    bakery::setup(system);
+    generic_business::setup(system);
     neighboring_town_trade::setup(system);
     ui::auto_setup(system);
 }
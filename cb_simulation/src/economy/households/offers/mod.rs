@@ -22,6 +22,8 @@ pub struct Offer {
     pub users: CVec<(HouseholdID, Option<MemberIdx>)>,
     pub active_users: CVec<(HouseholdID, MemberIdx)>,
     pub being_withdrawn: bool,
+    /// Withdrawn from the market until the offering household restocks
+    pub out_of_stock: bool,
 }
 
 impl Offer {
@@ -41,6 +43,7 @@ impl Offer {
             is_internal,
             max_users: max_users as u32,
             being_withdrawn: false,
+            out_of_stock: false,
         }
     }
 }
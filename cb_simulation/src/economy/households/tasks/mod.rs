@@ -1,6 +1,7 @@
 use kay::{ActorSystem, World};
 use compact::CVec;
 use time::{Instant, Duration, Temporal, TemporalID};
+use parameters::SimulationParameters;
 use transport::pathfinding::RoughLocationID;
 use transport::pathfinding::trip::TripID;
 use super::super::resources::Resource;
@@ -71,7 +72,13 @@ impl TaskEndScheduler {
 }
 
 impl Temporal for TaskEndScheduler {
-    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
+    fn tick(
+        &mut self,
+        _dt: f32,
+        current_instant: Instant,
+        _: &SimulationParameters,
+        world: &mut World,
+    ) {
         while self
             .task_ends
             .last()
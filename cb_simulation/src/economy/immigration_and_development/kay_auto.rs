@@ -36,10 +36,10 @@ impl TypedID for ImmigrationManagerID {
 }
 
 impl ImmigrationManagerID {
-    pub fn spawn(time: TimeID, development_manager: DevelopmentManagerID, world: &mut World) -> Self {
+    pub fn spawn(time: TimeID, development_manager: DevelopmentManagerID, pace: Duration, world: &mut World) -> Self {
         let id = ImmigrationManagerID::from_raw(world.allocate_instance_id::<ImmigrationManager>());
         let swarm = world.local_broadcast::<ImmigrationManager>();
-        world.send(swarm, MSG_ImmigrationManager_spawn(id, time, development_manager));
+        world.send(swarm, MSG_ImmigrationManager_spawn(id, time, development_manager, pace));
         id
     }
     
@@ -49,7 +49,7 @@ impl ImmigrationManagerID {
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_ImmigrationManager_spawn(pub ImmigrationManagerID, pub TimeID, pub DevelopmentManagerID);
+struct MSG_ImmigrationManager_spawn(pub ImmigrationManagerID, pub TimeID, pub DevelopmentManagerID, pub Duration);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_ImmigrationManager_on_unit_offer(pub BuildingID, pub UnitIdx);
 
@@ -87,10 +87,10 @@ impl TypedID for DevelopmentManagerID {
 }
 
 impl DevelopmentManagerID {
-    pub fn spawn(time: TimeID, plan_manager: PlanManagerID, world: &mut World) -> Self {
+    pub fn spawn(time: TimeID, plan_manager: PlanManagerID, pace: Duration, world: &mut World) -> Self {
         let id = DevelopmentManagerID::from_raw(world.allocate_instance_id::<DevelopmentManager>());
         let swarm = world.local_broadcast::<DevelopmentManager>();
-        world.send(swarm, MSG_DevelopmentManager_spawn(id, time, plan_manager));
+        world.send(swarm, MSG_DevelopmentManager_spawn(id, time, plan_manager, pace));
         id
     }
     
@@ -104,7 +104,7 @@ impl DevelopmentManagerID {
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_DevelopmentManager_spawn(pub DevelopmentManagerID, pub TimeID, pub PlanManagerID);
+struct MSG_DevelopmentManager_spawn(pub DevelopmentManagerID, pub TimeID, pub PlanManagerID, pub Duration);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_DevelopmentManager_try_develop(pub BuildingStyle);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
@@ -122,8 +122,8 @@ pub fn auto_setup(system: &mut ActorSystem) {
     
     SleeperID::register_implementor::<ImmigrationManager>(system);
     system.add_spawner::<ImmigrationManager, _, _>(
-        |&MSG_ImmigrationManager_spawn(id, time, development_manager), world| {
-            ImmigrationManager::spawn(id, time, development_manager, world)
+        |&MSG_ImmigrationManager_spawn(id, time, development_manager, pace), world| {
+            ImmigrationManager::spawn(id, time, development_manager, pace, world)
         }, false
     );
     
@@ -134,8 +134,8 @@ pub fn auto_setup(system: &mut ActorSystem) {
     );
     SleeperID::register_implementor::<DevelopmentManager>(system);
     system.add_spawner::<DevelopmentManager, _, _>(
-        |&MSG_DevelopmentManager_spawn(id, time, plan_manager), world| {
-            DevelopmentManager::spawn(id, time, plan_manager, world)
+        |&MSG_DevelopmentManager_spawn(id, time, plan_manager, pace), world| {
+            DevelopmentManager::spawn(id, time, plan_manager, pace, world)
         }, false
     );
     
@@ -8,42 +8,25 @@ const LOG_T: &str = "Immigration/Development";
 
 use economy::households::household_kinds;
 use self::household_kinds::family::FamilyID;
-use self::household_kinds::grocery_shop::GroceryShopID;
-use self::household_kinds::cow_farm::CowFarmID;
-use self::household_kinds::grain_farm::GrainFarmID;
-use self::household_kinds::vegetable_farm::VegetableFarmID;
-use self::household_kinds::mill::MillID;
-use self::household_kinds::bakery::BakeryID;
+use self::household_kinds::generic_business::{business_kinds, BusinessKindIdx, GenericBusinessID};
 use self::household_kinds::neighboring_town_trade::NeighboringTownTradeID;
 use land_use::buildings::BuildingStyle;
 use land_use::vacant_lots::VacantLotID;
 use land_use::zone_planning::BuildingIntent;
 use planning::{PlanManagerID, Project, PrototypeID, Plan, GestureID, Gesture, GestureIntent};
-
-// TODO: somehow get rid of this horrible duplication by having something like
-// a pointer to an abstract Household trait...
+use parameters::SimulationParameters;
 
 #[derive(Copy, Clone, Debug)]
 pub enum HouseholdTypeToSpawn {
     Family,
-    GroceryShop,
-    GrainFarm,
-    CowFarm,
-    VegetableFarm,
-    Mill,
-    Bakery,
+    Business(BusinessKindIdx),
     NeighboringTownTrade,
 }
 
 pub fn unit_type_for(household_type: HouseholdTypeToSpawn) -> UnitType {
     match household_type {
         HouseholdTypeToSpawn::Family => UnitType::Dwelling,
-        HouseholdTypeToSpawn::GroceryShop => UnitType::Retail,
-        HouseholdTypeToSpawn::GrainFarm
-        | HouseholdTypeToSpawn::CowFarm
-        | HouseholdTypeToSpawn::VegetableFarm => UnitType::Agriculture,
-        HouseholdTypeToSpawn::Mill => UnitType::Mill,
-        HouseholdTypeToSpawn::Bakery => UnitType::Bakery,
+        HouseholdTypeToSpawn::Business(kind_idx) => kind_idx.get().unit_type,
         HouseholdTypeToSpawn::NeighboringTownTrade => UnitType::NeighboringTownTrade,
     }
 }
@@ -51,12 +34,7 @@ pub fn unit_type_for(household_type: HouseholdTypeToSpawn) -> UnitType {
 pub fn building_style_for(household_type: HouseholdTypeToSpawn) -> BuildingStyle {
     match household_type {
         HouseholdTypeToSpawn::Family => BuildingStyle::FamilyHouse,
-        HouseholdTypeToSpawn::GroceryShop => BuildingStyle::GroceryShop,
-        HouseholdTypeToSpawn::GrainFarm
-        | HouseholdTypeToSpawn::CowFarm
-        | HouseholdTypeToSpawn::VegetableFarm => BuildingStyle::Field,
-        HouseholdTypeToSpawn::Mill => BuildingStyle::Mill,
-        HouseholdTypeToSpawn::Bakery => BuildingStyle::Bakery,
+        HouseholdTypeToSpawn::Business(kind_idx) => kind_idx.get().building_style,
         HouseholdTypeToSpawn::NeighboringTownTrade => BuildingStyle::NeighboringTownConnection,
     }
 }
@@ -66,6 +44,7 @@ pub struct ImmigrationManager {
     id: ImmigrationManagerID,
     time: TimeID,
     development_manager: DevelopmentManagerID,
+    pace: Duration,
     state: ImmigrationManagerState,
 }
 
@@ -74,14 +53,16 @@ impl ImmigrationManager {
         id: ImmigrationManagerID,
         time: TimeID,
         development_manager: DevelopmentManagerID,
+        pace: Duration,
         world: &mut World,
     ) -> ImmigrationManager {
-        time.wake_up_in(IMMIGRATION_PACE.into(), id.into(), world);
+        time.wake_up_in(pace.into(), id.into(), world);
 
         ImmigrationManager {
             id,
             time,
             development_manager,
+            pace,
             state: ImmigrationManagerState::Idle,
         }
     }
@@ -93,57 +74,32 @@ pub enum ImmigrationManagerState {
     FindingBuilding(HouseholdTypeToSpawn),
 }
 
-const IMMIGRATION_PACE: Duration = Duration(10);
-
 impl Sleeper for ImmigrationManager {
     fn wake(&mut self, current_instant: Instant, world: &mut World) {
         self.state = match self.state {
             ImmigrationManagerState::Idle => {
                 let family_share = 1.0;
-                let grocery_share = 0.2;
-                let cow_farm_share = 0.3;
-                let veg_farm_share = 0.26;
-                let grain_farm_share = 0.2; //0.0016;
-                let mill_share = 0.2; //0.001;
-                let bakery_share = 0.2; //0.01;
+                let business_kinds = business_kinds();
 
                 let total_share = family_share
-                    + grocery_share
-                    + cow_farm_share
-                    + veg_farm_share
-                    + grain_farm_share
-                    + mill_share
-                    + bakery_share;
+                    + business_kinds
+                        .iter()
+                        .map(|kind| kind.immigration_share)
+                        .sum::<f32>();
 
-                let dot = seed(current_instant).gen_range(0.0, total_share);
+                let mut dot = seed(current_instant).gen_range(0.0, total_share) - family_share;
 
-                let household_type_to_spawn = if dot < family_share {
+                let household_type_to_spawn = if dot < 0.0 {
                     HouseholdTypeToSpawn::Family
-                } else if dot < family_share + grocery_share {
-                    HouseholdTypeToSpawn::GroceryShop
-                } else if dot < family_share + grocery_share + cow_farm_share {
-                    HouseholdTypeToSpawn::CowFarm
-                } else if dot < family_share + grocery_share + cow_farm_share + veg_farm_share {
-                    HouseholdTypeToSpawn::VegetableFarm
-                } else if dot
-                    < family_share
-                        + grocery_share
-                        + cow_farm_share
-                        + veg_farm_share
-                        + grain_farm_share
-                {
-                    HouseholdTypeToSpawn::GrainFarm
-                } else if dot
-                    < family_share
-                        + grocery_share
-                        + cow_farm_share
-                        + veg_farm_share
-                        + grain_farm_share
-                        + mill_share
-                {
-                    HouseholdTypeToSpawn::Mill
                 } else {
-                    HouseholdTypeToSpawn::Bakery
+                    let kind_idx = business_kinds
+                        .iter()
+                        .position(|kind| {
+                            dot -= kind.immigration_share;
+                            dot < 0.0
+                        })
+                        .unwrap_or(business_kinds.len() - 1);
+                    HouseholdTypeToSpawn::Business(BusinessKindIdx(kind_idx))
                 };
 
                 debug(
@@ -173,7 +129,7 @@ impl Sleeper for ImmigrationManager {
         };
 
         self.time
-            .wake_up_in(IMMIGRATION_PACE.into(), self.id.into(), world);
+            .wake_up_in(self.pace.into(), self.id.into(), world);
     }
 }
 
@@ -188,23 +144,8 @@ impl ImmigrationManager {
                     HouseholdTypeToSpawn::Family => {
                         FamilyID::move_into(3, building_id, self.time, world).into()
                     }
-                    HouseholdTypeToSpawn::GroceryShop => {
-                        GroceryShopID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::GrainFarm => {
-                        GrainFarmID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::CowFarm => {
-                        CowFarmID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::VegetableFarm => {
-                        VegetableFarmID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::Mill => {
-                        MillID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::Bakery => {
-                        BakeryID::move_into(building_id, self.time, world).into()
+                    HouseholdTypeToSpawn::Business(kind_idx) => {
+                        GenericBusinessID::move_into(kind_idx, building_id, self.time, world).into()
                     }
                     HouseholdTypeToSpawn::NeighboringTownTrade => {
                         NeighboringTownTradeID::move_into(building_id, self.time, world).into()
@@ -225,6 +166,7 @@ pub struct DevelopmentManager {
     id: DevelopmentManagerID,
     time: TimeID,
     plan_manager: PlanManagerID,
+    pace: Duration,
     building_to_develop: COption<BuildingStyle>,
 }
 
@@ -233,12 +175,14 @@ impl DevelopmentManager {
         id: DevelopmentManagerID,
         time: TimeID,
         plan_manager: PlanManagerID,
+        pace: Duration,
         _world: &mut World,
     ) -> DevelopmentManager {
         DevelopmentManager {
             id,
             time,
             plan_manager,
+            pace,
             building_to_develop: COption(None),
         }
     }
@@ -254,7 +198,7 @@ impl DevelopmentManager {
             self.building_to_develop = COption(Some(building_style));
             VacantLotID::global_broadcast(world).suggest_lot(building_style, self.id, world);
             self.time
-                .wake_up_in(IMMIGRATION_PACE.into(), self.id.into(), world);
+                .wake_up_in(self.pace.into(), self.id.into(), world);
         }
     }
 
@@ -301,9 +245,15 @@ pub fn setup(system: &mut ActorSystem) {
     auto_setup(system);
 }
 
-pub fn spawn(world: &mut World, time: TimeID, plan_manager: PlanManagerID) {
-    let development_manager = DevelopmentManagerID::spawn(time, plan_manager, world);
-    ImmigrationManagerID::spawn(time, development_manager, world);
+pub fn spawn(
+    world: &mut World,
+    time: TimeID,
+    plan_manager: PlanManagerID,
+    parameters: &SimulationParameters,
+) {
+    let pace = parameters.immigration_pace();
+    let development_manager = DevelopmentManagerID::spawn(time, plan_manager, pace, world);
+    ImmigrationManagerID::spawn(time, development_manager, pace, world);
 }
 
 mod kay_auto;
@@ -124,7 +124,11 @@ struct MSG_Market_register(pub Resource, pub OfferID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Market_withdraw(pub Resource, pub OfferID);
 
-
+impl Into<InspectableID> for MarketID {
+    fn into(self) -> InspectableID {
+        InspectableID::from_raw(self.as_raw())
+    }
+}
 impl Actor for TripCostEstimator {
     type ID = TripCostEstimatorID;
 
@@ -187,7 +191,7 @@ impl Into<DistanceRequesterID> for TripCostEstimatorID {
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     EvaluationRequesterID::register_trait(system);
-    
+    InspectableID::register_implementor::<Market>(system);
     system.add_spawner::<Market, _, _>(
         |&MSG_Market_spawn(id, ), world| {
             Market::spawn(id, world)
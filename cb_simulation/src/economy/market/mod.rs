@@ -1,10 +1,11 @@
-use kay::{ActorSystem, Fate, World, Actor};
+use kay::{ActorSystem, Fate, World, Actor, TypedID};
 use compact::{CVec, CDict};
 use super::resources::{Inventory, Entry, Resource, ResourceAmount};
 use super::households::OfferID;
 use time::{TimeOfDayRange, Duration, Instant};
 use transport::pathfinding::{RoughLocationID, LocationRequesterID};
 use log::warn;
+use inspection::{Inspectable, InspectableID, InspectorID, StateSummary};
 const LOG_T: &str = "Market";
 
 #[derive(Compact, Clone, Serialize, Deserialize)]
@@ -31,6 +32,11 @@ impl Deal {
             .next()
             .unwrap()
     }
+
+    /// Money gained by the requester of the deal
+    pub fn money_delta(&self) -> ResourceAmount {
+        self.delta.get(Resource::Money).cloned().unwrap_or(0.0)
+    }
 }
 
 pub trait EvaluationRequester {
@@ -87,6 +93,19 @@ impl Market {
     }
 }
 
+impl Inspectable for Market {
+    fn inspect(&mut self, requester: InspectorID, world: &mut World) {
+        let summary = self.offers_by_resource.pairs().fold(
+            StateSummary::new("Market"),
+            |summary, (resource, offers)| {
+                summary.field(&format!("offers of {:?}", resource), offers.len())
+            },
+        );
+
+        requester.on_inspected(self.id.as_raw(), summary, world);
+    }
+}
+
 #[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct EvaluatedDeal {
     pub offer: OfferID,
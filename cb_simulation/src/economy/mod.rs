@@ -2,20 +2,33 @@ use kay::{ActorSystem, World};
 
 use time::TimeID;
 use planning::PlanManagerID;
+use parameters::SimulationParameters;
 
 pub mod resources;
+pub mod production;
 pub mod market;
 pub mod households;
 pub mod immigration_and_development;
+pub mod treasury;
+pub mod statistics;
 
 pub fn setup(system: &mut ActorSystem) {
     market::setup(system);
     households::setup(system);
     immigration_and_development::setup(system);
+    treasury::setup(system);
+    statistics::setup(system);
 }
 
-pub fn spawn(world: &mut World, time: TimeID, plan_manager: PlanManagerID) {
+pub fn spawn(
+    world: &mut World,
+    time: TimeID,
+    plan_manager: PlanManagerID,
+    parameters: &SimulationParameters,
+) {
     market::spawn(world);
+    treasury::spawn(world);
+    statistics::spawn(world);
     households::spawn(world);
-    immigration_and_development::spawn(world, time, plan_manager);
+    immigration_and_development::spawn(world, time, plan_manager, parameters);
 }
@@ -0,0 +1,122 @@
+use compact::CVec;
+use super::resources::{Resource, ResourceAmount, Inventory};
+
+/// Converts inputs into outputs, proportionally to the worker-hours put into it.
+/// Production stops as soon as any of the inputs runs out
+/// or when there is no more space to store the outputs.
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub struct Recipe {
+    /// Consumed per worker-hour
+    pub inputs: CVec<(Resource, ResourceAmount)>,
+    /// Produced per worker-hour
+    pub outputs: CVec<(Resource, ResourceAmount)>,
+}
+
+impl Recipe {
+    /// How many goods taken together are added to the stock per worker-hour
+    fn net_goods_per_worker_hour(&self) -> ResourceAmount {
+        let produced: ResourceAmount = self
+            .outputs
+            .iter()
+            .filter(|&&(resource, _)| resource.is_good())
+            .map(|&(_, amount)| amount)
+            .sum();
+        let consumed: ResourceAmount = self
+            .inputs
+            .iter()
+            .filter(|&&(resource, _)| resource.is_good())
+            .map(|&(_, amount)| amount)
+            .sum();
+        produced - consumed
+    }
+
+    pub fn max_worker_hours(&self, stock: &Inventory, storage_capacity: ResourceAmount) -> f32 {
+        let net_goods = self.net_goods_per_worker_hour();
+        let limited_by_storage = if net_goods > 0.0 {
+            (storage_capacity - stock.stored_goods()).max(0.0) / net_goods
+        } else {
+            ::std::f32::INFINITY
+        };
+
+        self.inputs
+            .iter()
+            .filter(|&&(_, amount)| amount > 0.0)
+            .map(|&(resource, amount)| {
+                stock.get(resource).cloned().unwrap_or(0.0).max(0.0) / amount
+            })
+            .fold(limited_by_storage, f32::min)
+    }
+
+    /// Returns the worker-hours that could actually be used
+    pub fn produce(
+        &self,
+        stock: &mut Inventory,
+        storage_capacity: ResourceAmount,
+        worker_hours: f32,
+    ) -> f32 {
+        let used_worker_hours = worker_hours.min(self.max_worker_hours(stock, storage_capacity));
+
+        if used_worker_hours > 0.0 {
+            for &(resource, amount) in self.inputs.iter() {
+                *stock.mut_entry_or(resource, 0.0) -= amount * used_worker_hours;
+            }
+
+            for &(resource, amount) in self.outputs.iter() {
+                *stock.mut_entry_or(resource, 0.0) += amount * used_worker_hours;
+            }
+        }
+
+        used_worker_hours.max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::resources::Resource::*;
+
+    fn milling() -> Recipe {
+        Recipe {
+            inputs: vec![(Grain, 2.0), (Money, 1.0)].into(),
+            outputs: vec![(Flour, 1.5)].into(),
+        }
+    }
+
+    #[test]
+    fn production_stops_when_an_input_runs_out() {
+        let mut stock: Inventory = vec![(Grain, 3.0), (Money, 10.0)].into_iter().collect();
+
+        let used_worker_hours = milling().produce(&mut stock, 1000.0, 4.0);
+
+        assert_eq!(used_worker_hours, 1.5);
+        assert_eq!(stock.get(Grain), Some(&0.0));
+        assert_eq!(stock.get(Money), Some(&8.5));
+        assert_eq!(stock.get(Flour), Some(&2.25));
+    }
+
+    #[test]
+    fn nothing_is_produced_without_an_input() {
+        let mut stock: Inventory = vec![(Grain, 3.0)].into_iter().collect();
+
+        let used_worker_hours = milling().produce(&mut stock, 1000.0, 4.0);
+
+        assert_eq!(used_worker_hours, 0.0);
+        assert_eq!(stock.get(Grain), Some(&3.0));
+        assert_eq!(stock.get(Flour), None);
+    }
+
+    #[test]
+    fn production_stops_when_storage_is_full() {
+        let baking = Recipe {
+            inputs: vec![(Flour, 1.0)].into(),
+            outputs: vec![(BakedGoods, 3.0)].into(),
+        };
+        let mut stock: Inventory = vec![(Flour, 10.0)].into_iter().collect();
+
+        // every worker-hour adds two goods to the stock, only four more fit
+        let used_worker_hours = baking.produce(&mut stock, 14.0, 8.0);
+
+        assert_eq!(used_worker_hours, 2.0);
+        assert_eq!(stock.stored_goods(), 14.0);
+    }
+}
@@ -49,9 +49,30 @@ impl Resource {
              * Devices => "Devices", */
         }
     }
+
+    /// Fraction of a stock that spoils per day
+    pub fn perishability(self) -> f32 {
+        match self {
+            Groceries => 0.05,
+            Produce => 0.1,
+            BakedGoods => 0.3,
+            Meat => 0.15,
+            DairyGoods => 0.1,
+            _ => 0.0,
+        }
+    }
+
+    /// Whether this is a physical good that takes up storage space
+    pub fn is_good(self) -> bool {
+        match self {
+            Wakefulness | Satiety | Money => false,
+            _ => true,
+        }
+    }
 }
 
 use compact::{CVec, Compact};
+use time::Duration;
 
 pub type ResourceAmount = f32;
 
@@ -191,4 +212,62 @@ impl Inventory {
             }
         }
     }
+
+    pub fn spoil(&mut self, dt: Duration) {
+        for &mut Entry(resource, ref mut amount) in self.entries.iter_mut() {
+            if *amount > 0.0 {
+                *amount -= *amount * (resource.perishability() * dt.as_days()).min(1.0);
+            }
+        }
+    }
+
+    pub fn stored_goods(&self) -> ResourceAmount {
+        self.iter()
+            .filter(|&&Entry(resource, _)| resource.is_good())
+            .map(|&Entry(_, amount)| amount.max(0.0))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perishable_goods_spoil() {
+        let mut inventory: Inventory = vec![(BakedGoods, 10.0), (Grain, 10.0), (Meat, -4.0)]
+            .into_iter()
+            .collect();
+
+        inventory.spoil(Duration::from_hours(24));
+
+        assert!((inventory.get(BakedGoods).unwrap() - 7.0).abs() < 0.001);
+        assert_eq!(inventory.get(Grain), Some(&10.0));
+        // debts of goods don't spoil
+        assert_eq!(inventory.get(Meat), Some(&-4.0));
+    }
+
+    #[test]
+    fn goods_spoil_at_most_completely() {
+        let mut inventory: Inventory = vec![(BakedGoods, 10.0)].into_iter().collect();
+
+        inventory.spoil(Duration::from_hours(24 * 10));
+
+        assert_eq!(inventory.get(BakedGoods), Some(&0.0));
+    }
+
+    #[test]
+    fn only_goods_take_up_storage() {
+        let inventory: Inventory = vec![
+            (Money, 500.0),
+            (Wakefulness, 1.0),
+            (Groceries, 20.0),
+            (Flour, 5.0),
+            (Meat, -3.0),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(inventory.stored_goods(), 25.0);
+    }
 }
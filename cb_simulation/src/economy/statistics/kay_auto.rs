@@ -0,0 +1,160 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct EconomicStatisticsUIID {
+    _raw_id: RawID
+}
+
+pub struct EconomicStatisticsUIRepresentative;
+
+impl ActorOrActorTrait for EconomicStatisticsUIRepresentative {
+    type ID = EconomicStatisticsUIID;
+}
+
+impl TypedID for EconomicStatisticsUIID {
+    type Target = EconomicStatisticsUIRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        EconomicStatisticsUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<A: Actor + EconomicStatisticsUI> TraitIDFrom<A> for EconomicStatisticsUIID {}
+
+impl EconomicStatisticsUIID {
+    pub fn on_economic_statistics(self, report: EconomicStatisticsReport, world: &mut World) {
+        world.send(self.as_raw(), MSG_EconomicStatisticsUI_on_economic_statistics(report));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<EconomicStatisticsUIRepresentative>();
+        system.register_trait_message::<MSG_EconomicStatisticsUI_on_economic_statistics>();
+    }
+
+    pub fn register_implementor<A: Actor + EconomicStatisticsUI>(system: &mut ActorSystem) {
+        system.register_implementor::<A, EconomicStatisticsUIRepresentative>();
+        system.add_handler::<A, _, _>(
+            |&MSG_EconomicStatisticsUI_on_economic_statistics(ref report), instance, world| {
+                instance.on_economic_statistics(report, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EconomicStatisticsUI_on_economic_statistics(pub EconomicStatisticsReport);
+
+impl Actor for EconomicStatistics {
+    type ID = EconomicStatisticsID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct EconomicStatisticsID {
+    _raw_id: RawID
+}
+
+impl TypedID for EconomicStatisticsID {
+    type Target = EconomicStatistics;
+
+    fn from_raw(id: RawID) -> Self {
+        EconomicStatisticsID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl EconomicStatisticsID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = EconomicStatisticsID::from_raw(world.allocate_instance_id::<EconomicStatistics>());
+        let swarm = world.local_broadcast::<EconomicStatistics>();
+        world.send(swarm, MSG_EconomicStatistics_spawn(id, ));
+        id
+    }
+    
+    pub fn add_household_sample(self, kind: CString, n_members: u32, stock: Inventory, world: &mut World) {
+        world.send(self.as_raw(), MSG_EconomicStatistics_add_household_sample(kind, n_members, stock));
+    }
+    
+    pub fn record_deal(self, provider_kind: CString, resource: Resource, amount: ResourceAmount, money_paid: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_EconomicStatistics_record_deal(provider_kind, resource, amount, money_paid));
+    }
+    
+    pub fn record_unmet_searches(self, searcher_kind: CString, resources: CVec < Resource >, world: &mut World) {
+        world.send(self.as_raw(), MSG_EconomicStatistics_record_unmet_searches(searcher_kind, resources));
+    }
+    
+    pub fn get_report(self, requester: EconomicStatisticsUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_EconomicStatistics_get_report(requester));
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EconomicStatistics_spawn(pub EconomicStatisticsID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EconomicStatistics_add_household_sample(pub CString, pub u32, pub Inventory);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EconomicStatistics_record_deal(pub CString, pub Resource, pub ResourceAmount, pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EconomicStatistics_record_unmet_searches(pub CString, pub CVec < Resource >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EconomicStatistics_get_report(pub EconomicStatisticsUIID);
+
+impl Into<TemporalID> for EconomicStatisticsID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    EconomicStatisticsUIID::register_trait(system);
+    TemporalID::register_implementor::<EconomicStatistics>(system);
+    system.add_spawner::<EconomicStatistics, _, _>(
+        |&MSG_EconomicStatistics_spawn(id, ), world| {
+            EconomicStatistics::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<EconomicStatistics, _, _>(
+        |&MSG_EconomicStatistics_add_household_sample(ref kind, n_members, ref stock), instance, world| {
+            instance.add_household_sample(kind, n_members, stock, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<EconomicStatistics, _, _>(
+        |&MSG_EconomicStatistics_record_deal(ref provider_kind, resource, amount, money_paid), instance, world| {
+            instance.record_deal(provider_kind, resource, amount, money_paid, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<EconomicStatistics, _, _>(
+        |&MSG_EconomicStatistics_record_unmet_searches(ref searcher_kind, ref resources), instance, world| {
+            instance.record_unmet_searches(searcher_kind, resources, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<EconomicStatistics, _, _>(
+        |&MSG_EconomicStatistics_get_report(requester), instance, world| {
+            instance.get_report(requester, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,342 @@
+use kay::{ActorSystem, World, TypedID};
+use compact::{CVec, CString};
+use time::{Temporal, TemporalID, Instant, Duration};
+use parameters::SimulationParameters;
+use economy::resources::{Resource, ResourceAmount, Inventory, Entry};
+use economy::households::HouseholdID;
+
+use log::info;
+const LOG_T: &str = "Economic Statistics";
+
+const SAMPLE_EVERY_N_HOURS: usize = 1;
+const SUMMARY_EVERY_N_HOURS: usize = 24;
+/// One week of hourly samples
+const HISTORY_LENGTH: usize = 7 * 24;
+
+#[derive(Copy, Clone, Default, Serialize, Deserialize)]
+pub struct ResourceSample {
+    pub total_stock: ResourceAmount,
+    pub deals_completed: u32,
+    /// Money paid per unit, zero if there were no deals
+    pub average_price: f32,
+    pub unmet_searches: u32,
+}
+
+#[derive(Copy, Clone, Default, Serialize, Deserialize)]
+pub struct HouseholdKindSample {
+    pub n_households: u32,
+    pub population: u32,
+    pub deals_completed: u32,
+    pub unmet_searches: u32,
+}
+
+#[derive(Copy, Clone, Default)]
+struct ResourceAccumulator {
+    total_stock: ResourceAmount,
+    deals_completed: u32,
+    amount_traded: ResourceAmount,
+    money_paid: ResourceAmount,
+    unmet_searches: u32,
+}
+
+impl ResourceAccumulator {
+    fn to_sample(&self) -> ResourceSample {
+        ResourceSample {
+            total_stock: self.total_stock,
+            deals_completed: self.deals_completed,
+            average_price: if self.amount_traded > 0.0 {
+                self.money_paid / self.amount_traded
+            } else {
+                0.0
+            },
+            unmet_searches: self.unmet_searches,
+        }
+    }
+}
+
+// Histories are ring buffers of at most HISTORY_LENGTH samples,
+// `oldest` is the index of the sample that gets overwritten next
+
+fn push_to_ring<S: Copy>(samples: &mut CVec<S>, oldest: &mut u32, sample: S) {
+    if samples.len() < HISTORY_LENGTH {
+        samples.push(sample);
+    } else {
+        samples[*oldest as usize] = sample;
+        *oldest = (*oldest + 1) % HISTORY_LENGTH as u32;
+    }
+}
+
+fn ring_in_order<S: Copy>(samples: &[S], oldest: u32) -> CVec<S> {
+    samples[oldest as usize..]
+        .iter()
+        .chain(samples[..oldest as usize].iter())
+        .cloned()
+        .collect()
+}
+
+fn ring_latest<S: Copy>(samples: &[S], oldest: u32) -> Option<S> {
+    if samples.is_empty() {
+        None
+    } else {
+        Some(samples[(oldest as usize + samples.len() - 1) % samples.len()])
+    }
+}
+
+#[derive(Compact, Clone)]
+struct ResourceStatistics {
+    resource: Resource,
+    current: ResourceAccumulator,
+    history: CVec<ResourceSample>,
+    oldest: u32,
+}
+
+#[derive(Compact, Clone)]
+struct HouseholdKindStatistics {
+    kind: CString,
+    current: HouseholdKindSample,
+    history: CVec<HouseholdKindSample>,
+    oldest: u32,
+}
+
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub struct ResourceHistory {
+    pub resource: Resource,
+    pub samples: CVec<ResourceSample>,
+}
+
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub struct HouseholdKindHistory {
+    pub kind: CString,
+    pub samples: CVec<HouseholdKindSample>,
+}
+
+/// All histories in chronological order. Histories that started later
+/// than others are shorter and line up with the end of `sample_instants`.
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub struct EconomicStatisticsReport {
+    pub sample_instants: CVec<Instant>,
+    pub resources: CVec<ResourceHistory>,
+    pub household_kinds: CVec<HouseholdKindHistory>,
+}
+
+pub trait EconomicStatisticsUI {
+    fn on_economic_statistics(&mut self, report: &EconomicStatisticsReport, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+pub struct EconomicStatistics {
+    id: EconomicStatisticsID,
+    sample_instants: CVec<Instant>,
+    oldest_sample: u32,
+    resources: CVec<ResourceStatistics>,
+    household_kinds: CVec<HouseholdKindStatistics>,
+}
+
+impl EconomicStatistics {
+    pub fn spawn(id: EconomicStatisticsID, _: &mut World) -> EconomicStatistics {
+        EconomicStatistics {
+            id,
+            sample_instants: CVec::new(),
+            oldest_sample: 0,
+            resources: CVec::new(),
+            household_kinds: CVec::new(),
+        }
+    }
+
+    fn resource_mut(&mut self, resource: Resource) -> &mut ResourceAccumulator {
+        let idx = if let Some(idx) = self.resources.iter().position(|r| r.resource == resource) {
+            idx
+        } else {
+            self.resources.push(ResourceStatistics {
+                resource,
+                current: ResourceAccumulator::default(),
+                history: CVec::new(),
+                oldest: 0,
+            });
+            self.resources.len() - 1
+        };
+
+        &mut self.resources[idx].current
+    }
+
+    fn household_kind_mut(&mut self, kind: &CString) -> &mut HouseholdKindSample {
+        let idx = if let Some(idx) = self.household_kinds.iter().position(|k| *k.kind == **kind) {
+            idx
+        } else {
+            self.household_kinds.push(HouseholdKindStatistics {
+                kind: kind.clone(),
+                current: HouseholdKindSample::default(),
+                history: CVec::new(),
+                oldest: 0,
+            });
+            self.household_kinds.len() - 1
+        };
+
+        &mut self.household_kinds[idx].current
+    }
+
+    pub fn add_household_sample(
+        &mut self,
+        kind: &CString,
+        n_members: u32,
+        stock: &Inventory,
+        _: &mut World,
+    ) {
+        {
+            let kind_sample = self.household_kind_mut(kind);
+            kind_sample.n_households += 1;
+            kind_sample.population += n_members;
+        }
+
+        for &Entry(resource, amount) in stock.iter() {
+            self.resource_mut(resource).total_stock += amount.max(0.0);
+        }
+    }
+
+    pub fn record_deal(
+        &mut self,
+        provider_kind: &CString,
+        resource: Resource,
+        amount: ResourceAmount,
+        money_paid: ResourceAmount,
+        _: &mut World,
+    ) {
+        self.household_kind_mut(provider_kind).deals_completed += 1;
+
+        let resource_accumulator = self.resource_mut(resource);
+        resource_accumulator.deals_completed += 1;
+        if resource != Resource::Money && money_paid > 0.0 {
+            resource_accumulator.amount_traded += amount;
+            resource_accumulator.money_paid += money_paid;
+        }
+    }
+
+    pub fn record_unmet_searches(
+        &mut self,
+        searcher_kind: &CString,
+        resources: &CVec<Resource>,
+        _: &mut World,
+    ) {
+        self.household_kind_mut(searcher_kind).unmet_searches += resources.len() as u32;
+
+        for &resource in resources.iter() {
+            self.resource_mut(resource).unmet_searches += 1;
+        }
+    }
+
+    fn finish_sample(&mut self, instant: Instant) {
+        push_to_ring(&mut self.sample_instants, &mut self.oldest_sample, instant);
+
+        for statistics in self.resources.iter_mut() {
+            let sample = statistics.current.to_sample();
+            push_to_ring(&mut statistics.history, &mut statistics.oldest, sample);
+            statistics.current = ResourceAccumulator::default();
+        }
+
+        for statistics in self.household_kinds.iter_mut() {
+            let sample = statistics.current;
+            push_to_ring(&mut statistics.history, &mut statistics.oldest, sample);
+            statistics.current = HouseholdKindSample::default();
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        let household_kinds = self
+            .household_kinds
+            .iter()
+            .filter_map(|statistics| {
+                ring_latest(&statistics.history, statistics.oldest).map(|sample| {
+                    format!(
+                        "{}: {} households, {} people",
+                        &*statistics.kind, sample.n_households, sample.population
+                    )
+                })
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let resources = self
+            .resources
+            .iter()
+            .filter_map(|statistics| {
+                ring_latest(&statistics.history, statistics.oldest).map(|sample| {
+                    format!(
+                        "{}: {:.0} in stock, {} deals at {:.2}, {} unmet searches",
+                        statistics.resource,
+                        sample.total_stock,
+                        sample.deals_completed,
+                        sample.average_price,
+                        sample.unmet_searches
+                    )
+                })
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("{}\n{}", household_kinds, resources)
+    }
+
+    pub fn get_report(&mut self, requester: EconomicStatisticsUIID, world: &mut World) {
+        let report = EconomicStatisticsReport {
+            sample_instants: ring_in_order(&self.sample_instants, self.oldest_sample),
+            resources: self
+                .resources
+                .iter()
+                .map(|statistics| ResourceHistory {
+                    resource: statistics.resource,
+                    samples: ring_in_order(&statistics.history, statistics.oldest),
+                })
+                .collect(),
+            household_kinds: self
+                .household_kinds
+                .iter()
+                .map(|statistics| HouseholdKindHistory {
+                    kind: statistics.kind.clone(),
+                    samples: ring_in_order(&statistics.history, statistics.oldest),
+                })
+                .collect(),
+        };
+
+        requester.on_economic_statistics(report, world);
+    }
+}
+
+impl Temporal for EconomicStatistics {
+    fn tick(
+        &mut self,
+        _dt: f32,
+        current_instant: Instant,
+        _: &SimulationParameters,
+        world: &mut World,
+    ) {
+        let ticks_per_hour = Duration::from_hours(1).0 as usize;
+
+        if current_instant.ticks() % (SAMPLE_EVERY_N_HOURS * ticks_per_hour) == 0 {
+            // households report back during the following sample period,
+            // so each sample contains their state from the start of the period
+            if current_instant.ticks() > 0 {
+                self.finish_sample(current_instant);
+            }
+
+            HouseholdID::global_broadcast(world).report_statistics(self.id, world);
+        }
+
+        if current_instant.ticks() > 0
+            && current_instant.ticks() % (SUMMARY_EVERY_N_HOURS * ticks_per_hour) == 0
+        {
+            info(LOG_T, self.summary(), self.id, world);
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<EconomicStatistics>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) -> EconomicStatisticsID {
+    EconomicStatisticsID::spawn(world)
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,184 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct TreasuryUIID {
+    _raw_id: RawID
+}
+
+pub struct TreasuryUIRepresentative;
+
+impl ActorOrActorTrait for TreasuryUIRepresentative {
+    type ID = TreasuryUIID;
+}
+
+impl TypedID for TreasuryUIID {
+    type Target = TreasuryUIRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        TreasuryUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<A: Actor + TreasuryUI> TraitIDFrom<A> for TreasuryUIID {}
+
+impl TreasuryUIID {
+    pub fn on_treasury_report(self, report: TreasuryReport, world: &mut World) {
+        world.send(self.as_raw(), MSG_TreasuryUI_on_treasury_report(report));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<TreasuryUIRepresentative>();
+        system.register_trait_message::<MSG_TreasuryUI_on_treasury_report>();
+    }
+
+    pub fn register_implementor<A: Actor + TreasuryUI>(system: &mut ActorSystem) {
+        system.register_implementor::<A, TreasuryUIRepresentative>();
+        system.add_handler::<A, _, _>(
+            |&MSG_TreasuryUI_on_treasury_report(ref report), instance, world| {
+                instance.on_treasury_report(report, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TreasuryUI_on_treasury_report(pub TreasuryReport);
+
+impl Actor for Treasury {
+    type ID = TreasuryID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct TreasuryID {
+    _raw_id: RawID
+}
+
+impl TypedID for TreasuryID {
+    type Target = Treasury;
+
+    fn from_raw(id: RawID) -> Self {
+        TreasuryID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TreasuryID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = TreasuryID::from_raw(world.allocate_instance_id::<Treasury>());
+        let swarm = world.local_broadcast::<Treasury>();
+        world.send(swarm, MSG_Treasury_spawn(id, ));
+        id
+    }
+    
+    pub fn collect_taxes(self, amount: ResourceAmount, key: InternalKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_collect_taxes(amount, key));
+    }
+    
+    pub fn lane_built(self, length: N, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_lane_built(length));
+    }
+    
+    pub fn lane_unbuilt(self, length: N, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_lane_unbuilt(length));
+    }
+    
+    pub fn request_funds(self, project_id: ProjectID, cost: ResourceAmount, nonce: FundingNonce, requester: PlanManagerID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_request_funds(project_id, cost, nonce, requester));
+    }
+    
+    pub fn settle_funds(self, nonce: FundingNonce, spent: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_settle_funds(nonce, spent));
+    }
+    
+    pub fn get_report(self, requester: TreasuryUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_get_report(requester));
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_spawn(pub TreasuryID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_collect_taxes(pub ResourceAmount, pub InternalKey);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_lane_built(pub N);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_lane_unbuilt(pub N);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_request_funds(pub ProjectID, pub ResourceAmount, pub FundingNonce, pub PlanManagerID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_settle_funds(pub FundingNonce, pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_get_report(pub TreasuryUIID);
+
+impl Into<TemporalID> for TreasuryID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    TreasuryUIID::register_trait(system);
+    TemporalID::register_implementor::<Treasury>(system);
+    system.add_spawner::<Treasury, _, _>(
+        |&MSG_Treasury_spawn(id, ), world| {
+            Treasury::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_collect_taxes(amount, key), instance, world| {
+            instance.collect_taxes(amount, key, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_lane_built(length), instance, world| {
+            instance.lane_built(length, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_lane_unbuilt(length), instance, world| {
+            instance.lane_unbuilt(length, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_request_funds(project_id, cost, nonce, requester), instance, world| {
+            instance.request_funds(project_id, cost, nonce, requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_settle_funds(nonce, spent), instance, world| {
+            instance.settle_funds(nonce, spent, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_get_report(requester), instance, world| {
+            instance.get_report(requester, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,215 @@
+use kay::{ActorSystem, World};
+use compact::CHashMap;
+use descartes::N;
+use time::{Temporal, TemporalID, Instant, Duration, ticks_per_sim_second};
+use parameters::SimulationParameters;
+use planning::{Action, ActionGroups, Prototype, PrototypeKind, ProjectID, PlanManagerID};
+use transport::transport_planning::{RoadPrototype, LanePrototype, SwitchLanePrototype,
+IntersectionPrototype};
+use economy::resources::ResourceAmount;
+use access::InternalKey;
+
+use log::{info, warn};
+const LOG_T: &str = "Treasury";
+
+const INITIAL_BALANCE: ResourceAmount = 5_000_000.0;
+/// Share of all money income of households that goes to the city
+pub const TAX_RATE: f32 = 0.1;
+const COST_PER_LANE_METER: ResourceAmount = 300.0;
+const COST_PER_SWITCH_LANE_METER: ResourceAmount = 50.0;
+const COST_PER_LOT_SQUARE_METER: ResourceAmount = 1.0;
+const COST_PER_PLANT: ResourceAmount = 20.0;
+/// Morphing reuses most of what is already there
+const MORPH_COST_FACTOR: f32 = 0.2;
+const UPKEEP_PER_LANE_METER_PER_DAY: ResourceAmount = 0.5;
+
+pub fn prototype_cost(prototype: &Prototype) -> ResourceAmount {
+    match prototype.kind {
+        PrototypeKind::Road(RoadPrototype::Lane(LanePrototype(ref path, _))) => {
+            path.length() * COST_PER_LANE_METER
+        }
+        PrototypeKind::Road(RoadPrototype::SwitchLane(SwitchLanePrototype(ref path))) => {
+            path.length() * COST_PER_SWITCH_LANE_METER
+        }
+        PrototypeKind::Road(RoadPrototype::Intersection(IntersectionPrototype {
+            ref connecting_lanes,
+            ..
+        })) => {
+            connecting_lanes
+                .values()
+                .flat_map(|group| group.iter())
+                .map(|&LanePrototype(ref path, _)| path.length())
+                .sum::<N>()
+                * COST_PER_LANE_METER
+        }
+        PrototypeKind::Road(RoadPrototype::PavedArea(_)) => 0.0,
+        PrototypeKind::Lot(ref lot_prototype) => {
+            lot_prototype.lot.surface_area() * COST_PER_LOT_SQUARE_METER
+        }
+        PrototypeKind::Plant(_) => COST_PER_PLANT,
+        PrototypeKind::Water(_) => 0.0,
+    }
+}
+
+pub fn construction_cost(actions: &ActionGroups, new_prototypes: &[Prototype]) -> ResourceAmount {
+    let cost_of = |prototype_id| {
+        new_prototypes
+            .iter()
+            .find(|prototype| prototype.id == prototype_id)
+            .map(prototype_cost)
+            .unwrap_or(0.0)
+    };
+
+    actions
+        .0
+        .iter()
+        .flat_map(|action_group| action_group.0.iter())
+        .map(|action| match *action {
+            Action::Construct(prototype_id) => cost_of(prototype_id),
+            Action::Morph(_, new_prototype_id) => cost_of(new_prototype_id) * MORPH_COST_FACTOR,
+            Action::Destruct(_) => 0.0,
+        })
+        .sum()
+}
+
+#[derive(Copy, Clone, Default, Serialize, Deserialize)]
+pub struct TreasuryReport {
+    pub balance: ResourceAmount,
+    pub maintained_lane_length: N,
+    pub total_tax_income: ResourceAmount,
+    pub total_construction_expenses: ResourceAmount,
+    pub total_upkeep_expenses: ResourceAmount,
+    pub n_rejected_projects: u32,
+}
+
+/// Drawn by the plan manager for each funding request and echoed back by the treasury,
+/// so grants can't be forged by clients and funds are only ever released once
+pub type FundingNonce = u64;
+
+pub trait TreasuryUI {
+    fn on_treasury_report(&mut self, report: &TreasuryReport, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+pub struct Treasury {
+    id: TreasuryID,
+    report: TreasuryReport,
+    reserved_funds: CHashMap<FundingNonce, ResourceAmount>,
+}
+
+impl Treasury {
+    pub fn spawn(id: TreasuryID, _: &mut World) -> Treasury {
+        Treasury {
+            id,
+            report: TreasuryReport {
+                balance: INITIAL_BALANCE,
+                ..TreasuryReport::default()
+            },
+            reserved_funds: CHashMap::new(),
+        }
+    }
+
+    /// Negative amounts refund taxes paid before
+    pub fn collect_taxes(&mut self, amount: ResourceAmount, key: InternalKey, world: &mut World) {
+        if !key.is_valid() {
+            warn(
+                LOG_T,
+                "Ignoring taxes not paid by households",
+                self.id,
+                world,
+            );
+            return;
+        }
+
+        self.report.balance += amount;
+        self.report.total_tax_income += amount;
+    }
+
+    pub fn lane_built(&mut self, length: N, _: &mut World) {
+        self.report.maintained_lane_length += length;
+    }
+
+    pub fn lane_unbuilt(&mut self, length: N, _: &mut World) {
+        self.report.maintained_lane_length -= length;
+    }
+
+    /// Granted funds stay reserved until the requester settles them
+    pub fn request_funds(
+        &mut self,
+        project_id: ProjectID,
+        cost: ResourceAmount,
+        nonce: FundingNonce,
+        requester: PlanManagerID,
+        world: &mut World,
+    ) {
+        if cost <= self.report.balance {
+            self.report.balance -= cost;
+            self.report.total_construction_expenses += cost;
+            self.reserved_funds.insert(nonce, cost);
+            info(
+                LOG_T,
+                format!("Spent ${:.0} on construction", cost),
+                self.id,
+                world,
+            );
+            requester.funds_granted(project_id, cost, nonce, world);
+        } else {
+            self.report.n_rejected_projects += 1;
+            warn(
+                LOG_T,
+                format!(
+                    "Can't afford project costing ${:.0} with a balance of ${:.0}",
+                    cost, self.report.balance
+                ),
+                self.id,
+                world,
+            );
+            requester.funds_denied(project_id, nonce, world);
+        }
+    }
+
+    /// Gives back what was reserved but not spent, at most all of it
+    pub fn settle_funds(&mut self, nonce: FundingNonce, spent: ResourceAmount, _: &mut World) {
+        if let Some(reserved) = self.reserved_funds.remove(nonce) {
+            let unspent = (reserved - spent).max(0.0);
+            self.report.balance += unspent;
+            self.report.total_construction_expenses -= unspent;
+        }
+    }
+
+    pub fn get_report(&mut self, requester: TreasuryUIID, world: &mut World) {
+        requester.on_treasury_report(self.report, world);
+    }
+}
+
+const UPDATE_EVERY_N_SECS: u32 = 4;
+
+impl Temporal for Treasury {
+    fn tick(
+        &mut self,
+        _dt: f32,
+        current_instant: Instant,
+        _: &SimulationParameters,
+        _: &mut World,
+    ) {
+        if current_instant.ticks() % (UPDATE_EVERY_N_SECS * ticks_per_sim_second()) as usize == 0 {
+            let dt = Duration(UPDATE_EVERY_N_SECS * ticks_per_sim_second());
+            let upkeep =
+                self.report.maintained_lane_length * UPKEEP_PER_LANE_METER_PER_DAY * dt.as_days();
+            self.report.balance -= upkeep;
+            self.report.total_upkeep_expenses += upkeep;
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Treasury>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) -> TreasuryID {
+    TreasuryID::spawn(world)
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
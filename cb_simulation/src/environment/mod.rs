@@ -1,6 +1,9 @@
 use kay::ActorSystem;
+pub mod terrain;
 pub mod vegetation;
+pub mod water;
 
 pub fn setup(system: &mut ActorSystem) {
     vegetation::setup(system);
+    water::setup(system);
 }
@@ -0,0 +1,69 @@
+use descartes::{N, P2, V2};
+use noise::{NoiseFn, BasicMulti, Seedable, MultiFractal};
+use itertools::{Itertools, MinMaxResult};
+use ordered_float::OrderedFloat;
+
+// The terrain is generated procedurally from a fixed seed, so that the server
+// and all browsers agree on it without ever exchanging a heightmap
+
+const TERRAIN_SEED: u32 = 1_742;
+/// Height difference between the lowest valleys and the highest hills, in meters
+pub const MAX_HEIGHT: N = 80.0;
+/// Rough horizontal size of the largest hills, in meters
+const HILL_SIZE: f64 = 3000.0;
+/// Distance of the height samples used to estimate slopes, in meters
+const SLOPE_SAMPLE_DISTANCE: N = 5.0;
+
+thread_local! {
+    static TERRAIN_NOISE: BasicMulti = BasicMulti::new()
+        .set_seed(TERRAIN_SEED)
+        .set_octaves(5)
+        .set_persistence(0.45);
+}
+
+/// Height of the terrain above its lowest possible point, in meters
+pub fn height_at(position: P2) -> N {
+    let noise = TERRAIN_NOISE.with(|noise| {
+        noise.get([
+            f64::from(position.x) / HILL_SIZE,
+            f64::from(position.y) / HILL_SIZE,
+        ])
+    });
+
+    ((noise.max(-1.0).min(1.0) + 1.0) / 2.0) as N * MAX_HEIGHT
+}
+
+/// Rise of the terrain per meter in x and in y direction
+pub fn gradient_at(position: P2) -> V2 {
+    let dx = V2::new(SLOPE_SAMPLE_DISTANCE, 0.0);
+    let dy = V2::new(0.0, SLOPE_SAMPLE_DISTANCE);
+
+    V2::new(
+        height_at(position + dx) - height_at(position - dx),
+        height_at(position + dy) - height_at(position - dy),
+    ) / (2.0 * SLOPE_SAMPLE_DISTANCE)
+}
+
+/// Rise per meter in the steepest direction
+pub fn slope_at(position: P2) -> N {
+    gradient_at(position).norm()
+}
+
+/// Average slope between the lowest and the highest of the given points
+pub fn slope_across(points: &[P2]) -> N {
+    match points
+        .iter()
+        .map(|point| (*point, height_at(*point)))
+        .minmax_by_key(|(_, height)| OrderedFloat(*height))
+    {
+        MinMaxResult::MinMax((lowest_point, lowest_height), (highest_point, highest_height)) => {
+            let distance = (highest_point - lowest_point).norm();
+            if distance > 0.0 {
+                (highest_height - lowest_height) / distance
+            } else {
+                0.0
+            }
+        }
+        _ => 0.0,
+    }
+}
@@ -53,11 +53,18 @@ impl Into<ConstructableID> for PlantID {
     }
 }
 
+impl Into<InspectableID> for PlantID {
+    fn into(self) -> InspectableID {
+        InspectableID::from_raw(self.as_raw())
+    }
+}
+
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
     ConstructableID::register_implementor::<Plant>(system);
+    InspectableID::register_implementor::<Plant>(system);
     system.add_spawner::<Plant, _, _>(
         |&MSG_Plant_spawn(id, proto), world| {
             Plant::spawn(id, proto, world)
@@ -1,15 +1,18 @@
 use kay::{World, Fate, ActorSystem, TypedID};
-use compact::CVec;
-use descartes::{P2, RoughEq, AreaError};
+use compact::{CVec, CHashMap};
+use descartes::{N, P2, V2, RoughEq, AreaError};
 use construction::{Constructable, ConstructableID, ConstructionID};
 use planning::{Prototype, PrototypeID, PrototypeKind, PlanHistory, PlanResult, PlanManagerID,
 Project, Plan, Gesture, GestureID, GestureIntent};
 use transport::transport_planning::RoadPrototype;
 use land_use::zone_planning::{LotPrototype, LotOccupancy};
-use land_use::buildings::BuildingStyle;
 use land_use::buildings::architecture::footprint_area;
 use util::random::{seed, Rng};
 use noise::{NoiseFn, BasicMulti, Seedable, MultiFractal};
+use std::collections::BTreeSet;
+use environment::terrain::{height_at, MAX_HEIGHT};
+use environment::water::WaterPrototype;
+use inspection::{Inspectable, InspectableID, InspectorID, StateSummary};
 
 pub mod ui;
 use self::ui::VegetationUIID;
@@ -92,23 +95,156 @@ impl Constructable for Plant {
         VegetationUIID::global_broadcast(world).on_plant_destroyed(self.id, world);
         Fate::Die
     }
+
+    fn close_for_construction(&mut self, _world: &mut World) {}
+}
+
+impl Inspectable for Plant {
+    fn inspect(&mut self, requester: InspectorID, world: &mut World) {
+        let summary = StateSummary::new("Plant")
+            .field("vegetation type", self.proto.vegetation_type)
+            .field("position", self.proto.position);
+
+        requester.on_inspected(self.id.as_raw(), summary, world);
+    }
+}
+
+/// Natural vegetation grows densest in valleys and thins out towards hilltops
+fn natural_growth_threshold(position: P2) -> f64 {
+    0.02 + 0.25 * f64::from(height_at(position) / MAX_HEIGHT)
+}
+
+/// Natural growth is generated in square chunks of this many cells per side
+const NATURAL_GROWTH_CHUNK_CELLS: i32 = 50;
+/// Each cell can have at most one plant
+const NATURAL_GROWTH_CELL_SIZE: N = 10.0;
+/// How many chunks around development (and the origin) natural growth is generated for
+const NATURAL_GROWTH_CHUNKS_AROUND_DEVELOPMENT: i32 = 1;
+
+fn natural_growth_chunk_at(position: P2) -> (i32, i32) {
+    let chunk_size = NATURAL_GROWTH_CELL_SIZE * NATURAL_GROWTH_CHUNK_CELLS as N;
+    (
+        (position.x / chunk_size).floor() as i32,
+        (position.y / chunk_size).floor() as i32,
+    )
+}
+
+/// All chunks around developed chunks and around the origin, where the city starts
+fn natural_growth_chunks(developed_chunks: &BTreeSet<(i32, i32)>) -> BTreeSet<(i32, i32)> {
+    let around = NATURAL_GROWTH_CHUNKS_AROUND_DEVELOPMENT;
+    let offsets = (-around..=around)
+        .flat_map(|dx| (-around..=around).map(move |dy| (dx, dy)))
+        .collect::<Vec<_>>();
+
+    developed_chunks
+        .iter()
+        .chain(Some(&(0, 0)))
+        .flat_map(|&(x_chunk, y_chunk)| {
+            offsets
+                .iter()
+                .map(move |&(dx, dy)| (x_chunk + dx, y_chunk + dy))
+        })
+        .collect()
+}
+
+/// Natural growth of each chunk, before anything that was built is cut out of it.
+/// Every result keeps the growth of the chunks it needed, so later calculations based on it
+/// don't have to generate those chunks again. Growth of previews and chunks that are gone
+/// is dropped along with their results.
+#[derive(Compact, Clone, Debug)]
+pub struct NaturalGrowth {
+    by_chunk: CHashMap<(GestureID, (i32, i32)), CVec<Prototype>>,
+}
+
+impl NaturalGrowth {
+    pub fn new() -> NaturalGrowth {
+        NaturalGrowth {
+            by_chunk: CHashMap::new(),
+        }
+    }
+}
+
+fn natural_growth_noise(gesture_id: GestureID) -> BasicMulti {
+    BasicMulti::new()
+        .set_seed(gesture_id.0.as_fields().0)
+        .set_octaves(9)
+        .set_persistence(0.98)
 }
 
-static mut OCC_VEG_CELLS: *mut Vec<(i32, i32)> = 0 as *mut Vec<(i32, i32)>;
+/// Only depends on the gesture and the chunk, so chunks can be generated independently
+/// and in any order. The noise is sampled in world coordinates, so it is continuous
+/// across chunk borders.
+fn natural_growth_in_chunk(
+    noise: &BasicMulti,
+    gesture_id: GestureID,
+    (x_chunk, y_chunk): (i32, i32),
+) -> Vec<Prototype> {
+    let mut rand = seed((gesture_id, x_chunk, y_chunk));
+    let x_cells = x_chunk * NATURAL_GROWTH_CHUNK_CELLS..(x_chunk + 1) * NATURAL_GROWTH_CHUNK_CELLS;
+    let y_cells = y_chunk * NATURAL_GROWTH_CHUNK_CELLS..(y_chunk + 1) * NATURAL_GROWTH_CHUNK_CELLS;
+    let mut plants = Vec::new();
 
+    for x_cell in x_cells {
+        for y_cell in y_cells.clone() {
+            let cell_position = P2::new(
+                x_cell as N * NATURAL_GROWTH_CELL_SIZE,
+                y_cell as N * NATURAL_GROWTH_CELL_SIZE,
+            );
+
+            if noise.get([f64::from(x_cell) / 50.0, f64::from(y_cell) / 50.0])
+                > natural_growth_threshold(cell_position)
+            {
+                let position = cell_position
+                    + V2::new(
+                        rand.gen_range(-NATURAL_GROWTH_CELL_SIZE, NATURAL_GROWTH_CELL_SIZE),
+                        rand.gen_range(-NATURAL_GROWTH_CELL_SIZE, NATURAL_GROWTH_CELL_SIZE),
+                    );
+                let vegetation_type = *rand.choose(&VEGETATION_TYPES).unwrap();
+
+                plants.push(Prototype::new_with_influences(
+                    (gesture_id, x_cell, y_cell),
+                    PrototypeKind::Plant(PlantPrototype {
+                        vegetation_type,
+                        position,
+                    }),
+                    position,
+                ));
+            }
+        }
+    }
+
+    plants
+}
+
+/// Reuses the natural growth of chunks that were already generated for `previous_growth`
 pub fn calculate_prototypes(
     history: &PlanHistory,
     current_result: &PlanResult,
-) -> Result<Vec<Prototype>, AreaError> {
+    previous_growth: &NaturalGrowth,
+) -> Result<(Vec<Prototype>, NaturalGrowth), AreaError> {
+    let mut natural_growth = NaturalGrowth::new();
     let mut constructed_areas = Vec::new();
+    let mut developed_chunks = BTreeSet::new();
     let mut prototypes = Vec::with_capacity(100_000);
 
     for prototype in current_result.prototypes.values() {
+        match prototype.kind {
+            PrototypeKind::Plant(_) | PrototypeKind::Water(_) => {}
+            _ => {
+                developed_chunks.insert(natural_growth_chunk_at(prototype.representative_position));
+            }
+        }
+
         match *prototype {
             Prototype {
                 kind: PrototypeKind::Road(RoadPrototype::PavedArea(ref area)),
                 ..
             } => constructed_areas.push(area.clone()),
+            // nothing grows in water either
+            Prototype {
+                kind: PrototypeKind::Water(WaterPrototype { ref area }),
+                ..
+            } => constructed_areas.push(area.clone()),
             Prototype {
                 kind:
                     PrototypeKind::Lot(LotPrototype {
@@ -119,7 +255,7 @@ pub fn calculate_prototypes(
                 ..
             } => {
                 constructed_areas.push(footprint_area(lot, style, 5.0));
-                if style == BuildingStyle::Field {
+                if style.is_field() {
                     let boundary = lot.original_area.primitives[0].boundary.path();
                     let mut pos_along = 0.0;
                     let mut i = 0;
@@ -158,57 +294,27 @@ pub fn calculate_prototypes(
                     proto.position,
                 )),
                 PlantIntent::NaturalGrowth => {
-                    let mut positions = Vec::new();
                     let mut prototypes_before_difference = Vec::new();
+                    let mut multi_noise = None;
 
-                    if unsafe { OCC_VEG_CELLS.is_null() } {
-                        let mut multi_noise = BasicMulti::new()
-                            .set_seed(gesture_id.0.as_fields().0)
-                            .set_octaves(9)
-                            .set_persistence(0.98);
-
-                        let cells = (-50..50)
-                            .flat_map(|x_cell| {
-                                (-50..50)
-                                    .filter_map(|y_cell| {
-                                        if multi_noise.get([
-                                            f64::from(x_cell) / 50.0,
-                                            f64::from(y_cell) / 50.0,
-                                        ]) > 0.12
-                                        {
-                                            Some((x_cell, y_cell))
-                                        } else {
-                                            None
-                                        }
-                                    })
-                                    .collect::<Vec<_>>()
-                            })
-                            .collect::<Vec<_>>();
-
-                        unsafe { OCC_VEG_CELLS = Box::into_raw(Box::new(cells)) }
+                    for chunk in natural_growth_chunks(&developed_chunks) {
+                        let key = (*gesture_id, chunk);
+                        let chunk_prototypes = match previous_growth.by_chunk.get(key) {
+                            Some(chunk_prototypes) => chunk_prototypes.clone(),
+                            None => {
+                                let noise = multi_noise
+                                    .get_or_insert_with(|| natural_growth_noise(*gesture_id));
+                                natural_growth_in_chunk(noise, *gesture_id, chunk).into()
+                            }
+                        };
+                        prototypes_before_difference.extend(chunk_prototypes.iter().cloned());
+                        natural_growth.by_chunk.insert(key, chunk_prototypes);
                     }
 
-                    let occ_vec_cells = unsafe { &(*OCC_VEG_CELLS) };
-
-                    for (x_cell, y_cell) in occ_vec_cells {
-                        let mut position = [*x_cell as f32 * 10.0, *y_cell as f32 * 10.0];
-                        let mut rand = seed((x_cell, y_cell));
-                        position[0] += rand.gen_range(-10.0, 10.0);
-                        position[1] += rand.gen_range(-10.0, 10.0);
-
-                        let position_p2 = P2::new(position[0], position[1]);
-                        let vegetation_type = rand.choose(&VEGETATION_TYPES).unwrap();
-
-                        positions.push(position_p2);
-                        prototypes_before_difference.push(Prototype::new_with_influences(
-                            (gesture_id, x_cell, y_cell),
-                            PrototypeKind::Plant(PlantPrototype {
-                                position: position_p2,
-                                vegetation_type: *vegetation_type,
-                            }),
-                            position_p2,
-                        ));
-                    }
+                    let positions = prototypes_before_difference
+                        .iter()
+                        .map(|proto| proto.representative_position)
+                        .collect::<Vec<_>>();
 
                     let mut winding_numbers = vec![0.0; positions.len()];
 
@@ -237,7 +343,7 @@ pub fn calculate_prototypes(
         }
     }
 
-    Ok(prototypes)
+    Ok((prototypes, natural_growth))
 }
 
 pub fn setup(system: &mut ActorSystem) {
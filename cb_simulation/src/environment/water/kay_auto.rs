@@ -0,0 +1,66 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for WaterBody {
+    type ID = WaterBodyID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct WaterBodyID {
+    _raw_id: RawID
+}
+
+impl TypedID for WaterBodyID {
+    type Target = WaterBody;
+
+    fn from_raw(id: RawID) -> Self {
+        WaterBodyID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl WaterBodyID {
+    pub fn spawn(area: Area, world: &mut World) -> Self {
+        let id = WaterBodyID::from_raw(world.allocate_instance_id::<WaterBody>());
+        let swarm = world.local_broadcast::<WaterBody>();
+        world.send(swarm, MSG_WaterBody_spawn(id, area));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_WaterBody_spawn(pub WaterBodyID, pub Area);
+
+impl Into<ConstructableID> for WaterBodyID {
+    fn into(self) -> ConstructableID {
+        ConstructableID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    ConstructableID::register_implementor::<WaterBody>(system);
+    system.add_spawner::<WaterBody, _, _>(
+        |&MSG_WaterBody_spawn(id, ref area), world| {
+            WaterBody::spawn(id, area, world)
+        }, false
+    );
+}
\ No newline at end of file
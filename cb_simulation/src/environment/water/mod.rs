@@ -0,0 +1,227 @@
+use kay::{World, Fate, ActorSystem};
+use compact::CVec;
+use descartes::{N, P2, V2, Area, Band, LinePath, ClosedLinePath, PointContainer, AreaError};
+use construction::{Constructable, ConstructableID, ConstructionID};
+use planning::{Prototype, PrototypeID, PrototypeKind, PlanHistory, PlanResult, PlanManagerID,
+Project, Plan, Gesture, GestureID, GestureIntent, VersionedGesture};
+use transport::transport_planning::smooth_path::smooth_path_from;
+use environment::terrain::{height_at, MAX_HEIGHT};
+use util::random::{seed, Rng};
+
+pub mod ui;
+use self::ui::WaterUIID;
+
+/// Natural lakes fill the terrain up to this height, in meters
+const NATURAL_WATER_LEVEL: N = 0.12 * MAX_HEIGHT;
+/// At most one natural lake is placed per grid cell of this size, in meters
+const NATURAL_LAKE_CELL_SIZE: N = 250.0;
+/// How many grid cells in each direction from the origin get natural lakes
+const NATURAL_LAKE_SEARCH_CELLS: i32 = 10;
+const MIN_NATURAL_LAKE_RADIUS: N = 30.0;
+/// Small enough that lakes of neighboring cells never overlap
+const MAX_NATURAL_LAKE_RADIUS: N = 120.0;
+const NATURAL_LAKE_CORNERS: usize = 16;
+/// Roads are checked for crossing water at this interval, in meters
+const WATER_CROSSING_SAMPLE_DISTANCE: N = 2.0;
+
+#[derive(Copy, Clone, Serialize, Deserialize, Debug)]
+pub enum WaterIntent {
+    /// The gesture points outline the lake
+    Lake,
+    /// The gesture points describe the course of the river
+    River { width: N },
+    /// A lake in one of the lowest valleys of the terrain, outlined by the gesture points
+    NaturalWater,
+}
+
+#[derive(Compact, Clone, Serialize, Deserialize, Debug)]
+pub struct WaterPrototype {
+    pub area: Area,
+}
+
+impl WaterPrototype {
+    pub fn construct(
+        &self,
+        _self_id: PrototypeID,
+        report_to: ConstructionID,
+        world: &mut World,
+    ) -> CVec<ConstructableID> {
+        let id = WaterBodyID::spawn(self.area.clone(), world).into();
+        report_to.action_done(id, world);
+        vec![id].into()
+    }
+
+    pub fn morphable_from(&self, _other: &Self) -> bool {
+        // water bodies are simply replaced whenever their shape changes
+        false
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct WaterBody {
+    id: WaterBodyID,
+    area: Area,
+}
+
+impl WaterBody {
+    pub fn spawn(id: WaterBodyID, area: &Area, world: &mut World) -> WaterBody {
+        WaterUIID::global_broadcast(world).on_water_body_spawned(id, area.clone(), world);
+        WaterBody {
+            id,
+            area: area.clone(),
+        }
+    }
+}
+
+impl Constructable for WaterBody {
+    fn morph(&mut self, _new_prototype: &Prototype, report_to: ConstructionID, world: &mut World) {
+        // water bodies are never morphable, so there is nothing to do
+        report_to.action_done(self.id.into(), world);
+    }
+
+    fn destruct(&mut self, report_to: ConstructionID, world: &mut World) -> Fate {
+        report_to.action_done(self.id.into(), world);
+        WaterUIID::global_broadcast(world).on_water_body_destroyed(self.id, world);
+        Fate::Die
+    }
+
+    fn close_for_construction(&mut self, _world: &mut World) {}
+}
+
+fn lake_area(outline_points: &[P2]) -> Option<Area> {
+    LinePath::new(
+        outline_points
+            .iter()
+            .chain(outline_points.first())
+            .cloned()
+            .collect(),
+    )
+    .and_then(ClosedLinePath::new)
+    .map(|closed_line_path| Area::new_simple(closed_line_path.to_clockwise()))
+}
+
+fn cell_center(x_cell: i32, y_cell: i32) -> P2 {
+    P2::new(
+        x_cell as N * NATURAL_LAKE_CELL_SIZE,
+        y_cell as N * NATURAL_LAKE_CELL_SIZE,
+    )
+}
+
+/// Only depends on the terrain and the cell
+fn natural_lake_outline(x_cell: i32, y_cell: i32) -> Option<Vec<P2>> {
+    let center = cell_center(x_cell, y_cell);
+    let depth = NATURAL_WATER_LEVEL - height_at(center);
+
+    let is_local_minimum = [(-1, 0), (1, 0), (0, -1), (0, 1)]
+        .iter()
+        .all(|(dx, dy)| height_at(cell_center(x_cell + dx, y_cell + dy)) >= height_at(center));
+
+    if depth <= 0.0 || !is_local_minimum {
+        return None;
+    }
+
+    let radius = (MIN_NATURAL_LAKE_RADIUS
+        + depth / NATURAL_WATER_LEVEL * (MAX_NATURAL_LAKE_RADIUS - MIN_NATURAL_LAKE_RADIUS))
+        .min(MAX_NATURAL_LAKE_RADIUS);
+    let mut rand = seed((x_cell, y_cell));
+
+    Some(
+        (0..NATURAL_LAKE_CORNERS)
+            .map(|i| {
+                let angle = i as N / NATURAL_LAKE_CORNERS as N * 2.0 * ::std::f32::consts::PI;
+                center + V2::new(angle.cos(), angle.sin()) * radius * rand.gen_range(0.7, 1.0)
+            })
+            .collect(),
+    )
+}
+
+pub fn calculate_prototypes(
+    history: &PlanHistory,
+    _current_result: &PlanResult,
+) -> Result<Vec<Prototype>, AreaError> {
+    let mut prototypes = Vec::new();
+
+    for (gesture_id, VersionedGesture(gesture, step_id)) in history.gestures.pairs() {
+        if let GestureIntent::Water(water_intent) = gesture.intent {
+            match water_intent {
+                WaterIntent::Lake | WaterIntent::NaturalWater if gesture.points.len() >= 3 => {
+                    if let Some(area) = lake_area(&gesture.points) {
+                        prototypes.push(Prototype::new_with_influences(
+                            (gesture_id, step_id),
+                            PrototypeKind::Water(WaterPrototype { area }),
+                            gesture.points[0],
+                        ));
+                    }
+                }
+                WaterIntent::River { width } if gesture.points.len() >= 2 => {
+                    if let Some(path) = smooth_path_from(&gesture.points) {
+                        prototypes.push(Prototype::new_with_influences(
+                            (gesture_id, step_id),
+                            PrototypeKind::Water(WaterPrototype {
+                                area: Band::new(path.to_line_path_with_max_angle(0.12), width)
+                                    .as_area(),
+                            }),
+                            gesture.points[0],
+                        ));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(prototypes)
+}
+
+/// All areas covered by water in a plan result
+pub fn water_areas(result: &PlanResult) -> Vec<&Area> {
+    result
+        .prototypes
+        .values()
+        .filter_map(|prototype| match prototype.kind {
+            PrototypeKind::Water(WaterPrototype { ref area }) => Some(area),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn path_crosses_water(path: &LinePath, water_areas: &[&Area]) -> bool {
+    let n_samples = (path.length() / WATER_CROSSING_SAMPLE_DISTANCE).ceil() as usize;
+
+    (0..=n_samples).any(|i| {
+        let point = path.along(i as N * path.length() / n_samples.max(1) as N);
+        water_areas.iter().any(|area| area.contains(point))
+    })
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<WaterBody>();
+    kay_auto::auto_setup(system);
+    self::ui::auto_setup(system);
+}
+
+/// Every natural lake is a gesture of its own, so changing the plan around one of them
+/// only needs to recalculate what is near it
+pub fn spawn(world: &mut World, plan_manager: PlanManagerID) {
+    let search_range = -NATURAL_LAKE_SEARCH_CELLS..=NATURAL_LAKE_SEARCH_CELLS;
+    let gestures = search_range
+        .clone()
+        .flat_map(|x_cell| search_range.clone().map(move |y_cell| (x_cell, y_cell)))
+        .filter_map(|(x_cell, y_cell)| natural_lake_outline(x_cell, y_cell))
+        .map(|outline_points| {
+            (
+                GestureID::new(),
+                Gesture::new(
+                    outline_points.into(),
+                    GestureIntent::Water(WaterIntent::NaturalWater),
+                ),
+            )
+        })
+        .collect::<Vec<_>>();
+    let project = Project::from_plan(Plan::from_gestures(gestures));
+
+    plan_manager.implement_artificial_project(project, CVec::new(), world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,91 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct WaterUIID {
+    _raw_id: RawID
+}
+
+pub struct WaterUIRepresentative;
+
+impl ActorOrActorTrait for WaterUIRepresentative {
+    type ID = WaterUIID;
+}
+
+impl TypedID for WaterUIID {
+    type Target = WaterUIRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        WaterUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<A: Actor + WaterUI> TraitIDFrom<A> for WaterUIID {}
+
+impl WaterUIID {
+    pub fn on_water_body_spawned(self, id: WaterBodyID, area: Area, world: &mut World) {
+        world.send(self.as_raw(), MSG_WaterUI_on_water_body_spawned(id, area));
+    }
+    
+    pub fn on_water_body_destroyed(self, id: WaterBodyID, world: &mut World) {
+        world.send(self.as_raw(), MSG_WaterUI_on_water_body_destroyed(id));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<WaterUIRepresentative>();
+        system.register_trait_message::<MSG_WaterUI_on_water_body_spawned>();
+        system.register_trait_message::<MSG_WaterUI_on_water_body_destroyed>();
+    }
+
+    pub fn register_implementor<A: Actor + WaterUI>(system: &mut ActorSystem) {
+        system.register_implementor::<A, WaterUIRepresentative>();
+        system.add_handler::<A, _, _>(
+            |&MSG_WaterUI_on_water_body_spawned(id, ref area), instance, world| {
+                instance.on_water_body_spawned(id, area, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<A, _, _>(
+            |&MSG_WaterUI_on_water_body_destroyed(id), instance, world| {
+                instance.on_water_body_destroyed(id, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_WaterUI_on_water_body_spawned(pub WaterBodyID, pub Area);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_WaterUI_on_water_body_destroyed(pub WaterBodyID);
+
+
+
+impl WaterBodyID {
+    pub fn get_render_info(self, requester: WaterUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_WaterBody_get_render_info(requester));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_WaterBody_get_render_info(pub WaterUIID);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    WaterUIID::register_trait(system);
+    
+    system.add_handler::<WaterBody, _, _>(
+        |&MSG_WaterBody_get_render_info(requester), instance, world| {
+            instance.get_render_info(requester, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
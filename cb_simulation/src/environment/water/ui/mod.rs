@@ -0,0 +1,17 @@
+use kay::World;
+use descartes::Area;
+use super::{WaterBody, WaterBodyID};
+
+impl WaterBody {
+    pub fn get_render_info(&mut self, requester: WaterUIID, world: &mut World) {
+        requester.on_water_body_spawned(self.id, self.area.clone(), world);
+    }
+}
+
+pub trait WaterUI {
+    fn on_water_body_spawned(&mut self, id: WaterBodyID, area: &Area, world: &mut World);
+    fn on_water_body_destroyed(&mut self, id: WaterBodyID, world: &mut World);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,191 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct HeatmapUIID {
+    _raw_id: RawID
+}
+
+pub struct HeatmapUIRepresentative;
+
+impl ActorOrActorTrait for HeatmapUIRepresentative {
+    type ID = HeatmapUIID;
+}
+
+impl TypedID for HeatmapUIID {
+    type Target = HeatmapUIRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        HeatmapUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<A: Actor + HeatmapUI> TraitIDFrom<A> for HeatmapUIID {}
+
+impl HeatmapUIID {
+    pub fn on_heatmap(self, layer: HeatmapLayer, cells: CVec < HeatmapCell >, world: &mut World) {
+        world.send(self.as_raw(), MSG_HeatmapUI_on_heatmap(layer, cells));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<HeatmapUIRepresentative>();
+        system.register_trait_message::<MSG_HeatmapUI_on_heatmap>();
+    }
+
+    pub fn register_implementor<A: Actor + HeatmapUI>(system: &mut ActorSystem) {
+        system.register_implementor::<A, HeatmapUIRepresentative>();
+        system.add_handler::<A, _, _>(
+            |&MSG_HeatmapUI_on_heatmap(layer, ref cells), instance, world| {
+                instance.on_heatmap(layer, cells, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HeatmapUI_on_heatmap(pub HeatmapLayer, pub CVec < HeatmapCell >);
+
+impl Actor for Heatmaps {
+    type ID = HeatmapsID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct HeatmapsID {
+    _raw_id: RawID
+}
+
+impl TypedID for HeatmapsID {
+    type Target = Heatmaps;
+
+    fn from_raw(id: RawID) -> Self {
+        HeatmapsID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl HeatmapsID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = HeatmapsID::from_raw(world.allocate_instance_id::<Heatmaps>());
+        let swarm = world.local_broadcast::<Heatmaps>();
+        world.send(swarm, MSG_Heatmaps_spawn(id, ));
+        id
+    }
+    
+    pub fn add_traffic_samples(self, samples: CVec < TrafficSample >, world: &mut World) {
+        world.send(self.as_raw(), MSG_Heatmaps_add_traffic_samples(samples));
+    }
+    
+    pub fn add_household_sample(self, position: P2, graveness: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Heatmaps_add_household_sample(position, graveness));
+    }
+    
+    pub fn add_vacancy_sample(self, position: P2, n_places: u32, n_vacant_places: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Heatmaps_add_vacancy_sample(position, n_places, n_vacant_places));
+    }
+    
+    pub fn add_failed_trip(self, origin: RoughLocationID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Heatmaps_add_failed_trip(origin));
+    }
+    
+    pub fn subscribe(self, layer: HeatmapLayer, subscriber: HeatmapUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Heatmaps_subscribe(layer, subscriber));
+    }
+    
+    pub fn unsubscribe(self, subscriber: HeatmapUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Heatmaps_unsubscribe(subscriber));
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Heatmaps_spawn(pub HeatmapsID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Heatmaps_add_traffic_samples(pub CVec < TrafficSample >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Heatmaps_add_household_sample(pub P2, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Heatmaps_add_vacancy_sample(pub P2, pub u32, pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Heatmaps_add_failed_trip(pub RoughLocationID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Heatmaps_subscribe(pub HeatmapLayer, pub HeatmapUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Heatmaps_unsubscribe(pub HeatmapUIID);
+
+impl Into<PositionRequesterID> for HeatmapsID {
+    fn into(self) -> PositionRequesterID {
+        PositionRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TemporalID> for HeatmapsID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    HeatmapUIID::register_trait(system);
+    PositionRequesterID::register_implementor::<Heatmaps>(system);
+    TemporalID::register_implementor::<Heatmaps>(system);
+    system.add_spawner::<Heatmaps, _, _>(
+        |&MSG_Heatmaps_spawn(id, ), world| {
+            Heatmaps::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<Heatmaps, _, _>(
+        |&MSG_Heatmaps_add_traffic_samples(ref samples), instance, world| {
+            instance.add_traffic_samples(samples, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Heatmaps, _, _>(
+        |&MSG_Heatmaps_add_household_sample(position, graveness), instance, world| {
+            instance.add_household_sample(position, graveness, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Heatmaps, _, _>(
+        |&MSG_Heatmaps_add_vacancy_sample(position, n_places, n_vacant_places), instance, world| {
+            instance.add_vacancy_sample(position, n_places, n_vacant_places, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Heatmaps, _, _>(
+        |&MSG_Heatmaps_add_failed_trip(origin), instance, world| {
+            instance.add_failed_trip(origin, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Heatmaps, _, _>(
+        |&MSG_Heatmaps_subscribe(layer, subscriber), instance, world| {
+            instance.subscribe(layer, subscriber, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Heatmaps, _, _>(
+        |&MSG_Heatmaps_unsubscribe(subscriber), instance, world| {
+            instance.unsubscribe(subscriber, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
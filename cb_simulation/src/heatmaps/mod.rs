@@ -0,0 +1,244 @@
+//! Aggregates metrics from all over the city on a coarse grid,
+//! so the UI can show where traffic, unmet needs and vacancies pile up
+
+use kay::{ActorSystem, World, TypedID};
+use compact::{CVec, CHashMap};
+use descartes::{N, P2};
+use time::{Temporal, TemporalID, Instant, Duration, Ticks};
+use parameters::SimulationParameters;
+use transport::lane::LaneID;
+use transport::pathfinding::{RoughLocationID, PositionRequester, PositionRequesterID};
+use land_use::buildings::BuildingID;
+use land_use::vacant_lots::VacantLotID;
+
+pub const HEATMAP_CELL_SIZE: N = 100.0;
+const SAMPLE_EVERY_N_MINUTES: usize = 10;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum HeatmapLayer {
+    /// Cars per km of lane
+    CarDensity,
+    /// Meters per second, of the cars that are in the cell
+    AverageSpeed,
+    /// Trips that started in the cell and failed, per sample period
+    FailedTrips,
+    /// Average graveness of the top problems of households
+    Graveness,
+    /// Share of building units and vacant lots that nobody uses
+    Vacancy,
+}
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct HeatmapCell {
+    pub x: i32,
+    pub y: i32,
+    pub value: f32,
+}
+
+/// What a lane saw in one cell of the grid
+#[derive(Copy, Clone)]
+pub struct TrafficSample {
+    pub cell: (i32, i32),
+    pub lane_length: N,
+    pub n_cars: u32,
+    pub speed_sum: f32,
+}
+
+#[derive(Copy, Clone, Default)]
+struct CellAccumulator {
+    lane_length: N,
+    n_cars: u32,
+    speed_sum: f32,
+    failed_trips: u32,
+    n_households: u32,
+    graveness_sum: f32,
+    n_places: u32,
+    n_vacant_places: u32,
+}
+
+impl CellAccumulator {
+    fn value(&self, layer: HeatmapLayer) -> Option<f32> {
+        match layer {
+            HeatmapLayer::CarDensity if self.lane_length > 0.0 => {
+                Some(self.n_cars as f32 / (self.lane_length / 1000.0))
+            }
+            HeatmapLayer::AverageSpeed if self.n_cars > 0 => {
+                Some(self.speed_sum / self.n_cars as f32)
+            }
+            HeatmapLayer::FailedTrips if self.failed_trips > 0 => Some(self.failed_trips as f32),
+            HeatmapLayer::Graveness if self.n_households > 0 => {
+                Some(self.graveness_sum / self.n_households as f32)
+            }
+            HeatmapLayer::Vacancy if self.n_places > 0 => {
+                Some(self.n_vacant_places as f32 / self.n_places as f32)
+            }
+            _ => None,
+        }
+    }
+}
+
+pub fn cell_of(position: P2) -> (i32, i32) {
+    (
+        (position.x / HEATMAP_CELL_SIZE).floor() as i32,
+        (position.y / HEATMAP_CELL_SIZE).floor() as i32,
+    )
+}
+
+/// Finds or adds the sample for the given cell, for reporters that cover several cells
+pub fn traffic_sample_in(
+    samples: &mut CVec<TrafficSample>,
+    cell: (i32, i32),
+) -> &mut TrafficSample {
+    let idx = match samples.iter().position(|sample| sample.cell == cell) {
+        Some(idx) => idx,
+        None => {
+            samples.push(TrafficSample {
+                cell,
+                lane_length: 0.0,
+                n_cars: 0,
+                speed_sum: 0.0,
+            });
+            samples.len() - 1
+        }
+    };
+
+    &mut samples[idx]
+}
+
+pub trait HeatmapUI {
+    fn on_heatmap(&mut self, layer: HeatmapLayer, cells: &CVec<HeatmapCell>, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+pub struct Heatmaps {
+    id: HeatmapsID,
+    current: CHashMap<(i32, i32), CellAccumulator>,
+    finished: CHashMap<(i32, i32), CellAccumulator>,
+    subscribers: CVec<(HeatmapUIID, HeatmapLayer)>,
+}
+
+impl Heatmaps {
+    pub fn spawn(id: HeatmapsID, _: &mut World) -> Heatmaps {
+        Heatmaps {
+            id,
+            current: CHashMap::new(),
+            finished: CHashMap::new(),
+            subscribers: CVec::new(),
+        }
+    }
+
+    fn cell_mut(&mut self, cell: (i32, i32)) -> &mut CellAccumulator {
+        if self.current.get(cell).is_none() {
+            self.current.insert(cell, CellAccumulator::default());
+        }
+
+        self.current
+            .get_mut(cell)
+            .expect("just made sure the cell exists")
+    }
+
+    pub fn add_traffic_samples(&mut self, samples: &CVec<TrafficSample>, _: &mut World) {
+        for sample in samples.iter() {
+            let accumulator = self.cell_mut(sample.cell);
+            accumulator.lane_length += sample.lane_length;
+            accumulator.n_cars += sample.n_cars;
+            accumulator.speed_sum += sample.speed_sum;
+        }
+    }
+
+    pub fn add_household_sample(&mut self, position: P2, graveness: f32, _: &mut World) {
+        let accumulator = self.cell_mut(cell_of(position));
+        accumulator.n_households += 1;
+        accumulator.graveness_sum += graveness;
+    }
+
+    pub fn add_vacancy_sample(
+        &mut self,
+        position: P2,
+        n_places: u32,
+        n_vacant_places: u32,
+        _: &mut World,
+    ) {
+        let accumulator = self.cell_mut(cell_of(position));
+        accumulator.n_places += n_places;
+        accumulator.n_vacant_places += n_vacant_places;
+    }
+
+    /// Called by trips that failed, their origin is resolved to a position first
+    pub fn add_failed_trip(&mut self, origin: RoughLocationID, world: &mut World) {
+        origin.resolve_as_position(self.id.into(), origin, world);
+    }
+
+    fn cells(&self, layer: HeatmapLayer) -> CVec<HeatmapCell> {
+        self.finished
+            .pairs()
+            .filter_map(|(&(x, y), accumulator)| {
+                accumulator
+                    .value(layer)
+                    .map(|value| HeatmapCell { x, y, value })
+            })
+            .collect()
+    }
+
+    /// The subscriber gets the last finished heatmap of the layer right away
+    /// and a new one at the end of each sample period, until it unsubscribes.
+    /// Subscribing again switches the layer that it gets.
+    pub fn subscribe(&mut self, layer: HeatmapLayer, subscriber: HeatmapUIID, world: &mut World) {
+        self.subscribers
+            .retain(|&(existing, _)| existing != subscriber);
+        self.subscribers.push((subscriber, layer));
+
+        subscriber.on_heatmap(layer, self.cells(layer), world);
+    }
+
+    pub fn unsubscribe(&mut self, subscriber: HeatmapUIID, _: &mut World) {
+        self.subscribers
+            .retain(|&(existing, _)| existing != subscriber);
+    }
+}
+
+impl PositionRequester for Heatmaps {
+    fn position_resolved(&mut self, _: RoughLocationID, position: P2, _: &mut World) {
+        self.cell_mut(cell_of(position)).failed_trips += 1;
+    }
+}
+
+impl Temporal for Heatmaps {
+    fn tick(
+        &mut self,
+        _dt: f32,
+        current_instant: Instant,
+        _: &SimulationParameters,
+        world: &mut World,
+    ) {
+        let ticks_per_sample =
+            Ticks::from(Duration::from_minutes(SAMPLE_EVERY_N_MINUTES)).0 as usize;
+
+        if current_instant.ticks() % ticks_per_sample == 0 {
+            // like economic statistics, reports arrive during the following sample period
+            if current_instant.ticks() > 0 {
+                self.finished = ::std::mem::replace(&mut self.current, CHashMap::new());
+
+                for &(subscriber, layer) in self.subscribers.iter() {
+                    subscriber.on_heatmap(layer, self.cells(layer), world);
+                }
+            }
+
+            LaneID::global_broadcast(world).report_to_heatmaps(self.id, world);
+            BuildingID::global_broadcast(world).report_to_heatmaps(self.id, current_instant, world);
+            VacantLotID::global_broadcast(world).report_to_heatmaps(self.id, world);
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Heatmaps>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) -> HeatmapsID {
+    HeatmapsID::spawn(world)
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,112 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct InspectableID {
+    _raw_id: RawID
+}
+
+pub struct InspectableRepresentative;
+
+impl ActorOrActorTrait for InspectableRepresentative {
+    type ID = InspectableID;
+}
+
+impl TypedID for InspectableID {
+    type Target = InspectableRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        InspectableID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<A: Actor + Inspectable> TraitIDFrom<A> for InspectableID {}
+
+impl InspectableID {
+    pub fn inspect(self, requester: InspectorID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Inspectable_inspect(requester));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<InspectableRepresentative>();
+        system.register_trait_message::<MSG_Inspectable_inspect>();
+    }
+
+    pub fn register_implementor<A: Actor + Inspectable>(system: &mut ActorSystem) {
+        system.register_implementor::<A, InspectableRepresentative>();
+        system.add_handler::<A, _, _>(
+            |&MSG_Inspectable_inspect(requester), instance, world| {
+                instance.inspect(requester, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Inspectable_inspect(pub InspectorID);
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct InspectorID {
+    _raw_id: RawID
+}
+
+pub struct InspectorRepresentative;
+
+impl ActorOrActorTrait for InspectorRepresentative {
+    type ID = InspectorID;
+}
+
+impl TypedID for InspectorID {
+    type Target = InspectorRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        InspectorID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<A: Actor + Inspector> TraitIDFrom<A> for InspectorID {}
+
+impl InspectorID {
+    pub fn on_inspected(self, id: RawID, summary: StateSummary, world: &mut World) {
+        world.send(self.as_raw(), MSG_Inspector_on_inspected(id, summary));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<InspectorRepresentative>();
+        system.register_trait_message::<MSG_Inspector_on_inspected>();
+    }
+
+    pub fn register_implementor<A: Actor + Inspector>(system: &mut ActorSystem) {
+        system.register_implementor::<A, InspectorRepresentative>();
+        system.add_handler::<A, _, _>(
+            |&MSG_Inspector_on_inspected(id, ref summary), instance, world| {
+                instance.on_inspected(id, summary, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Inspector_on_inspected(pub RawID, pub StateSummary);
+
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    InspectableID::register_trait(system);
+    InspectorID::register_trait(system);
+    
+}
\ No newline at end of file
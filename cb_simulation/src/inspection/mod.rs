@@ -0,0 +1,51 @@
+//! Lets debugging tools look into the state of any actor that supports it,
+//! without the actor having to log everything it does all the time
+
+use kay::{World, ActorSystem, RawID};
+use compact::{CVec, CString};
+use std::fmt::Debug;
+
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub struct SummaryField {
+    pub name: CString,
+    pub value: CString,
+}
+
+/// Human-readable snapshot of the interesting parts of an actor's state
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub struct StateSummary {
+    pub actor_type: CString,
+    pub fields: CVec<SummaryField>,
+}
+
+impl StateSummary {
+    pub fn new(actor_type: &str) -> StateSummary {
+        StateSummary {
+            actor_type: actor_type.to_owned().into(),
+            fields: CVec::new(),
+        }
+    }
+
+    pub fn field<V: Debug>(mut self, name: &str, value: V) -> StateSummary {
+        self.fields.push(SummaryField {
+            name: name.to_owned().into(),
+            value: format!("{:?}", value).into(),
+        });
+        self
+    }
+}
+
+pub trait Inspectable {
+    fn inspect(&mut self, requester: InspectorID, world: &mut World);
+}
+
+pub trait Inspector {
+    fn on_inspected(&mut self, id: RawID, summary: &StateSummary, world: &mut World);
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -1,4 +1,4 @@
-use kay::{World, TypedID};
+use kay::World;
 use descartes::{N, P2, V2, WithUniqueOrthogonal, LinePath, ClosedLinePath, PrimitiveArea, Area};
 use util::random::{Rng, seed};
 use michelangelo::{Vertex, Mesh, Instance, FlatSurface, Sculpture};
@@ -12,12 +12,14 @@ pub fn ideal_lot_shape(building_style: BuildingStyle) -> (N, N, N) {
         BuildingStyle::GroceryShop => (15.0, 20.0, 0.5),
         BuildingStyle::Bakery => (20.0, 30.0, 0.5),
         BuildingStyle::Mill => (20.0, 30.0, 0.5),
-        BuildingStyle::Field => (50.0, 100.0, 0.1),
+        BuildingStyle::GrainField | BuildingStyle::VegetableField | BuildingStyle::Pasture => {
+            (50.0, 100.0, 0.1)
+        }
         BuildingStyle::NeighboringTownConnection => (5.0, 5.0, 0.1),
     }
 }
 
-fn footprint_dimensions(building_style: BuildingStyle) -> (N, N) {
+pub fn footprint_dimensions(building_style: BuildingStyle) -> (N, N) {
     match building_style {
         BuildingStyle::FamilyHouse => (12.0, 8.0),
         _ => (15.0, 10.0),
@@ -87,7 +89,7 @@ pub struct BuildingMesh {
 }
 
 pub fn footprint_area(lot: &Lot, building_style: BuildingStyle, extra_padding: N) -> Area {
-    if let BuildingStyle::Field = building_style {
+    if building_style.is_field() {
         lot.area.clone()
     } else {
         // TODO keep original building if lot changes
@@ -106,7 +108,7 @@ pub fn build_building(
     lot: &Lot,
     building_style: BuildingStyle,
     household_ids: &[::economy::households::HouseholdID],
-    world: &mut World,
+    _world: &mut World,
 ) -> BuildingMesh {
     // TODO keep original building if lot changes
     let mut rng = seed(lot.original_lot_id);
@@ -261,24 +263,15 @@ pub fn build_building(
                 .collect(),
             }
         }
-        BuildingStyle::Field => {
-            use ::economy::households::household_kinds::*;
-
-            let material = if let Some(farm) = household_ids.get(0) {
-                let farm_type_id = farm.as_raw().type_id;
-                if farm_type_id == grain_farm::GrainFarmID::local_first(world).as_raw().type_id {
-                    BuildingMaterial::FieldWheat
-                } else if farm_type_id
-                    == vegetable_farm::VegetableFarmID::local_first(world)
-                        .as_raw()
-                        .type_id
-                {
-                    BuildingMaterial::FieldPlant
-                } else {
-                    BuildingMaterial::FieldMeadow
-                }
-            } else {
+        BuildingStyle::GrainField | BuildingStyle::VegetableField | BuildingStyle::Pasture => {
+            let material = if household_ids.is_empty() {
                 BuildingMaterial::FieldRows
+            } else {
+                match building_style {
+                    BuildingStyle::GrainField => BuildingMaterial::FieldWheat,
+                    BuildingStyle::VegetableField => BuildingMaterial::FieldPlant,
+                    _ => BuildingMaterial::FieldMeadow,
+                }
             };
 
             let lot_surface = FlatSurface::from_primitive_area(lot.area.primitives[0].clone(), 0.0);
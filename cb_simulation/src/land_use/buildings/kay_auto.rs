@@ -63,6 +63,10 @@ impl BuildingID {
         world.send(self.as_raw(), MSG_Building_get_ui_info(requester));
     }
     
+    pub fn report_to_heatmaps(self, collector: HeatmapsID, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_report_to_heatmaps(collector, instant));
+    }
+    
     pub fn reconnect(self, new_location: PreciseLocation, new_connection_point: P2, world: &mut World) {
         world.send(self.as_raw(), MSG_Building_reconnect(new_location, new_connection_point));
     }
@@ -81,6 +85,8 @@ struct MSG_Building_finally_destroy();
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Building_get_ui_info(pub LandUseUIID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_report_to_heatmaps(pub HeatmapsID, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Building_reconnect(pub PreciseLocation, pub P2);
 
 impl Into<ConstructableID> for BuildingID {
@@ -107,6 +113,12 @@ impl Into<RoughLocationID> for BuildingID {
     }
 }
 
+impl Into<InspectableID> for BuildingID {
+    fn into(self) -> InspectableID {
+        InspectableID::from_raw(self.as_raw())
+    }
+}
+
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
@@ -115,6 +127,7 @@ pub fn auto_setup(system: &mut ActorSystem) {
     AttacheeID::register_implementor::<Building>(system);
     SleeperID::register_implementor::<Building>(system);
     RoughLocationID::register_implementor::<Building>(system);
+    InspectableID::register_implementor::<Building>(system);
     system.add_spawner::<Building, _, _>(
         |&MSG_Building_spawn(id, style, ref lot), world| {
             Building::spawn(id, style, lot, world)
@@ -151,6 +164,12 @@ pub fn auto_setup(system: &mut ActorSystem) {
         }, false
     );
     
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_report_to_heatmaps(collector, instant), instance, world| {
+            instance.report_to_heatmaps(collector, instant, world); Fate::Live
+        }, false
+    );
+    
     system.add_handler::<Building, _, _>(
         |&MSG_Building_reconnect(new_location, new_connection_point), instance, world| {
             instance.reconnect(new_location, new_connection_point, world); Fate::Live
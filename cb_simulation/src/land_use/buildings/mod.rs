@@ -3,7 +3,7 @@ use compact::{CVec, COption};
 use descartes::P2;
 
 use transport::lane::LaneID;
-use time::{Ticks, TimeID};
+use time::{Ticks, TimeID, Instant};
 use construction::{ConstructionID, Constructable, ConstructableID};
 use planning::{Prototype, PrototypeKind};
 
@@ -11,10 +11,12 @@ pub mod rendering;
 pub mod architecture;
 
 use economy::households::HouseholdID;
+use economy::resources::ResourceAmount;
 use transport::pathfinding::PreciseLocation;
 use economy::immigration_and_development::ImmigrationManagerID;
 use land_use::zone_planning::{Lot, LandUse};
 use super::ui::{LandUseUIID};
+use heatmaps::HeatmapsID;
 
 use log::debug;
 const LOG_T: &str = "Buildings";
@@ -22,16 +24,31 @@ const LOG_T: &str = "Buildings";
 #[derive(Copy, Clone)]
 pub struct Unit(Option<HouseholdID>, UnitType);
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum UnitType {
     Dwelling,
     Retail,
-    Agriculture,
+    GrainFarming,
+    VegetableFarming,
+    Livestock,
     Mill,
     Bakery,
     NeighboringTownTrade,
 }
 
+impl UnitType {
+    /// How much of all goods taken together fit into a unit
+    pub fn storage_capacity(self) -> ResourceAmount {
+        match self {
+            UnitType::Dwelling => 100.0,
+            UnitType::Retail | UnitType::Bakery => 2_000.0,
+            UnitType::VegetableFarming | UnitType::Livestock => 1_000.0,
+            UnitType::GrainFarming | UnitType::Mill => 5_000.0,
+            UnitType::NeighboringTownTrade => ::std::f32::INFINITY,
+        }
+    }
+}
+
 #[derive(Copy, Clone)]
 pub struct UnitIdx(usize);
 
@@ -39,7 +56,9 @@ pub struct UnitIdx(usize);
 pub enum BuildingStyle {
     FamilyHouse,
     GroceryShop,
-    Field,
+    GrainField,
+    VegetableField,
+    Pasture,
     Mill,
     Bakery,
     NeighboringTownConnection,
@@ -52,7 +71,18 @@ impl BuildingStyle {
             (BuildingStyle::GroceryShop, LandUse::Commercial) => true,
             (BuildingStyle::Mill, LandUse::Commercial) => true,
             (BuildingStyle::Bakery, LandUse::Commercial) => true,
-            (BuildingStyle::Field, LandUse::Agricultural) => true,
+            (BuildingStyle::GrainField, LandUse::Agricultural) => true,
+            (BuildingStyle::VegetableField, LandUse::Agricultural) => true,
+            (BuildingStyle::Pasture, LandUse::Agricultural) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_field(self) -> bool {
+        match self {
+            BuildingStyle::GrainField | BuildingStyle::VegetableField | BuildingStyle::Pasture => {
+                true
+            }
             _ => false,
         }
     }
@@ -168,6 +198,27 @@ impl Building {
     pub fn get_ui_info(&mut self, requester: LandUseUIID, world: &mut World) {
         requester.on_building_ui_info(self.id, self.style, self.all_households().into(), world);
     }
+
+    /// Reports its vacant units and lets its households report their problems at its position
+    pub fn report_to_heatmaps(
+        &mut self,
+        collector: HeatmapsID,
+        instant: Instant,
+        world: &mut World,
+    ) {
+        let position = self.lot.center_point();
+        let households = self.all_households();
+        collector.add_vacancy_sample(
+            position,
+            self.units.len() as u32,
+            (self.units.len() - households.len()) as u32,
+            world,
+        );
+
+        for household in households {
+            household.report_to_heatmaps(collector, position, instant, world);
+        }
+    }
 }
 
 impl Constructable for Building {
@@ -195,6 +246,28 @@ impl Constructable for Building {
             Fate::Live
         }
     }
+
+    fn close_for_construction(&mut self, _world: &mut World) {}
+}
+
+use inspection::{Inspectable, InspectableID, InspectorID, StateSummary};
+
+impl Inspectable for Building {
+    fn inspect(&mut self, requester: InspectorID, world: &mut World) {
+        let summary = StateSummary::new("Building")
+            .field("style", self.style)
+            .field("lot center", self.lot.center_point())
+            .field(
+                "units",
+                self.units
+                    .iter()
+                    .map(|&Unit(user, unit_type)| (unit_type, user))
+                    .collect::<Vec<_>>(),
+            )
+            .field("location", self.location);
+
+        requester.on_inspected(self.id.as_raw(), summary, world);
+    }
 }
 
 use transport::pathfinding::{Location, Attachee, AttacheeID};
@@ -290,7 +363,9 @@ pub fn units_for_style(style: BuildingStyle) -> CVec<Unit> {
         BuildingStyle::GroceryShop => vec![Unit(None, UnitType::Retail)],
         BuildingStyle::Bakery => vec![Unit(None, UnitType::Bakery)],
         BuildingStyle::Mill => vec![Unit(None, UnitType::Mill)],
-        BuildingStyle::Field => vec![Unit(None, UnitType::Agriculture)],
+        BuildingStyle::GrainField => vec![Unit(None, UnitType::GrainFarming)],
+        BuildingStyle::VegetableField => vec![Unit(None, UnitType::VegetableFarming)],
+        BuildingStyle::Pasture => vec![Unit(None, UnitType::Livestock)],
         BuildingStyle::NeighboringTownConnection => {
             Some(Unit(None, UnitType::NeighboringTownTrade))
                 .into_iter()
@@ -46,12 +46,18 @@ impl VacantLotID {
     pub fn suggest_lot(self, building_style: BuildingStyle, requester: DevelopmentManagerID, world: &mut World) {
         world.send(self.as_raw(), MSG_VacantLot_suggest_lot(building_style, requester));
     }
+    
+    pub fn report_to_heatmaps(self, collector: HeatmapsID, world: &mut World) {
+        world.send(self.as_raw(), MSG_VacantLot_report_to_heatmaps(collector));
+    }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_VacantLot_spawn(pub VacantLotID, pub Lot, pub PrototypeID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_VacantLot_suggest_lot(pub BuildingStyle, pub DevelopmentManagerID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_VacantLot_report_to_heatmaps(pub HeatmapsID);
 
 impl Into<ConstructableID> for VacantLotID {
     fn into(self) -> ConstructableID {
@@ -59,11 +65,18 @@ impl Into<ConstructableID> for VacantLotID {
     }
 }
 
+impl Into<InspectableID> for VacantLotID {
+    fn into(self) -> InspectableID {
+        InspectableID::from_raw(self.as_raw())
+    }
+}
+
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
     ConstructableID::register_implementor::<VacantLot>(system);
+    InspectableID::register_implementor::<VacantLot>(system);
     system.add_spawner::<VacantLot, _, _>(
         |&MSG_VacantLot_spawn(id, ref lot, based_on), world| {
             VacantLot::spawn(id, lot, based_on, world)
@@ -75,4 +88,10 @@ pub fn auto_setup(system: &mut ActorSystem) {
             instance.suggest_lot(building_style, requester, world); Fate::Live
         }, false
     );
+    
+    system.add_handler::<VacantLot, _, _>(
+        |&MSG_VacantLot_report_to_heatmaps(collector), instance, world| {
+            instance.report_to_heatmaps(collector, world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use kay::{World, Fate, ActorSystem};
+use kay::{World, Fate, ActorSystem, TypedID};
 use compact::CVec;
 use descartes::{N, P2, V2, Area, WithUniqueOrthogonal, ClosedLinePath, LinePath,
 AreaError, AreaEmbedding, AreaFilter};
@@ -9,12 +9,20 @@ use land_use::buildings::BuildingStyle;
 use land_use::buildings::architecture::ideal_lot_shape;
 use economy::immigration_and_development::DevelopmentManagerID;
 use itertools::{Itertools, MinMaxResult};
+use environment::terrain::slope_across;
 
 use construction::{ConstructionID, Constructable, ConstructableID};
 use planning::{Prototype, PrototypeID};
 use log::{debug, error};
+use inspection::{Inspectable, InspectableID, InspectorID, StateSummary};
+use heatmaps::HeatmapsID;
 const LOG_T: &str = "Vacant Lots";
 
+/// Steepest average terrain slope that buildings can still be built on
+const MAX_BUILDING_LOT_SLOPE: N = 0.15;
+/// Fields tolerate steeper terrain than buildings
+const MAX_FIELD_LOT_SLOPE: N = 0.25;
+
 #[derive(Compact, Clone)]
 pub struct VacantLot {
     pub id: VacantLotID,
@@ -23,6 +31,20 @@ pub struct VacantLot {
 }
 
 impl Lot {
+    pub fn terrain_slope(&self) -> N {
+        slope_across(&self.area.primitives[0].boundary.path().points)
+    }
+
+    pub fn is_too_steep_for(&self, building_style: BuildingStyle) -> bool {
+        let max_slope = if building_style.is_field() {
+            MAX_FIELD_LOT_SLOPE
+        } else {
+            MAX_BUILDING_LOT_SLOPE
+        };
+
+        self.terrain_slope() > max_slope
+    }
+
     pub fn width_depth_per_road_connection(&self) -> Vec<(P2, V2, f32, f32)> {
         let midpoints = self
             .area
@@ -120,10 +142,22 @@ impl Lot {
 
                 if width_ratio > 0.5 && width_ratio < 2.0 && depth_ratio > 0.5 && depth_ratio < 2.0
                 {
-                    if compactness >= needed_compactness {
-                        return Ok(Some(self.clone()));
-                    } else {
+                    if compactness < needed_compactness {
+                        return Ok(None);
+                    } else if self.is_too_steep_for(building_style) {
+                        debug(
+                            LOG_T,
+                            format!(
+                                "{}Lot is too steep: {}",
+                                debug_padding,
+                                self.terrain_slope()
+                            ),
+                            log_as,
+                            log_in,
+                        );
                         return Ok(None);
+                    } else {
+                        return Ok(Some(self.clone()));
                     }
                 } else if width_ratio > 2.0 && depth_ratio > 0.5 {
                     let orthogonal = direction.orthogonal_right();
@@ -338,6 +372,10 @@ impl VacantLot {
             }
         }
     }
+
+    pub fn report_to_heatmaps(&mut self, collector: HeatmapsID, world: &mut World) {
+        collector.add_vacancy_sample(self.lot.center_point(), 1, 1, world);
+    }
 }
 
 impl Constructable for VacantLot {
@@ -349,6 +387,23 @@ impl Constructable for VacantLot {
         report_to.action_done(self.id.into(), world);
         Fate::Die
     }
+
+    fn close_for_construction(&mut self, _world: &mut World) {}
+}
+
+impl Inspectable for VacantLot {
+    fn inspect(&mut self, requester: InspectorID, world: &mut World) {
+        let summary = StateSummary::new("VacantLot")
+            .field("based on", self.based_on)
+            .field("original lot", self.lot.original_lot_id)
+            .field("land uses", &self.lot.land_uses[..])
+            .field("max height", self.lot.max_height)
+            .field("set back", self.lot.set_back)
+            .field("road boundaries", self.lot.road_boundaries.len())
+            .field("terrain slope", self.lot.terrain_slope());
+
+        requester.on_inspected(self.id.as_raw(), summary, world);
+    }
 }
 
 pub fn setup(system: &mut ActorSystem) {
@@ -1,5 +1,5 @@
 use compact::CVec;
-use descartes::{P2, V2, Area, ClosedLinePath, LinePath, PointContainer,
+use descartes::{N, P2, V2, Area, ClosedLinePath, LinePath, PointContainer,
 AreaError, WithUniqueOrthogonal, AreaEmbedding, AreaFilter};
 use land_use::buildings::BuildingStyle;
 use ordered_float::OrderedFloat;
@@ -7,6 +7,7 @@ use itertools::Itertools;
 use util::random::{seed, RngCore};
 
 use transport::transport_planning::{RoadPrototype, LanePrototype};
+use environment::water::WaterPrototype;
 
 use planning::{PlanHistory, VersionedGesture, PlanResult, Prototype, PrototypeID,
 PrototypeKind, GestureIntent, GestureID, StepID};
@@ -101,6 +102,22 @@ impl Lot {
             })
             .collect()
     }
+
+    pub fn surface_area(&self) -> N {
+        self.area
+            .primitives
+            .iter()
+            .map(|primitive| {
+                let points = &primitive.boundary.path().points;
+                let doubled_signed_area: N = points
+                    .iter()
+                    .zip(points.iter().skip(1).chain(points.first()))
+                    .map(|(a, b)| a.x * b.y - b.x * a.y)
+                    .sum();
+                (doubled_signed_area / 2.0).abs()
+            })
+            .sum()
+    }
 }
 
 #[derive(Compact, Clone, Debug, Serialize, Deserialize)]
@@ -128,6 +145,7 @@ pub fn calculate_prototypes(
     #[derive(Clone, PartialEq, Eq, Hash, Debug)]
     enum ZoneEmbeddingLabel {
         Paved(PrototypeID),
+        Water(PrototypeID),
         Building(GestureID, StepID),
         Zone(ZoneIntent, GestureID, StepID),
     };
@@ -143,6 +161,15 @@ pub fn calculate_prototypes(
         {
             zone_embedding.insert(area.clone(), ZoneEmbeddingLabel::Paved(id))
         }
+
+        if let Prototype {
+            kind: PrototypeKind::Water(WaterPrototype { ref area }),
+            id,
+            ..
+        } = *prototype
+        {
+            zone_embedding.insert(area.clone(), ZoneEmbeddingLabel::Water(id))
+        }
     }
 
     for (gesture_id, VersionedGesture(gesture, step_id)) in history.gestures.pairs() {
@@ -155,6 +182,7 @@ pub fn calculate_prototypes(
     }
 
     // see what's left of original building lots after subtracting (potentially new) paved areas
+    // and water
     let building_prototypes = history
         .gestures
         .pairs()
@@ -172,6 +200,7 @@ pub fn calculate_prototypes(
                         .and(AreaFilter::Function(Box::new(|labels| {
                             labels.iter().all(|label| match label {
                                 ZoneEmbeddingLabel::Paved(_) => false,
+                                ZoneEmbeddingLabel::Water(_) => false,
                                 _ => true,
                             })
                         }))),
@@ -191,7 +220,7 @@ pub fn calculate_prototypes(
                         main_area_pieces[0].0.points[0].y.to_bits(),
                     ]);
 
-                    for paved_id in main_area_pieces
+                    for subtracted_label in main_area_pieces
                         .into_iter()
                         .flat_map(|(_piece, piece_area_label)| {
                             Some(piece_area_label.own_right_label)
@@ -199,12 +228,12 @@ pub fn calculate_prototypes(
                                 .chain(piece_area_label.right_labels)
                         })
                         .filter(|label| match label {
-                            ZoneEmbeddingLabel::Paved(_) => true,
+                            ZoneEmbeddingLabel::Paved(_) | ZoneEmbeddingLabel::Water(_) => true,
                             _ => false,
                         })
                         .unique()
                     {
-                        influenced_id = influenced_id.add_influences(paved_id);
+                        influenced_id = influenced_id.add_influences(subtracted_label);
                     }
 
                     Ok(Some(Prototype {
@@ -358,7 +387,7 @@ pub fn calculate_prototypes(
         }
     }
 
-    // remove paved and existing buildings to get vacant lots
+    // remove paved, water and existing buildings to get vacant lots
     let mut vacant_lot_prototypes = vec![];
 
     for &land_use in &LAND_USES {
@@ -378,6 +407,7 @@ pub fn calculate_prototypes(
                     labels.iter().all(|label| match label {
                         ZoneEmbeddingLabel::Building(..) => false,
                         ZoneEmbeddingLabel::Paved(_) => false,
+                        ZoneEmbeddingLabel::Water(_) => false,
                         _ => true,
                     })
                 }))),
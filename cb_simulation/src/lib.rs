@@ -8,6 +8,8 @@ extern crate noise;
 extern crate fnv;
 extern crate roaring;
 extern crate uuid;
+extern crate ron;
+extern crate xml;
 
 pub extern crate compact;
 #[macro_use]
@@ -21,6 +23,7 @@ extern crate serde_derive;
 
 pub mod util;
 pub mod log;
+pub mod inspection;
 pub mod time;
 pub mod transport;
 pub mod planning;
@@ -29,17 +32,24 @@ pub mod economy;
 pub mod land_use;
 pub mod dimensions;
 pub mod environment;
+pub mod heatmaps;
+pub mod api;
+pub mod parameters;
+pub mod access;
 
 pub fn setup_common(system: &mut kay::ActorSystem) {
     for setup_fn in &[
         time::setup,
         log::setup,
+        inspection::setup,
         planning::setup,
         construction::setup,
         transport::setup,
         economy::setup,
         land_use::setup,
         environment::setup,
+        heatmaps::setup,
+        api::setup,
     ] {
         setup_fn(system)
     }
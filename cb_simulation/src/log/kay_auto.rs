@@ -31,27 +31,40 @@ impl TypedID for LogRecipientID {
 impl<A: Actor + LogRecipient> TraitIDFrom<A> for LogRecipientID {}
 
 impl LogRecipientID {
-    pub fn receive_newest_logs(self, entries: CVec < Entry >, text: CString, effective_last: u32, effective_text_start: u32, world: &mut World) {
-        world.send(self.as_raw(), MSG_LogRecipient_receive_newest_logs(entries, text, effective_last, effective_text_start));
+    pub fn receive_newest_logs(self, entries: CVec < Entry >, effective_last: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_LogRecipient_receive_newest_logs(entries, effective_last));
+    }
+    
+    pub fn receive_log_query_result(self, query_id: u32, entries: CVec < Entry >, world: &mut World) {
+        world.send(self.as_raw(), MSG_LogRecipient_receive_log_query_result(query_id, entries));
     }
 
     pub fn register_trait(system: &mut ActorSystem) {
         system.register_trait::<LogRecipientRepresentative>();
         system.register_trait_message::<MSG_LogRecipient_receive_newest_logs>();
+        system.register_trait_message::<MSG_LogRecipient_receive_log_query_result>();
     }
 
     pub fn register_implementor<A: Actor + LogRecipient>(system: &mut ActorSystem) {
         system.register_implementor::<A, LogRecipientRepresentative>();
         system.add_handler::<A, _, _>(
-            |&MSG_LogRecipient_receive_newest_logs(ref entries, ref text, effective_last, effective_text_start), instance, world| {
-                instance.receive_newest_logs(entries, text, effective_last, effective_text_start, world); Fate::Live
+            |&MSG_LogRecipient_receive_newest_logs(ref entries, effective_last), instance, world| {
+                instance.receive_newest_logs(entries, effective_last, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<A, _, _>(
+            |&MSG_LogRecipient_receive_log_query_result(query_id, ref entries), instance, world| {
+                instance.receive_log_query_result(query_id, entries, world); Fate::Live
             }, false
         );
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_LogRecipient_receive_newest_logs(pub CVec < Entry >, pub CString, pub u32, pub u32);
+struct MSG_LogRecipient_receive_newest_logs(pub CVec < Entry >, pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_LogRecipient_receive_log_query_result(pub u32, pub CVec < Entry >);
 
 impl Actor for Log {
     type ID = LogID;
@@ -82,10 +95,10 @@ impl TypedID for LogID {
 }
 
 impl LogID {
-    pub fn spawn(world: &mut World) -> Self {
+    pub fn spawn(capacity: u32, rotation_dir: COption < CString >, world: &mut World) -> Self {
         let id = LogID::from_raw(world.allocate_instance_id::<Log>());
         let swarm = world.local_broadcast::<Log>();
-        world.send(swarm, MSG_Log_spawn(id, ));
+        world.send(swarm, MSG_Log_spawn(id, capacity, rotation_dir));
         id
     }
     
@@ -93,17 +106,35 @@ impl LogID {
         world.send(self.as_raw(), MSG_Log_log(topic, message, from, level));
     }
     
+    pub fn flush(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_Log_flush());
+    }
+    
     pub fn get_after(self, last_known: u32, max_diff: u32, recipient: LogRecipientID, world: &mut World) {
         world.send(self.as_raw(), MSG_Log_get_after(last_known, max_diff, recipient));
     }
+    
+    pub fn query(self, query: LogQuery, query_id: u32, recipient: LogRecipientID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Log_query(query, query_id, recipient));
+    }
 }
 
-#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Log_spawn(pub LogID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Log_spawn(pub LogID, pub u32, pub COption < CString >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Log_log(pub CString, pub CString, pub Option < RawID >, pub LogLevel);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Log_flush();
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Log_get_after(pub u32, pub u32, pub LogRecipientID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Log_query(pub LogQuery, pub u32, pub LogRecipientID);
+
+impl Into<TemporalID> for LogID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
 
 
 #[allow(unused_variables)]
@@ -111,9 +142,10 @@ struct MSG_Log_get_after(pub u32, pub u32, pub LogRecipientID);
 pub fn auto_setup(system: &mut ActorSystem) {
     LogRecipientID::register_trait(system);
     
+    TemporalID::register_implementor::<Log>(system);
     system.add_spawner::<Log, _, _>(
-        |&MSG_Log_spawn(id, ), world| {
-            Log::spawn(id, world)
+        |&MSG_Log_spawn(id, capacity, ref rotation_dir), world| {
+            Log::spawn(id, capacity, rotation_dir, world)
         }, false
     );
     
@@ -123,9 +155,21 @@ pub fn auto_setup(system: &mut ActorSystem) {
         }, false
     );
     
+    system.add_handler::<Log, _, _>(
+        |&MSG_Log_flush(), instance, world| {
+            instance.flush(world); Fate::Live
+        }, false
+    );
+    
     system.add_handler::<Log, _, _>(
         |&MSG_Log_get_after(last_known, max_diff, recipient), instance, world| {
             instance.get_after(last_known, max_diff, recipient, world); Fate::Live
         }, false
     );
+    
+    system.add_handler::<Log, _, _>(
+        |&MSG_Log_query(ref query, query_id, recipient), instance, world| {
+            instance.query(query, query_id, recipient, world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
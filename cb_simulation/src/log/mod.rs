@@ -1,7 +1,14 @@
 use kay::{World, ActorSystem, TypedID, RawID};
-use compact::{CVec, CString};
+use compact::{CVec, CString, COption};
+use time::{Temporal, TemporalID, Instant, Duration, Ticks};
+use parameters::SimulationParameters;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
 
-#[derive(Copy, Clone, Serialize, Deserialize)]
+/// How many entries are kept in memory if not configured otherwise
+pub const DEFAULT_LOG_CAPACITY: u32 = 10_000;
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
 pub enum LogLevel {
     Debug,
     Info,
@@ -9,39 +16,221 @@ pub enum LogLevel {
     Error,
 }
 
-#[derive(Copy, Clone, Serialize, Deserialize)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct Entry {
-    from: Option<RawID>,
-    topic_start: u32,
-    message_start: u32,
-    message_len: u32,
-    level: LogLevel,
+    /// Position in the whole log, keeps counting up when old entries are rotated out
+    pub index: u32,
+    pub instant: Instant,
+    pub from: Option<RawID>,
+    pub level: LogLevel,
+    pub topic: CString,
+    pub message: CString,
+}
+
+impl Entry {
+    fn as_line(&self) -> String {
+        format!(
+            "{}\t{}\t{:?}\t{}\t{:?}\t{}\n",
+            self.index,
+            self.instant.ticks(),
+            self.level,
+            &*self.topic,
+            self.from,
+            &*self.message
+        )
+    }
+}
+
+/// Selects entries of the log, all conditions have to apply
+#[derive(Compact, Clone)]
+pub struct LogQuery {
+    pub min_level: LogLevel,
+    pub topic: COption<CString>,
+    pub from: Option<RawID>,
+    /// Only entries that are at most this old
+    pub within: Option<Duration>,
+    /// Only entries with at least this index
+    pub after: u32,
+    /// Only the newest this many of the matching entries
+    pub max: u32,
+}
+
+impl LogQuery {
+    pub fn everything() -> LogQuery {
+        LogQuery {
+            min_level: LogLevel::Debug,
+            topic: COption(None),
+            from: None,
+            within: None,
+            after: 0,
+            max: u32::max_value(),
+        }
+    }
+
+    fn matches(&self, entry: &Entry, current_instant: Instant) -> bool {
+        entry.index >= self.after
+            && entry.level >= self.min_level
+            && self
+                .topic
+                .0
+                .as_ref()
+                .map(|topic| **topic == *entry.topic)
+                .unwrap_or(true)
+            && self
+                .from
+                .map(|from| entry.from == Some(from))
+                .unwrap_or(true)
+            && self
+                .within
+                .map(|within| {
+                    entry.instant.ticks() + Ticks::from(within).0 as usize
+                        >= current_instant.ticks()
+                })
+                .unwrap_or(true)
+    }
+}
+
+/// The newest entries of the log, at most `capacity`. Older entries are
+/// rotated out in chunks and appended to files in `rotation_dir`, if one is set.
+#[derive(Compact, Clone)]
+struct EntryBuffer {
+    entries: CVec<Entry>,
+    n_entries_total: u32,
+    /// All entries before this index are already in a file in `rotation_dir`
+    n_entries_persisted: u32,
+    capacity: u32,
+    rotation_dir: COption<CString>,
+}
+
+impl EntryBuffer {
+    fn new(capacity: u32, rotation_dir: COption<CString>) -> EntryBuffer {
+        EntryBuffer {
+            entries: CVec::new(),
+            n_entries_total: 0,
+            n_entries_persisted: 0,
+            capacity: capacity.max(1),
+            rotation_dir,
+        }
+    }
+
+    fn push(
+        &mut self,
+        instant: Instant,
+        from: Option<RawID>,
+        level: LogLevel,
+        topic: CString,
+        message: CString,
+    ) {
+        self.entries.push(Entry {
+            index: self.n_entries_total,
+            instant,
+            from,
+            level,
+            topic,
+            message,
+        });
+        self.n_entries_total += 1;
+
+        if self.entries.len() > self.capacity as usize {
+            self.rotate();
+        }
+    }
+
+    /// Drops the oldest quarter of the entries at once, so not every new entry has to move all others
+    fn rotate(&mut self) {
+        let n_rotated = self.entries.len() - self.capacity as usize * 3 / 4;
+
+        if let Some((path, lines)) = self.take_unpersisted(n_rotated) {
+            // rotation happens while logging during a tick, so the file is written on the side
+            LOG_WRITER.with(|writer| writer.write(path, lines));
+        }
+
+        self.entries = self.entries[n_rotated..].to_vec().into();
+    }
+
+    /// The file path and contents for the first `n_entries` in memory that aren't persisted yet
+    fn take_unpersisted(&mut self, n_entries: usize) -> Option<(PathBuf, String)> {
+        let n_entries_persisted = self.n_entries_persisted;
+        if let Some(last) = self.entries[..n_entries].last() {
+            self.n_entries_persisted = n_entries_persisted.max(last.index + 1);
+        }
+
+        let rotation_dir = self.rotation_dir.0.as_ref()?;
+        let to_persist = self.entries[..n_entries]
+            .iter()
+            .filter(|entry| entry.index >= n_entries_persisted)
+            .collect::<Vec<_>>();
+        let first = to_persist.first()?;
+
+        let path = Path::new(&**rotation_dir).join(format!("log_{:010}.tsv", first.index));
+        let lines = to_persist
+            .iter()
+            .map(|entry| entry.as_line())
+            .collect::<String>();
+        Some((path, lines))
+    }
+
+    fn first_index(&self) -> u32 {
+        self.entries
+            .first()
+            .map(|entry| entry.index)
+            .unwrap_or(self.n_entries_total)
+    }
+
+    /// The entries after `last_known`, but at most the newest `max_diff`,
+    /// together with the index of the first of them
+    fn newest_after(&self, last_known: u32, max_diff: u32) -> Option<(&[Entry], u32)> {
+        let effective_last = last_known
+            .max(self.n_entries_total.saturating_sub(max_diff))
+            .max(self.first_index());
+        if effective_last < self.n_entries_total {
+            let skip = (effective_last - self.first_index()) as usize;
+            Some((&self.entries[skip..], effective_last))
+        } else {
+            None
+        }
+    }
+
+    fn query(&self, query: &LogQuery, current_instant: Instant) -> Vec<Entry> {
+        let mut matching = self
+            .entries
+            .iter()
+            .rev()
+            .filter(|entry| query.matches(entry, current_instant))
+            .take(query.max as usize)
+            .cloned()
+            .collect::<Vec<_>>();
+        matching.reverse();
+        matching
+    }
 }
 
 #[derive(Compact, Clone)]
 pub struct Log {
     id: LogID,
-    entries: CVec<Entry>,
-    text: CString,
+    buffer: EntryBuffer,
+    current_instant: Instant,
 }
 
 pub trait LogRecipient {
+    /// `effective_last` is the index of the first entry, entries
+    /// in between the last known one and it were skipped
     fn receive_newest_logs(
         &mut self,
         entries: &CVec<Entry>,
-        text: &CString,
         effective_last: u32,
-        effective_text_start: u32,
         world: &mut World,
     );
+
+    fn receive_log_query_result(&mut self, query_id: u32, entries: &CVec<Entry>, world: &mut World);
 }
 
 impl Log {
-    pub fn spawn(id: LogID, _: &mut World) -> Log {
+    pub fn spawn(id: LogID, capacity: u32, rotation_dir: &COption<CString>, _: &mut World) -> Log {
         Log {
             id,
-            entries: CVec::new(),
-            text: CString::new(),
+            buffer: EntryBuffer::new(capacity, rotation_dir.clone()),
+            current_instant: Instant::new(0),
         }
     }
 
@@ -53,17 +242,42 @@ impl Log {
         level: LogLevel,
         _: &mut World,
     ) {
-        let topic_start = self.text.len() as u32;
-        self.text.push_str(topic);
-        let message_start = self.text.len() as u32;
-        self.text.push_str(message);
-        self.entries.push(Entry {
+        self.buffer.push(
+            self.current_instant,
             from,
-            topic_start,
-            message_start,
-            message_len: message.len() as u32,
             level,
-        });
+            topic.clone(),
+            message.clone(),
+        );
+    }
+
+    /// Persists all entries still in memory, blocking until all files are written.
+    /// Only meant for shutting down, when no more ticks happen
+    pub fn flush(&mut self, _: &mut World) {
+        let n_entries = self.buffer.entries.len();
+        if let Some((path, lines)) = self.buffer.take_unpersisted(n_entries) {
+            LOG_WRITER.with(|writer| writer.write(path, lines));
+        }
+
+        if self.buffer.rotation_dir.0.is_some() {
+            LOG_WRITER.with(|writer| writer.wait_until_written());
+            self.report_failed_writes();
+        }
+    }
+
+    /// Logs files the writer couldn't write as errors of the log itself.
+    /// Not done while rotating, since these entries can cause another rotation
+    fn report_failed_writes(&mut self) {
+        let failures = LOG_WRITER.with(|writer| writer.failures.try_iter().collect::<Vec<_>>());
+        for failure in failures {
+            self.buffer.push(
+                self.current_instant,
+                Some(self.id.as_raw()),
+                LogLevel::Error,
+                "Log".to_owned().into(),
+                failure.into(),
+            );
+        }
     }
 
     pub fn get_after(
@@ -73,18 +287,95 @@ impl Log {
         recipient: LogRecipientID,
         world: &mut World,
     ) {
-        let effective_last = (last_known as usize).max(self.entries.len() - max_diff as usize);
-        if effective_last < self.entries.len() {
-            let entries = self.entries[effective_last..].to_vec().into();
-            let effective_text_start = self.entries[effective_last].topic_start as usize;
-            let text = self.text[effective_text_start..].to_owned().into();
-            recipient.receive_newest_logs(
-                entries,
-                text,
-                effective_last as u32,
-                effective_text_start as u32,
-                world,
-            );
+        if let Some((entries, effective_last)) = self.buffer.newest_after(last_known, max_diff) {
+            recipient.receive_newest_logs(entries.to_vec().into(), effective_last, world);
+        }
+    }
+
+    /// Always answers, with an empty list if no entries match
+    pub fn query(
+        &mut self,
+        query: &LogQuery,
+        query_id: u32,
+        recipient: LogRecipientID,
+        world: &mut World,
+    ) {
+        let matching = self.buffer.query(query, self.current_instant);
+        recipient.receive_log_query_result(query_id, matching.into(), world);
+    }
+}
+
+enum WriterJob {
+    Write(PathBuf, String),
+    /// Answered once all writes sent before it are done
+    Sync(Sender<()>),
+}
+
+/// Writes rotated log files in order on a single long-lived thread
+struct LogWriter {
+    jobs: Sender<WriterJob>,
+    /// Descriptions of writes that failed, to be logged by the log itself
+    failures: Receiver<String>,
+}
+
+impl LogWriter {
+    fn start() -> LogWriter {
+        let (jobs, jobs_rx) = channel();
+        let (failures_tx, failures) = channel();
+
+        ::std::thread::spawn(move || {
+            for job in jobs_rx {
+                match job {
+                    WriterJob::Write(path, lines) => {
+                        if let Err(err) = write_log_file(&path, &lines) {
+                            let failure = format!("Couldn't rotate log to {:?}: {}", path, err);
+                            let _ = failures_tx.send(failure);
+                        }
+                    }
+                    WriterJob::Sync(done) => {
+                        let _ = done.send(());
+                    }
+                }
+            }
+        });
+
+        LogWriter { jobs, failures }
+    }
+
+    fn write(&self, path: PathBuf, lines: String) {
+        let _ = self.jobs.send(WriterJob::Write(path, lines));
+    }
+
+    fn wait_until_written(&self) {
+        let (done, done_rx) = channel();
+        if self.jobs.send(WriterJob::Sync(done)).is_ok() {
+            let _ = done_rx.recv();
+        }
+    }
+}
+
+thread_local! {
+    static LOG_WRITER: LogWriter = LogWriter::start();
+}
+
+fn write_log_file(path: &Path, lines: &str) -> ::std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        ::std::fs::create_dir_all(dir)?;
+    }
+    ::std::fs::write(path, lines)
+}
+
+impl Temporal for Log {
+    fn tick(
+        &mut self,
+        _dt: f32,
+        current_instant: Instant,
+        _: &SimulationParameters,
+        _: &mut World,
+    ) {
+        self.current_instant = current_instant;
+        if self.buffer.rotation_dir.0.is_some() {
+            self.report_failed_writes();
         }
     }
 }
@@ -151,6 +442,96 @@ pub fn setup(system: &mut ActorSystem) {
     auto_setup(system);
 }
 
-pub fn spawn(world: &mut World) {
-    LogID::spawn(world);
+pub fn spawn(world: &mut World, capacity: u32, rotation_dir: Option<String>) {
+    LogID::spawn(capacity, COption(rotation_dir.map(CString::from)), world);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(buffer: &mut EntryBuffer, ticks: usize, level: LogLevel, topic: &str) {
+        buffer.push(
+            Instant::new(ticks),
+            None,
+            level,
+            topic.to_owned().into(),
+            "message".to_owned().into(),
+        );
+    }
+
+    fn indices(entries: &[Entry]) -> Vec<u32> {
+        entries.iter().map(|entry| entry.index).collect()
+    }
+
+    #[test]
+    fn buffer_stays_within_capacity() {
+        let mut buffer = EntryBuffer::new(10, COption(None));
+
+        for _ in 0..25 {
+            push(&mut buffer, 0, LogLevel::Info, "Test");
+            assert!(buffer.entries.len() <= 10);
+        }
+
+        assert_eq!(buffer.n_entries_total, 25);
+        assert_eq!(indices(&buffer.entries), (16..25).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn newest_after_skips_rotated_and_too_many_entries() {
+        let mut buffer = EntryBuffer::new(10, COption(None));
+        for _ in 0..25 {
+            push(&mut buffer, 0, LogLevel::Info, "Test");
+        }
+
+        let (entries, effective_last) = buffer.newest_after(20, 3).unwrap();
+        assert_eq!(effective_last, 22);
+        assert_eq!(indices(entries), vec![22, 23, 24]);
+
+        let (entries, effective_last) = buffer.newest_after(0, 100).unwrap();
+        assert_eq!(effective_last, 16);
+        assert_eq!(entries.len(), 9);
+
+        assert!(buffer.newest_after(25, 100).is_none());
+    }
+
+    #[test]
+    fn query_applies_all_conditions() {
+        let mut buffer = EntryBuffer::new(100, COption(None));
+        push(&mut buffer, 0, LogLevel::Warning, "Planning");
+        push(&mut buffer, 0, LogLevel::Debug, "Planning");
+        push(&mut buffer, 100, LogLevel::Error, "Construction");
+        push(&mut buffer, 100, LogLevel::Warning, "Planning");
+        push(&mut buffer, 100, LogLevel::Error, "Planning");
+        let now = Instant::new(100);
+
+        let everything = buffer.query(&LogQuery::everything(), now);
+        assert_eq!(indices(&everything), vec![0, 1, 2, 3, 4]);
+
+        let planning_warnings = LogQuery {
+            min_level: LogLevel::Warning,
+            topic: COption(Some("Planning".to_owned().into())),
+            ..LogQuery::everything()
+        };
+        assert_eq!(
+            indices(&buffer.query(&planning_warnings, now)),
+            vec![0, 3, 4]
+        );
+
+        let recent = LogQuery {
+            within: Some(Duration::from_seconds(10)),
+            ..LogQuery::everything()
+        };
+        assert_eq!(indices(&buffer.query(&recent, now)), vec![2, 3, 4]);
+
+        let newest_two_after_one = LogQuery {
+            after: 1,
+            max: 2,
+            ..LogQuery::everything()
+        };
+        assert_eq!(
+            indices(&buffer.query(&newest_two_after_one, now)),
+            vec![3, 4]
+        );
+    }
 }
@@ -0,0 +1,45 @@
+use time::{Duration, DEFAULT_TICKS_PER_SIM_SECOND};
+
+/// Parameters of the simulation that can be tuned without recompiling,
+/// passed to the actors that need them when the simulation is spawned.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimulationParameters {
+    /// Also sent to browsers when they load the page, so they convert ticks the same way
+    pub ticks_per_sim_second: u32,
+    /// Ticks simulated per frame when the simulation starts
+    pub initial_speed: u16,
+    /// Sim seconds between attempts to settle a new household or develop a building for it
+    pub immigration_pace_seconds: u32,
+    /// Makes "time pass slower" for traffic, so we can still use realistic
+    /// unit values while traffic happens at a slower pace to be visible
+    pub microtraffic_unrealistic_slowdown: f32,
+    /// How many construction sites can be worked on at the same time, 0 means unlimited
+    pub construction_crew_capacity: u32,
+}
+
+impl SimulationParameters {
+    pub fn immigration_pace(&self) -> Duration {
+        Duration(self.immigration_pace_seconds)
+    }
+
+    pub fn construction_crew_capacity(&self) -> Option<u32> {
+        if self.construction_crew_capacity == 0 {
+            None
+        } else {
+            Some(self.construction_crew_capacity)
+        }
+    }
+}
+
+impl Default for SimulationParameters {
+    fn default() -> Self {
+        SimulationParameters {
+            ticks_per_sim_second: DEFAULT_TICKS_PER_SIM_SECOND,
+            initial_speed: 1,
+            immigration_pace_seconds: 10,
+            microtraffic_unrealistic_slowdown: 6.0,
+            construction_crew_capacity: 0,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+
+
+impl PlanManagerID {
+    pub fn export_master_plan(self, requester: PlanningUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_export_master_plan(requester));
+    }
+    
+    pub fn export_project(self, project_id: ProjectID, requester: PlanningUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_export_project(project_id, requester));
+    }
+    
+    pub fn import_project(self, project_id: ProjectID, author: SessionID, source: CString, transform: PlanTransform, token: EditorToken, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_import_project(project_id, author, source, transform, token));
+    }
+    
+    pub fn import_osm(self, source: CString, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_import_osm(source));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_export_master_plan(pub PlanningUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_export_project(pub ProjectID, pub PlanningUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_import_project(pub ProjectID, pub SessionID, pub CString, pub PlanTransform, pub EditorToken);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_import_osm(pub CString);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_export_master_plan(requester), instance, world| {
+            instance.export_master_plan(requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_export_project(project_id, requester), instance, world| {
+            instance.export_project(project_id, requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_import_project(project_id, author, ref source, transform, ref token), instance, world| {
+            instance.import_project(project_id, author, source, transform, token, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_import_osm(ref source), instance, world| {
+            instance.import_osm(source, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,322 @@
+use kay::World;
+use compact::{CString, COption};
+use descartes::{N, P2, V2};
+use ron::ser::{to_string_pretty, PrettyConfig};
+use planning::{PlanManager, ProjectID, Project, ProjectAuthor, SessionID, Plan, Gesture,
+GestureID, GestureIntent, VersionedGesture};
+use planning::ui::PlanningUIID;
+use access::EditorToken;
+use environment::water::WaterIntent;
+
+use log::{error, info, warn};
+const LOG_T: &str = "Plan Exchange";
+
+/// Increased whenever the file format changes in an incompatible way
+pub const PLAN_FILE_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+pub struct PlanFileGesture {
+    pub points: Vec<P2>,
+    pub intent: GestureIntent,
+}
+
+/// A portable description of planned gestures, independent of any city they were planned in
+#[derive(Serialize, Deserialize)]
+pub struct PlanFile {
+    pub version: u32,
+    pub gestures: Vec<PlanFileGesture>,
+}
+
+impl PlanFile {
+    fn from_gestures<'a, I: IntoIterator<Item = &'a Gesture>>(gestures: I) -> PlanFile {
+        PlanFile {
+            version: PLAN_FILE_VERSION,
+            gestures: gestures
+                .into_iter()
+                .filter(|gesture| !gesture.deleted)
+                // buildings, natural vegetation and natural water are created by the city itself
+                .filter(|gesture| match gesture.intent {
+                    GestureIntent::Road(_) | GestureIntent::Zone(_) => true,
+                    GestureIntent::Water(WaterIntent::Lake)
+                    | GestureIntent::Water(WaterIntent::River { .. }) => true,
+                    GestureIntent::Building(_)
+                    | GestureIntent::Plant(_)
+                    | GestureIntent::Water(WaterIntent::NaturalWater) => false,
+                })
+                .map(|gesture| PlanFileGesture {
+                    points: gesture.points.to_vec(),
+                    intent: gesture.intent.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn to_ron(&self) -> String {
+        to_string_pretty(self, PrettyConfig::default()).expect("Plan file should be serializable")
+    }
+
+    pub fn from_ron(source: &str) -> Result<PlanFile, String> {
+        let file = ::ron::de::from_str::<PlanFile>(source).map_err(|err| format!("{}", err))?;
+
+        if file.version == PLAN_FILE_VERSION {
+            Ok(file)
+        } else {
+            Err(format!(
+                "Unsupported plan file version {} (expected {})",
+                file.version, PLAN_FILE_VERSION
+            ))
+        }
+    }
+
+    fn center(&self) -> P2 {
+        let all_points = self
+            .gestures
+            .iter()
+            .flat_map(|gesture| gesture.points.iter())
+            .collect::<Vec<_>>();
+
+        if all_points.is_empty() {
+            P2::new(0.0, 0.0)
+        } else {
+            P2::from_coordinates(
+                all_points
+                    .iter()
+                    .fold(V2::new(0.0, 0.0), |sum, point| sum + point.coords)
+                    / all_points.len() as N,
+            )
+        }
+    }
+}
+
+/// Applied to imported gestures: first a rotation around their center, then a translation
+#[derive(Copy, Clone, Serialize, Deserialize, Debug)]
+pub struct PlanTransform {
+    pub translation: V2,
+    /// In radians, counter-clockwise
+    pub rotation: N,
+}
+
+impl PlanTransform {
+    fn apply(&self, point: P2, center: P2) -> P2 {
+        let (sin, cos) = self.rotation.sin_cos();
+        let relative = point - center;
+        let rotated = V2::new(
+            relative.x * cos - relative.y * sin,
+            relative.x * sin + relative.y * cos,
+        );
+        center + rotated + self.translation
+    }
+}
+
+pub mod osm;
+
+impl PlanManager {
+    pub fn export_master_plan(&mut self, requester: PlanningUIID, world: &mut World) {
+        let file = PlanFile::from_gestures(
+            self.master_plan
+                .gestures
+                .values()
+                .map(|VersionedGesture(gesture, _)| gesture),
+        );
+
+        requester.on_plan_exported(COption(None), file.to_ron().into(), world);
+    }
+
+    pub fn export_project(
+        &mut self,
+        project_id: ProjectID,
+        requester: PlanningUIID,
+        world: &mut World,
+    ) {
+        if let Some(project) = self.projects.get(project_id) {
+            // only the most recent version of each gesture
+            let latest_gestures = project
+                .current_history()
+                .iter()
+                .flat_map(|plan| plan.gestures.pairs())
+                .collect::<::std::collections::HashMap<_, _>>();
+
+            let file = PlanFile::from_gestures(latest_gestures.values().cloned());
+
+            requester.on_plan_exported(COption(Some(project_id)), file.to_ron().into(), world);
+        } else {
+            error(
+                LOG_T,
+                format!("Can't export unknown project {:?}", project_id),
+                self.id,
+                world,
+            );
+        }
+    }
+
+    pub fn import_project(
+        &mut self,
+        project_id: ProjectID,
+        author: SessionID,
+        source: &CString,
+        transform: PlanTransform,
+        token: &EditorToken,
+        world: &mut World,
+    ) {
+        if !self.permits(token, world) {
+            return;
+        }
+
+        match PlanFile::from_ron(source) {
+            Ok(file) => {
+                let center = file.center();
+                let n_gestures = file.gestures.len();
+
+                // new gesture ids, so the same file can be imported several times
+                let plan = Plan::from_gestures(file.gestures.into_iter().map(|gesture| {
+                    (
+                        GestureID::new(),
+                        Gesture::new(
+                            gesture
+                                .points
+                                .into_iter()
+                                .map(|point| transform.apply(point, center))
+                                .collect(),
+                            gesture.intent,
+                        ),
+                    )
+                }));
+
+                let mut project = Project::new(
+                    ProjectAuthor::Session(author),
+                    self.master_plan.latest_step_id(),
+                );
+                project.set_ongoing_step(plan);
+                project.start_new_step();
+                self.projects.insert(project_id, project);
+
+                info(
+                    LOG_T,
+                    format!("Imported {} gestures as a new project", n_gestures),
+                    self.id,
+                    world,
+                );
+            }
+            Err(err) => error(
+                LOG_T,
+                format!("Couldn't import plan: {}", err),
+                self.id,
+                world,
+            ),
+        }
+    }
+
+    pub fn import_osm(&mut self, source: &CString, world: &mut World) {
+        match osm::plan_from_osm(source) {
+            Ok((plan, warnings)) => {
+                for warning in warnings {
+                    warn(LOG_T, warning, self.id, world);
+                }
+
+                let n_gestures = plan.gestures.len();
+                let mut project =
+                    Project::new(ProjectAuthor::Server, self.master_plan.latest_step_id());
+                project.set_ongoing_step(plan);
+                project.start_new_step();
+                self.projects.insert(ProjectID::new(), project);
+
+                info(
+                    LOG_T,
+                    format!(
+                        "Imported {} roads, zones and water bodies from OpenStreetMap as a new project",
+                        n_gestures
+                    ),
+                    self.id,
+                    world,
+                );
+            }
+            Err(err) => error(
+                LOG_T,
+                format!("Couldn't import OpenStreetMap data: {}", err),
+                self.id,
+                world,
+            ),
+        }
+    }
+}
+
+pub mod kay_auto;
+pub use self::kay_auto::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use transport::transport_planning::RoadIntent;
+    use land_use::zone_planning::{ZoneIntent, LandUse};
+
+    fn gesture(points: &[(N, N)], intent: GestureIntent) -> Gesture {
+        Gesture::new(points.iter().map(|&(x, y)| P2::new(x, y)).collect(), intent)
+    }
+
+    #[test]
+    fn plan_file_round_trip() {
+        let gestures = vec![
+            gesture(
+                &[(0.0, 0.0), (100.0, 0.0)],
+                GestureIntent::Road(RoadIntent::new(2, 1)),
+            ),
+            gesture(
+                &[(0.0, 10.0), (50.0, 10.0), (50.0, 60.0)],
+                GestureIntent::Zone(ZoneIntent::LandUse(LandUse::Commercial)),
+            ),
+        ];
+
+        let file = PlanFile::from_ron(&PlanFile::from_gestures(&gestures).to_ron()).unwrap();
+
+        assert_eq!(file.version, PLAN_FILE_VERSION);
+        assert_eq!(file.gestures.len(), 2);
+        assert_eq!(file.gestures[0].points, gestures[0].points.to_vec());
+        assert_eq!(file.gestures[1].points, gestures[1].points.to_vec());
+        match file.gestures[0].intent {
+            GestureIntent::Road(road_intent) => {
+                assert_eq!(road_intent.n_lanes_forward, 2);
+                assert_eq!(road_intent.n_lanes_backward, 1);
+            }
+            ref other => panic!("Expected a road, got {:?}", other),
+        }
+        match file.gestures[1].intent {
+            GestureIntent::Zone(ref zone_intent) => {
+                assert_eq!(*zone_intent, ZoneIntent::LandUse(LandUse::Commercial))
+            }
+            ref other => panic!("Expected a zone, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn natural_water_is_not_exported() {
+        let gestures = vec![
+            gesture(
+                &[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)],
+                GestureIntent::Water(WaterIntent::NaturalWater),
+            ),
+            gesture(
+                &[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)],
+                GestureIntent::Water(WaterIntent::Lake),
+            ),
+        ];
+
+        let file = PlanFile::from_gestures(&gestures);
+
+        assert_eq!(file.gestures.len(), 1);
+        match file.gestures[0].intent {
+            GestureIntent::Water(WaterIntent::Lake) => {}
+            ref other => panic!("Expected a lake, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn other_versions_are_rejected() {
+        let source = PlanFile {
+            version: PLAN_FILE_VERSION + 1,
+            gestures: Vec::new(),
+        }
+        .to_ron();
+
+        assert!(PlanFile::from_ron(&source).is_err());
+    }
+}
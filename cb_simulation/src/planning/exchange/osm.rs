@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use xml::reader::{EventReader, XmlEvent};
+use xml::attribute::OwnedAttribute;
+use descartes::{N, P2};
+use planning::{Plan, Gesture, GestureID, GestureIntent};
+use transport::transport_planning::RoadIntent;
+use land_use::zone_planning::{ZoneIntent, LandUse};
+use environment::water::WaterIntent;
+
+const EARTH_RADIUS: f64 = 6_371_000.0;
+const MAX_LANES_PER_DIRECTION: u8 = 4;
+const DEFAULT_RIVER_WIDTH: f32 = 30.0;
+const DEFAULT_CANAL_WIDTH: f32 = 15.0;
+
+fn attribute<'a>(attributes: &'a [OwnedAttribute], key: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|attribute| attribute.name.local_name == key)
+        .map(|attribute| attribute.value.as_str())
+}
+
+#[derive(Default)]
+struct Way {
+    node_refs: Vec<i64>,
+    tags: HashMap<String, String>,
+}
+
+fn road_intent(tags: &HashMap<String, String>) -> Option<RoadIntent> {
+    let highway = tags.get("highway")?;
+
+    let default_lanes_per_direction = match highway.as_str() {
+        "motorway" | "trunk" => 2,
+        "primary" | "secondary" | "tertiary" | "unclassified" | "residential" | "living_street"
+        | "service" | "motorway_link" | "trunk_link" | "primary_link" | "secondary_link"
+        | "tertiary_link" => 1,
+        // footways, cycleways, tracks, paths etc.
+        _ => return None,
+    };
+
+    let lanes_tag = |key: &str| tags.get(key).and_then(|value| value.parse::<u8>().ok());
+    let is_oneway = match tags.get("oneway").map(|value| value.as_str()) {
+        Some("yes") | Some("1") | Some("true") | Some("-1") => true,
+        _ => highway.as_str() == "motorway",
+    };
+
+    let (n_lanes_forward, n_lanes_backward) = if is_oneway {
+        (lanes_tag("lanes").unwrap_or(default_lanes_per_direction), 0)
+    } else {
+        let total_lanes = lanes_tag("lanes").unwrap_or(2 * default_lanes_per_direction);
+        (
+            lanes_tag("lanes:forward").unwrap_or((total_lanes + 1) / 2),
+            lanes_tag("lanes:backward").unwrap_or(total_lanes / 2),
+        )
+    };
+
+    Some(RoadIntent::new(
+        n_lanes_forward.max(1).min(MAX_LANES_PER_DIRECTION),
+        n_lanes_backward.min(MAX_LANES_PER_DIRECTION),
+    ))
+}
+
+fn land_use(tags: &HashMap<String, String>) -> Option<LandUse> {
+    match tags.get("landuse")?.as_str() {
+        "residential" => Some(LandUse::Residential),
+        "commercial" | "retail" => Some(LandUse::Commercial),
+        "industrial" => Some(LandUse::Industrial),
+        "farmland" | "farmyard" | "orchard" | "meadow" | "vineyard" | "greenhouse_horticulture" => {
+            Some(LandUse::Agricultural)
+        }
+        "recreation_ground" | "village_green" | "grass" => Some(LandUse::Recreational),
+        "civic_admin" | "institutional" | "education" | "religious" => Some(LandUse::Official),
+        _ => None,
+    }
+}
+
+fn water_intent(tags: &HashMap<String, String>) -> Option<WaterIntent> {
+    if tags.get("natural").map(|value| value.as_str()) == Some("water") {
+        return Some(WaterIntent::Lake);
+    }
+
+    let default_width = match tags.get("waterway")?.as_str() {
+        "river" => DEFAULT_RIVER_WIDTH,
+        "canal" => DEFAULT_CANAL_WIDTH,
+        // streams, ditches and drains are too small to matter
+        _ => return None,
+    };
+
+    Some(WaterIntent::River {
+        width: tags
+            .get("width")
+            .and_then(|value| value.parse::<f32>().ok())
+            .unwrap_or(default_width),
+    })
+}
+
+/// Turns the drivable ways, land use areas and water bodies of an OSM XML extract into gestures.
+/// Coordinates are projected onto the simulation plane (in meters, x to the east
+/// and y to the north) around the center of the extract.
+/// Also returns warnings about parts of the extract that had to be skipped.
+pub fn plan_from_osm(source: &str) -> Result<(Plan, Vec<String>), String> {
+    let mut maybe_bounds_center = None;
+    let mut nodes = HashMap::<i64, (f64, f64)>::new();
+    let mut ways = Vec::<Way>::new();
+    let mut current_way: Option<Way> = None;
+    let mut warnings = Vec::new();
+    let mut n_invalid_nodes = 0;
+
+    for event in EventReader::from_str(source) {
+        match event.map_err(|err| format!("Invalid OSM XML: {}", err))? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                let coordinate = |key: &str| {
+                    attribute(&attributes, key).and_then(|value| value.parse::<f64>().ok())
+                };
+
+                match name.local_name.as_str() {
+                    "bounds" => {
+                        if let (Some(min_lat), Some(max_lat), Some(min_lon), Some(max_lon)) = (
+                            coordinate("minlat"),
+                            coordinate("maxlat"),
+                            coordinate("minlon"),
+                            coordinate("maxlon"),
+                        ) {
+                            maybe_bounds_center =
+                                Some(((min_lat + max_lat) / 2.0, (min_lon + max_lon) / 2.0));
+                        } else {
+                            warnings.push(
+                                "Ignored <bounds> without valid coordinates, centering on the \
+                                 nodes instead"
+                                    .to_owned(),
+                            );
+                        }
+                    }
+                    "node" => {
+                        if let (Some(id), Some(lat), Some(lon)) = (
+                            attribute(&attributes, "id").and_then(|id| id.parse().ok()),
+                            coordinate("lat"),
+                            coordinate("lon"),
+                        ) {
+                            nodes.insert(id, (lat, lon));
+                        } else {
+                            n_invalid_nodes += 1;
+                        }
+                    }
+                    "way" => {
+                        current_way = Some(Way::default());
+                    }
+                    "nd" => {
+                        if let (Some(way), Some(node_ref)) = (
+                            current_way.as_mut(),
+                            attribute(&attributes, "ref").and_then(|id| id.parse().ok()),
+                        ) {
+                            way.node_refs.push(node_ref);
+                        }
+                    }
+                    "tag" => {
+                        // tags of nodes and relations are ignored, since only ways are imported
+                        if let (Some(way), Some(key), Some(value)) = (
+                            current_way.as_mut(),
+                            attribute(&attributes, "k"),
+                            attribute(&attributes, "v"),
+                        ) {
+                            way.tags.insert(key.to_owned(), value.to_owned());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            XmlEvent::EndElement { name } => {
+                if name.local_name == "way" {
+                    if let Some(finished_way) = current_way.take() {
+                        ways.push(finished_way);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if n_invalid_nodes > 0 {
+        warnings.push(format!(
+            "Skipped {} nodes without a valid id, lat or lon",
+            n_invalid_nodes
+        ));
+    }
+
+    if nodes.is_empty() {
+        return Err("No nodes found in OSM file".to_owned());
+    }
+
+    let (center_lat, center_lon) = maybe_bounds_center.unwrap_or_else(|| {
+        let (lat_sum, lon_sum) = nodes
+            .values()
+            .fold((0.0, 0.0), |(lat_sum, lon_sum), (lat, lon)| {
+                (lat_sum + lat, lon_sum + lon)
+            });
+        (lat_sum / nodes.len() as f64, lon_sum / nodes.len() as f64)
+    });
+
+    // equirectangular projection, precise enough for the extent of a city
+    let project = |(lat, lon): (f64, f64)| {
+        P2::new(
+            (EARTH_RADIUS * (lon - center_lon).to_radians() * center_lat.to_radians().cos()) as N,
+            (EARTH_RADIUS * (lat - center_lat).to_radians()) as N,
+        )
+    };
+
+    let way_points = |way: &Way| {
+        let mut points = way
+            .node_refs
+            .iter()
+            .filter_map(|node_ref| nodes.get(node_ref).map(|coordinates| project(*coordinates)))
+            .collect::<Vec<_>>();
+        points.dedup_by(|a, b| (*a - *b).norm() < 0.5);
+        points
+    };
+
+    let area_points = |way: &Way| {
+        let mut points = way_points(way);
+        // zones and lakes are closed implicitly
+        if points.len() > 1 && (points[0] - points[points.len() - 1]).norm() < 0.5 {
+            points.pop();
+        }
+        if points.len() >= 3 {
+            Some(points)
+        } else {
+            None
+        }
+    };
+
+    let gestures = ways
+        .iter()
+        .filter_map(|way| {
+            if let Some(road_intent) = road_intent(&way.tags) {
+                let mut points = way_points(way);
+                if way.tags.get("oneway").map(|value| value.as_str()) == Some("-1") {
+                    points.reverse();
+                }
+                Some((points, GestureIntent::Road(road_intent)))
+            } else if let Some(land_use) = land_use(&way.tags) {
+                let intent = GestureIntent::Zone(ZoneIntent::LandUse(land_use));
+                area_points(way).map(|points| (points, intent))
+            } else if let Some(water_intent) = water_intent(&way.tags) {
+                let intent = GestureIntent::Water(water_intent);
+                if let WaterIntent::Lake = water_intent {
+                    area_points(way).map(|points| (points, intent))
+                } else {
+                    Some((way_points(way), intent))
+                }
+            } else {
+                None
+            }
+        })
+        .filter(|(points, _)| points.len() >= 2)
+        .map(|(points, intent)| (GestureID::new(), Gesture::new(points.into(), intent)))
+        .collect::<Vec<_>>();
+
+    if gestures.is_empty() {
+        Err("No roads, land use areas or water found in OSM file".to_owned())
+    } else {
+        Ok((Plan::from_gestures(gestures), warnings))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|&(key, value)| (key.to_owned(), value.to_owned()))
+            .collect()
+    }
+
+    fn lanes(tag_pairs: &[(&str, &str)]) -> (u8, u8) {
+        let intent = road_intent(&tags(tag_pairs)).expect("Should be a road");
+        (intent.n_lanes_forward, intent.n_lanes_backward)
+    }
+
+    #[test]
+    fn lane_tags_map_to_lane_counts() {
+        assert_eq!(lanes(&[("highway", "residential")]), (1, 1));
+        assert_eq!(lanes(&[("highway", "primary"), ("lanes", "4")]), (2, 2));
+        assert_eq!(lanes(&[("highway", "primary"), ("lanes", "3")]), (2, 1));
+        assert_eq!(
+            lanes(&[
+                ("highway", "secondary"),
+                ("lanes:forward", "3"),
+                ("lanes:backward", "1"),
+            ]),
+            (3, 1)
+        );
+        assert_eq!(lanes(&[("highway", "trunk"), ("lanes", "20")]), (4, 4));
+        assert!(road_intent(&tags(&[("highway", "footway")])).is_none());
+    }
+
+    #[test]
+    fn oneway_tags_map_to_lane_counts() {
+        assert_eq!(lanes(&[("highway", "tertiary"), ("oneway", "yes")]), (1, 0));
+        assert_eq!(
+            lanes(&[("highway", "primary"), ("oneway", "-1"), ("lanes", "2")]),
+            (2, 0)
+        );
+        assert_eq!(lanes(&[("highway", "motorway")]), (2, 0));
+    }
+
+    #[test]
+    fn nodes_with_invalid_coordinates_are_skipped() {
+        let source = r#"<?xml version="1.0" encoding="UTF-8"?>
+<osm version="0.6">
+  <bounds minlat="52.0" minlon="13.0" maxlat="52.01" maxlon="13.01"/>
+  <node id="1" lat="52.001" lon="13.001"/>
+  <node id="2" lat="north" lon="13.002"/>
+  <node id="3" lat="52.002" lon="13.003"/>
+  <node id="4" lat="52.003"/>
+  <way id="10">
+    <nd ref="1"/>
+    <nd ref="2"/>
+    <nd ref="3"/>
+    <nd ref="4"/>
+    <tag k="highway" v="residential"/>
+  </way>
+</osm>"#;
+
+        let (plan, warnings) = plan_from_osm(source).unwrap();
+
+        assert_eq!(plan.gestures.len(), 1);
+        let n_points = plan
+            .gestures
+            .values()
+            .map(|gesture| gesture.points.len())
+            .next();
+        assert_eq!(n_points, Some(2));
+        assert!(warnings
+            .iter()
+            .any(|warning| warning.contains("Skipped 2 nodes")));
+    }
+}
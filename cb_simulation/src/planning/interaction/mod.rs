@@ -3,6 +3,7 @@ use compact::{CHashMap, COption};
 use descartes::{P2, AreaError, LinePath};
 use planning::{ProjectID, PlanHistory, PlanResult, ActionGroups, PlanManager, PlanManagerID, KnownHistoryState, KnownProjectState, ProjectUpdate, GestureID, GestureIntent, Gesture, Plan, KnownPlanResultState};
 use planning::ui::PlanningUIID;
+use access::EditorToken;
 use log::error;
 const LOG_T: &str = "Planning Interaction";
 
@@ -16,21 +17,37 @@ pub struct PreviewSet {
 #[derive(Compact, Clone)]
 pub struct PlanManagerUIState {
     previews: CHashMap<ProjectID, PreviewSet>,
+    // invalidated previews are kept around so their results can be
+    // reused for the parts of the plan that didn't change
+    outdated_previews: CHashMap<ProjectID, PreviewSet>,
 }
 
 impl PlanManagerUIState {
     pub fn new() -> Self {
         PlanManagerUIState {
             previews: CHashMap::new(),
+            outdated_previews: CHashMap::new(),
         }
     }
 
     pub fn invalidate(&mut self, project_id: ProjectID) {
+        if let Some(preview) = self.previews.remove(project_id) {
+            if preview.result.0.is_some() {
+                self.outdated_previews.insert(project_id, preview);
+            }
+        }
+    }
+
+    pub fn forget(&mut self, project_id: ProjectID) {
         self.previews.remove(project_id);
+        self.outdated_previews.remove(project_id);
     }
 
     pub fn invalidate_all(&mut self) {
-        self.previews = CHashMap::new();
+        let project_ids = self.previews.keys().cloned().collect::<Vec<_>>();
+        for project_id in project_ids {
+            self.invalidate(project_id);
+        }
     }
 }
 
@@ -103,7 +120,18 @@ impl PlanManager {
                     .unwrap()
                     .apply_to_with_ongoing(&self.master_plan);
 
-            let maybe_preview_result = match preview_history.calculate_result() {
+            let calculated_result = match self.ui_state.outdated_previews.remove(project_id) {
+                Some(PreviewSet {
+                    history: outdated_history,
+                    result: COption(Some(outdated_result)),
+                    ..
+                }) => {
+                    preview_history.calculate_result_based_on(&outdated_history, &outdated_result)
+                }
+                _ => preview_history.calculate_result_reusing_growth_of(&self.master_result),
+            };
+
+            let maybe_preview_result = match calculated_result {
                 Ok(preview_plan_result) => Some(preview_plan_result),
                 Err(err) => {
                     let err_str = match err {
@@ -141,8 +169,13 @@ impl PlanManager {
         new_gesture_id: GestureID,
         intent: &GestureIntent,
         start: P2,
-        _: &mut World,
+        token: &EditorToken,
+        world: &mut World,
     ) {
+        if !self.permits(token, world) {
+            return;
+        }
+
         let new_gesture = Gesture::new(vec![start].into(), intent.clone());
 
         let new_step = Plan::from_gestures(Some((new_gesture_id, new_gesture)));
@@ -163,8 +196,13 @@ impl PlanManager {
         new_point: P2,
         add_to_end: bool,
         commit: bool,
-        _: &mut World,
+        token: &EditorToken,
+        world: &mut World,
     ) {
+        if !self.permits(token, world) {
+            return;
+        }
+
         let new_step = {
             let current_gesture = self.get_current_version_of(gesture_id, project_id);
 
@@ -209,8 +247,13 @@ impl PlanManager {
         gesture_id: GestureID,
         new_point: P2,
         commit: bool,
-        _: &mut World,
+        token: &EditorToken,
+        world: &mut World,
     ) {
+        if !self.permits(token, world) {
+            return;
+        }
+
         let new_step = {
             let current_gesture = self.get_current_version_of(gesture_id, project_id);
 
@@ -257,8 +300,13 @@ impl PlanManager {
         point_index: u32,
         new_position: P2,
         is_move_finished: bool,
-        _: &mut World,
+        token: &EditorToken,
+        world: &mut World,
     ) {
+        if !self.permits(token, world) {
+            return;
+        }
+
         let current_change = {
             let current_gesture = self.get_current_version_of(gesture_id, project_id);
 
@@ -282,8 +330,6 @@ impl PlanManager {
             .unwrap()
             .set_ongoing_step(current_change);
 
-        // TODO: can we update only part of the preview
-        // for better rendering performance while dragging?
         self.ui_state.invalidate(project_id);
 
         if is_move_finished {
@@ -297,8 +343,13 @@ impl PlanManager {
         gesture_id: GestureID,
         split_at: P2,
         commit: bool,
-        _: &mut World,
+        token: &EditorToken,
+        world: &mut World,
     ) {
+        if !self.permits(token, world) {
+            return;
+        }
+
         let maybe_new_step = {
             let current_gesture = self.get_current_version_of(gesture_id, project_id);
 
@@ -363,8 +414,13 @@ impl PlanManager {
         gesture_id: GestureID,
         new_intent: &GestureIntent,
         is_move_finished: bool,
-        _: &mut World,
+        token: &EditorToken,
+        world: &mut World,
     ) {
+        if !self.permits(token, world) {
+            return;
+        }
+
         let current_change = {
             let current_gesture = self.get_current_version_of(gesture_id, project_id);
 
@@ -381,8 +437,6 @@ impl PlanManager {
             .unwrap()
             .set_ongoing_step(current_change);
 
-        // TODO: can we update only part of the preview
-        // for better rendering performance while dragging?
         self.ui_state.invalidate(project_id);
 
         if is_move_finished {
@@ -390,12 +444,20 @@ impl PlanManager {
         }
     }
 
-    pub fn undo(&mut self, project_id: ProjectID, _: &mut World) {
+    pub fn undo(&mut self, project_id: ProjectID, token: &EditorToken, world: &mut World) {
+        if !self.permits(token, world) {
+            return;
+        }
+
         self.projects.get_mut(project_id).unwrap().undo();
         self.ui_state.invalidate(project_id);
     }
 
-    pub fn redo(&mut self, project_id: ProjectID, _: &mut World) {
+    pub fn redo(&mut self, project_id: ProjectID, token: &EditorToken, world: &mut World) {
+        if !self.permits(token, world) {
+            return;
+        }
+
         self.projects.get_mut(project_id).unwrap().redo();
         self.ui_state.invalidate(project_id);
     }
@@ -36,19 +36,35 @@ impl TypedID for PlanManagerID {
 }
 
 impl PlanManagerID {
-    pub fn spawn(world: &mut World) -> Self {
+    pub fn spawn(access: AccessControl, world: &mut World) -> Self {
         let id = PlanManagerID::from_raw(world.allocate_instance_id::<PlanManager>());
         let swarm = world.local_broadcast::<PlanManager>();
-        world.send(swarm, MSG_PlanManager_spawn(id, ));
+        world.send(swarm, MSG_PlanManager_spawn(id, access));
         id
     }
     
-    pub fn start_new_project(self, project_id: ProjectID, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_start_new_project(project_id));
+    pub fn start_new_project(self, project_id: ProjectID, author: SessionID, token: EditorToken, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_start_new_project(project_id, author, token));
     }
     
-    pub fn implement(self, project_id: ProjectID, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_implement(project_id));
+    pub fn rebase_project(self, project_id: ProjectID, token: EditorToken, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_rebase_project(project_id, token));
+    }
+    
+    pub fn resolve_conflict(self, project_id: ProjectID, gesture_id: GestureID, keep_own: bool, token: EditorToken, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_resolve_conflict(project_id, gesture_id, keep_own, token));
+    }
+    
+    pub fn implement(self, project_id: ProjectID, token: EditorToken, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_implement(project_id, token));
+    }
+    
+    pub fn funds_granted(self, project_id: ProjectID, cost: ResourceAmount, nonce: FundingNonce, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_funds_granted(project_id, cost, nonce));
+    }
+    
+    pub fn funds_denied(self, project_id: ProjectID, nonce: FundingNonce, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_funds_denied(project_id, nonce));
     }
     
     pub fn implement_artificial_project(self, project: Project, based_on: CVec < PrototypeID >, world: &mut World) {
@@ -56,12 +72,20 @@ impl PlanManagerID {
     }
 }
 
-#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_spawn(pub PlanManagerID, );
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_start_new_project(pub ProjectID);
+struct MSG_PlanManager_spawn(pub PlanManagerID, pub AccessControl);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_implement(pub ProjectID);
+struct MSG_PlanManager_start_new_project(pub ProjectID, pub SessionID, pub EditorToken);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_rebase_project(pub ProjectID, pub EditorToken);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_resolve_conflict(pub ProjectID, pub GestureID, pub bool, pub EditorToken);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_implement(pub ProjectID, pub EditorToken);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_funds_granted(pub ProjectID, pub ResourceAmount, pub FundingNonce);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_funds_denied(pub ProjectID, pub FundingNonce);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_PlanManager_implement_artificial_project(pub Project, pub CVec < PrototypeID >);
 
@@ -72,20 +96,44 @@ pub fn auto_setup(system: &mut ActorSystem) {
     
     
     system.add_spawner::<PlanManager, _, _>(
-        |&MSG_PlanManager_spawn(id, ), world| {
-            PlanManager::spawn(id, world)
+        |&MSG_PlanManager_spawn(id, ref access), world| {
+            PlanManager::spawn(id, access, world)
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_start_new_project(project_id, author, ref token), instance, world| {
+            instance.start_new_project(project_id, author, token, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_rebase_project(project_id, ref token), instance, world| {
+            instance.rebase_project(project_id, token, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_resolve_conflict(project_id, gesture_id, keep_own, ref token), instance, world| {
+            instance.resolve_conflict(project_id, gesture_id, keep_own, token, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_implement(project_id, ref token), instance, world| {
+            instance.implement(project_id, token, world); Fate::Live
         }, false
     );
     
     system.add_handler::<PlanManager, _, _>(
-        |&MSG_PlanManager_start_new_project(project_id), instance, world| {
-            instance.start_new_project(project_id, world); Fate::Live
+        |&MSG_PlanManager_funds_granted(project_id, cost, nonce), instance, world| {
+            instance.funds_granted(project_id, cost, nonce, world); Fate::Live
         }, false
     );
     
     system.add_handler::<PlanManager, _, _>(
-        |&MSG_PlanManager_implement(project_id), instance, world| {
-            instance.implement(project_id, world); Fate::Live
+        |&MSG_PlanManager_funds_denied(project_id, nonce), instance, world| {
+            instance.funds_denied(project_id, nonce, world); Fate::Live
         }, false
     );
     
@@ -3,18 +3,24 @@
 use kay::{World, ActorSystem, TypedID};
 use compact::{CVec, COption, CHashMap};
 use descartes::{N, P2, AreaError};
-use util::random::{seed, RngCore, Uuid, uuid};
+use util::random::{seed, thread_rng, RngCore, Uuid, uuid};
 use std::hash::Hash;
 
 use transport::transport_planning::{RoadIntent, RoadPrototype};
 use land_use::zone_planning::{ZoneIntent, BuildingIntent, LotPrototype};
-use environment::vegetation::{PlantIntent, PlantPrototype};
+use environment::vegetation::{PlantIntent, PlantPrototype, NaturalGrowth};
+use environment::water::{WaterIntent, WaterPrototype};
 use construction::ConstructionID;
+use economy::treasury::{TreasuryID, FundingNonce, construction_cost};
+use economy::resources::ResourceAmount;
+use access::{AccessControl, EditorToken};
 
-use log::{error, info};
+use log::{error, info, warn};
 const LOG_T: &str = "Planning";
 
 pub mod interaction;
+pub mod exchange;
+pub mod timeline;
 pub mod ui;
 
 // idea for improvement:
@@ -54,6 +60,7 @@ pub enum GestureIntent {
     Zone(ZoneIntent),
     Building(BuildingIntent),
     Plant(PlantIntent),
+    Water(WaterIntent),
 }
 
 #[derive(Copy, Clone, Hash, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -246,16 +253,43 @@ impl PlanHistoryUpdate {
     }
 }
 
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct SessionID(pub Uuid);
+
+impl SessionID {
+    pub fn new() -> SessionID {
+        SessionID(uuid())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum ProjectAuthor {
+    /// The city developing on its own
+    Simulation,
+    /// Whoever runs the server, for example by importing files on startup
+    Server,
+    Session(SessionID),
+}
+
 #[derive(Compact, Clone, Debug, Serialize, Deserialize)]
 pub struct Project {
+    pub author: ProjectAuthor,
+    /// The latest step of the master plan when the project was started or last rebased
+    pub based_on: StepID,
+    /// Gestures that were also changed by others before the last rebase,
+    /// the project can only be implemented once each of them is resolved
+    pub unresolved_conflicts: CVec<GestureID>,
     undoable_history: CVec<Plan>,
     ongoing: Plan,
     redoable_history: CVec<Plan>,
 }
 
 impl Project {
-    pub fn new() -> Project {
+    pub fn new(author: ProjectAuthor, based_on: StepID) -> Project {
         Project {
+            author,
+            based_on,
+            unresolved_conflicts: CVec::new(),
             undoable_history: CVec::new(),
             ongoing: Plan::new(),
             redoable_history: CVec::new(),
@@ -264,6 +298,9 @@ impl Project {
 
     pub fn from_plan(plan: Plan) -> Project {
         Project {
+            author: ProjectAuthor::Simulation,
+            based_on: plan.step_id,
+            unresolved_conflicts: CVec::new(),
             undoable_history: vec![plan].into(),
             ongoing: Plan::new(),
             redoable_history: CVec::new(),
@@ -392,12 +429,28 @@ impl PrototypesSpatialGrid {
         }
     }
 
+    pub fn coords_of(position: P2) -> (i32, i32) {
+        (
+            (position.x / PROTO_SPATIAL_GRID_CELL_SIZE).floor() as i32,
+            (position.y / PROTO_SPATIAL_GRID_CELL_SIZE).floor() as i32,
+        )
+    }
+
+    fn bounds_of_cell((x, y): (i32, i32)) -> GestureBounds {
+        GestureBounds {
+            min: P2::new(
+                x as N * PROTO_SPATIAL_GRID_CELL_SIZE,
+                y as N * PROTO_SPATIAL_GRID_CELL_SIZE,
+            ),
+            max: P2::new(
+                (x + 1) as N * PROTO_SPATIAL_GRID_CELL_SIZE,
+                (y + 1) as N * PROTO_SPATIAL_GRID_CELL_SIZE,
+            ),
+        }
+    }
+
     pub fn add_protoype(&mut self, proto: &Prototype) {
-        let pos = proto.representative_position;
-        let grid_coords = (
-            (pos.x / PROTO_SPATIAL_GRID_CELL_SIZE) as i32,
-            (pos.y / PROTO_SPATIAL_GRID_CELL_SIZE) as i32,
-        );
+        let grid_coords = PrototypesSpatialGrid::coords_of(proto.representative_position);
         let found_cell = if let Some(grid_cell) = self.cells.get_mut(grid_coords) {
             match grid_cell.members.binary_search(&proto.id) {
                 Err(empty_pos) => grid_cell.members.insert(empty_pos, proto.id),
@@ -420,11 +473,7 @@ impl PrototypesSpatialGrid {
     }
 
     pub fn remove_prototype(&mut self, proto: &Prototype) {
-        let pos = proto.representative_position;
-        let grid_coords = (
-            (pos.x / PROTO_SPATIAL_GRID_CELL_SIZE) as i32,
-            (pos.y / PROTO_SPATIAL_GRID_CELL_SIZE) as i32,
-        );
+        let grid_coords = PrototypesSpatialGrid::coords_of(proto.representative_position);
         let grid_cell = self
             .cells
             .get_mut(grid_coords)
@@ -493,6 +542,8 @@ impl PrototypesSpatialGrid {
 pub struct PlanResult {
     pub prototypes: CHashMap<PrototypeID, Prototype>,
     pub grid: PrototypesSpatialGrid,
+    #[serde(skip, default = "NaturalGrowth::new")]
+    pub natural_growth: NaturalGrowth,
 }
 
 impl PlanResult {
@@ -500,7 +551,31 @@ impl PlanResult {
         PlanResult {
             prototypes: CHashMap::new(),
             grid: PrototypesSpatialGrid::new(),
+            natural_growth: NaturalGrowth::new(),
+        }
+    }
+
+    fn add_prototype(&mut self, prototype: Prototype) {
+        self.grid.add_protoype(&prototype);
+        self.prototypes.insert(prototype.id, prototype);
+    }
+
+    fn remove_prototype(&mut self, prototype_id: PrototypeID) {
+        if let Some(prototype) = self.prototypes.remove(prototype_id) {
+            self.grid.remove_prototype(&prototype);
+        }
+    }
+
+    fn filtered<F: Fn(&Prototype) -> bool>(&self, predicate: F) -> PlanResult {
+        let mut filtered = PlanResult::new();
+        for prototype in self
+            .prototypes
+            .values()
+            .filter(|prototype| predicate(prototype))
+        {
+            filtered.add_prototype(prototype.clone());
         }
+        filtered
     }
 
     pub fn actions_to(&self, other: &PlanResult) -> (ActionGroups, CVec<Prototype>) {
@@ -651,6 +726,7 @@ pub enum PrototypeKind {
     Road(RoadPrototype),
     Lot(LotPrototype),
     Plant(PlantPrototype),
+    Water(WaterPrototype),
 }
 
 #[derive(Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Debug)]
@@ -767,31 +843,287 @@ impl ActionGroups {
     }
 }
 
+type PrototypeFn = fn(&PlanHistory, &PlanResult) -> Result<Vec<Prototype>, AreaError>;
+
+/// Each stage sees the prototypes of all previous stages.
+/// Water goes first, since it blocks roads, lots and vegetation.
+const PLANNED_STAGES: [PrototypeFn; 3] = [
+    ::environment::water::calculate_prototypes,
+    ::transport::transport_planning::calculate_prototypes,
+    ::land_use::zone_planning::calculate_prototypes,
+];
+
+fn is_vegetation(prototype: &Prototype) -> bool {
+    if let PrototypeKind::Plant(_) = prototype.kind {
+        true
+    } else {
+        false
+    }
+}
+
 impl PlanHistory {
     pub fn calculate_result(&self) -> Result<PlanResult, AreaError> {
+        self.calculate_result_reusing_growth_of(&PlanResult::new())
+    }
+
+    /// Calculates everything again, except for natural growth already generated
+    /// for `other_result`, which only depends on the gesture and the chunk
+    pub fn calculate_result_reusing_growth_of(
+        &self,
+        other_result: &PlanResult,
+    ) -> Result<PlanResult, AreaError> {
+        if self.has_deleted_gestures() {
+            return self
+                .without_deleted_gestures()
+                .calculate_result_reusing_growth_of(other_result);
+        }
+
+        let mut result = self.calculate_planned_result()?;
+        self.add_vegetation(&mut result, &other_result.natural_growth)?;
+        Ok(result)
+    }
+
+    fn calculate_planned_result(&self) -> Result<PlanResult, AreaError> {
         let mut result = PlanResult::new();
 
-        for prototype_fn in &[
-            ::transport::transport_planning::calculate_prototypes,
-            ::land_use::zone_planning::calculate_prototypes,
-            ::environment::vegetation::calculate_prototypes,
-        ] {
-            let new_prototypes = prototype_fn(self, &result)?;
+        for prototype_fn in &PLANNED_STAGES {
+            for prototype in prototype_fn(self, &result)? {
+                result.add_prototype(prototype);
+            }
+        }
 
-            for (id, prototype) in new_prototypes
-                .into_iter()
-                .map(|prototype| (prototype.id, prototype))
-            {
-                result.grid.add_protoype(&prototype);
-                result.prototypes.insert(id, prototype);
+        Ok(result)
+    }
+
+    /// Vegetation grows around all development and wherever nothing else was built,
+    /// so it always needs the complete result of all other stages
+    fn add_vegetation(
+        &self,
+        result: &mut PlanResult,
+        previous_growth: &NaturalGrowth,
+    ) -> Result<(), AreaError> {
+        let (prototypes, natural_growth) =
+            ::environment::vegetation::calculate_prototypes(self, result, previous_growth)?;
+        for prototype in prototypes {
+            result.add_prototype(prototype);
+        }
+        result.natural_growth = natural_growth;
+        Ok(())
+    }
+
+    /// Calculates the same result as `calculate_result`, but only recalculates prototypes
+    /// in grid cells that might be affected by gestures that differ from `previous_history`
+    /// and reuses all other prototypes from `previous_result`.
+    pub fn calculate_result_based_on(
+        &self,
+        previous_history: &PlanHistory,
+        previous_result: &PlanResult,
+    ) -> Result<PlanResult, AreaError> {
+        if self.has_deleted_gestures() || previous_history.has_deleted_gestures() {
+            return self.without_deleted_gestures().calculate_result_based_on(
+                &previous_history.without_deleted_gestures(),
+                previous_result,
+            );
+        }
+
+        let changed_gestures = self
+            .changed_gestures_since(previous_history)
+            .chain(previous_history.changed_gestures_since(self))
+            .collect::<Vec<_>>();
+
+        if changed_gestures.is_empty() {
+            return Ok(previous_result.clone());
+        }
+
+        let changed_bounds = changed_gestures
+            .into_iter()
+            .map(GestureBounds::of)
+            .collect::<Option<Vec<_>>>();
+
+        // Gestures without points (like natural growth) can affect everything
+        let changed_bounds = match changed_bounds {
+            Some(changed_bounds) => changed_bounds,
+            None => return self.calculate_result_reusing_growth_of(previous_result),
+        };
+
+        let gesture_bounds = self
+            .gestures
+            .values()
+            .filter_map(|VersionedGesture(gesture, _)| GestureBounds::of(gesture))
+            .collect::<Vec<_>>();
+
+        let expand = |bounds: Vec<GestureBounds>| {
+            gesture_bounds
+                .iter()
+                .filter(|gesture_bounds| bounds.iter().any(|other| gesture_bounds.overlaps(other)))
+                .chain(bounds.iter())
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+
+        // Changed gestures directly affect prototypes of gestures overlapping them,
+        // all prototypes in the cells these lie in are recalculated
+        let mut affected_cells = ::std::collections::HashSet::new();
+        for bounds in expand(changed_bounds) {
+            let (min_x, min_y) = PrototypesSpatialGrid::coords_of(bounds.min);
+            let (max_x, max_y) = PrototypesSpatialGrid::coords_of(bounds.max);
+            for x in min_x..=max_x {
+                for y in min_y..=max_y {
+                    affected_cells.insert((x, y));
+                }
             }
         }
 
+        // Prototypes in affected cells can stem from any gesture overlapping them,
+        // which in turn needs all gestures overlapping it to be calculated completely
+        let needed_bounds = expand(expand(
+            affected_cells
+                .iter()
+                .map(|&coords| PrototypesSpatialGrid::bounds_of_cell(coords))
+                .collect(),
+        ));
+
+        let partial_history = PlanHistory {
+            gestures: self
+                .gestures
+                .pairs()
+                .filter(|(_, VersionedGesture(gesture, _))| {
+                    GestureBounds::of(gesture)
+                        .map(|bounds| needed_bounds.iter().any(|other| bounds.overlaps(other)))
+                        .unwrap_or(true)
+                })
+                .map(|(gesture_id, versioned_gesture)| (*gesture_id, versioned_gesture.clone()))
+                .collect(),
+            steps: self.steps.clone(),
+        };
+
+        let is_affected = |prototype: &Prototype| {
+            affected_cells.contains(&PrototypesSpatialGrid::coords_of(
+                prototype.representative_position,
+            ))
+        };
+
+        let mut result = previous_result.filtered(|prototype| !is_vegetation(prototype));
+
+        let previously_affected = result.filtered(&is_affected);
+        let newly_affected = partial_history
+            .calculate_planned_result()?
+            .filtered(&is_affected);
+
+        let (outdated_ids, new_ids) = previously_affected.grid.difference(&newly_affected.grid);
+
+        for outdated_id in outdated_ids {
+            result.remove_prototype(outdated_id);
+        }
+
+        for new_id in new_ids {
+            result.add_prototype(
+                newly_affected
+                    .prototypes
+                    .get(new_id)
+                    .expect("Prototype of grid should exist")
+                    .clone(),
+            );
+        }
+
+        self.add_vegetation(&mut result, &previous_result.natural_growth)?;
         Ok(result)
     }
+
+    fn has_deleted_gestures(&self) -> bool {
+        self.gestures
+            .values()
+            .any(|VersionedGesture(gesture, _)| gesture.deleted)
+    }
+
+    fn without_deleted_gestures(&self) -> PlanHistory {
+        PlanHistory {
+            gestures: self
+                .gestures
+                .pairs()
+                .filter(|(_, VersionedGesture(gesture, _))| !gesture.deleted)
+                .map(|(gesture_id, versioned_gesture)| (*gesture_id, versioned_gesture.clone()))
+                .collect(),
+            steps: self.steps.clone(),
+        }
+    }
+
+    fn changed_gestures_since<'a>(
+        &'a self,
+        other: &'a PlanHistory,
+    ) -> impl Iterator<Item = &'a Gesture> + 'a {
+        self.gestures
+            .pairs()
+            .filter_map(move |(gesture_id, VersionedGesture(gesture, step_id))| {
+                match other.gestures.get(*gesture_id) {
+                    Some(VersionedGesture(_, other_step_id)) if other_step_id == step_id => None,
+                    _ => Some(gesture),
+                }
+            })
+    }
+}
+
+/// Prototypes resulting from a gesture never lie further away than this from its points
+const GESTURE_INFLUENCE_MARGIN: N = 50.0;
+
+#[derive(Copy, Clone)]
+struct GestureBounds {
+    min: P2,
+    max: P2,
+}
+
+impl GestureBounds {
+    fn of(gesture: &Gesture) -> Option<GestureBounds> {
+        let lot_points: Vec<P2> =
+            if let GestureIntent::Building(BuildingIntent { ref lot, .. }) = gesture.intent {
+                lot.area
+                    .primitives
+                    .iter()
+                    .flat_map(|primitive| primitive.boundary.path().points.to_vec())
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+        gesture
+            .points
+            .iter()
+            .cloned()
+            .chain(lot_points)
+            .fold(None, |maybe_bounds: Option<GestureBounds>, point| {
+                Some(match maybe_bounds {
+                    Some(bounds) => GestureBounds {
+                        min: P2::new(bounds.min.x.min(point.x), bounds.min.y.min(point.y)),
+                        max: P2::new(bounds.max.x.max(point.x), bounds.max.y.max(point.y)),
+                    },
+                    None => GestureBounds {
+                        min: point,
+                        max: point,
+                    },
+                })
+            })
+            .map(|bounds| GestureBounds {
+                min: P2::new(
+                    bounds.min.x - GESTURE_INFLUENCE_MARGIN,
+                    bounds.min.y - GESTURE_INFLUENCE_MARGIN,
+                ),
+                max: P2::new(
+                    bounds.max.x + GESTURE_INFLUENCE_MARGIN,
+                    bounds.max.y + GESTURE_INFLUENCE_MARGIN,
+                ),
+            })
+    }
+
+    fn overlaps(&self, other: &GestureBounds) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
 }
 
 use self::interaction::PlanManagerUIState;
+use self::ui::PlanningUIID;
 
 #[derive(Copy, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub struct ProjectID(pub Uuid);
@@ -810,21 +1142,35 @@ pub struct PlanManager {
     master_result: PlanResult,
     projects: CHashMap<ProjectID, Project>,
     implemented_projects: CHashMap<ProjectID, Project>,
+    pending_funding: CHashMap<ProjectID, FundingNonce>,
     ui_state: PlanManagerUIState,
+    access: AccessControl,
 }
 
 mod compact_workaround;
 
 impl PlanManager {
-    pub fn spawn(id: PlanManagerID, _: &mut World) -> PlanManager {
+    pub fn spawn(id: PlanManagerID, access: &AccessControl, _: &mut World) -> PlanManager {
         PlanManager {
             id,
             master_plan: PlanHistory::new(),
             master_result: PlanResult::new(),
             projects: CHashMap::new(),
             implemented_projects: CHashMap::new(),
+            pending_funding: CHashMap::new(),
             ui_state: PlanManagerUIState::new(),
+            access: access.clone(),
+        }
+    }
+
+    /// Spectators may look at all plans, but not change them in any way.
+    /// Project IDs are visible to everybody, so every change to a project is checked.
+    fn permits(&self, token: &EditorToken, world: &mut World) -> bool {
+        let permitted = self.access.permits(token);
+        if !permitted {
+            warn(LOG_T, "Spectators can't change plans", self.id, world);
         }
+        permitted
     }
 
     pub fn get_current_version_of(&self, gesture_id: GestureID, project_id: ProjectID) -> &Gesture {
@@ -847,21 +1193,351 @@ impl PlanManager {
             .expect("Expected gesture (that point should be added to) to exist!")
     }
 
-    pub fn start_new_project(&mut self, project_id: ProjectID, _: &mut World) {
-        self.projects.insert(project_id, Project::new());
+    pub fn start_new_project(
+        &mut self,
+        project_id: ProjectID,
+        author: SessionID,
+        token: &EditorToken,
+        world: &mut World,
+    ) {
+        if !self.permits(token, world) {
+            return;
+        }
+
+        let based_on = self.master_plan.latest_step_id();
+        self.projects.insert(
+            project_id,
+            Project::new(ProjectAuthor::Session(author), based_on),
+        );
+    }
+
+    /// Gestures implemented by other sessions since the project was based on the master plan.
+    /// Changes made by the simulation itself (like new buildings) are not considered.
+    fn newer_gestures_of_others(
+        &self,
+        project: &Project,
+    ) -> Vec<(GestureID, Option<GestureBounds>)> {
+        let newer_master_steps = match self
+            .master_plan
+            .steps
+            .iter()
+            .position(|step_id| *step_id == project.based_on)
+        {
+            Some(base_idx) => &self.master_plan.steps[base_idx + 1..],
+            None => &self.master_plan.steps[..],
+        };
+
+        if newer_master_steps.is_empty() {
+            return Vec::new();
+        }
+
+        self.implemented_projects
+            .values()
+            .filter(|implemented| {
+                implemented.author != ProjectAuthor::Simulation
+                    && implemented.author != project.author
+            })
+            .flat_map(|implemented| implemented.current_history())
+            .filter(|plan| newer_master_steps.contains(&plan.step_id))
+            .flat_map(|plan| plan.gestures.pairs())
+            .map(|(gesture_id, gesture)| (*gesture_id, GestureBounds::of(gesture)))
+            .collect()
+    }
+
+    /// Gestures of a project that collide with gestures implemented by other sessions
+    /// since the project was based on the master plan, either because they are
+    /// the same gestures or because they overlap.
+    fn colliding_gestures(&self, project: &Project) -> Vec<GestureID> {
+        let newer_gestures_of_others = self.newer_gestures_of_others(project);
+
+        if newer_gestures_of_others.is_empty() {
+            return Vec::new();
+        }
+
+        project
+            .current_history()
+            .iter()
+            .flat_map(|plan| plan.gestures.pairs())
+            .filter(|(gesture_id, gesture)| {
+                let maybe_bounds = GestureBounds::of(gesture);
+                newer_gestures_of_others
+                    .iter()
+                    .any(|(other_gesture_id, maybe_other_bounds)| {
+                        other_gesture_id == *gesture_id
+                            || match (maybe_bounds, maybe_other_bounds) {
+                                (Some(bounds), Some(other_bounds)) => bounds.overlaps(other_bounds),
+                                _ => false,
+                            }
+                    })
+            })
+            .map(|(gesture_id, _)| *gesture_id)
+            .collect::<::std::collections::HashSet<_>>()
+            .into_iter()
+            .collect()
     }
 
-    pub fn implement(&mut self, project_id: ProjectID, world: &mut World) {
+    /// Rebasing re-applies the project on top of the current master plan. Gestures that
+    /// only overlap gestures of others are then recalculated together with them,
+    /// but gestures that were changed by others as well would silently overwrite those
+    /// changes, so they stay conflicts until they are resolved with `resolve_conflict`.
+    pub fn rebase_project(
+        &mut self,
+        project_id: ProjectID,
+        token: &EditorToken,
+        world: &mut World,
+    ) {
+        if !self.permits(token, world) {
+            return;
+        }
+
+        let changed_by_others = match self.projects.get(project_id) {
+            Some(project) => {
+                let project_gestures = project
+                    .current_history()
+                    .iter()
+                    .flat_map(|plan| plan.gestures.keys())
+                    .cloned()
+                    .collect::<::std::collections::HashSet<_>>();
+                self.newer_gestures_of_others(project)
+                    .into_iter()
+                    .map(|(gesture_id, _)| gesture_id)
+                    .filter(|gesture_id| project_gestures.contains(gesture_id))
+                    .collect::<Vec<_>>()
+            }
+            None => return,
+        };
+
+        let latest_step_id = self.master_plan.latest_step_id();
         let project = self
             .projects
-            .remove(project_id)
+            .get_mut(project_id)
             .expect("Project should exist");
+        project.based_on = latest_step_id;
+        for gesture_id in changed_by_others {
+            if !project.unresolved_conflicts.contains(&gesture_id) {
+                project.unresolved_conflicts.push(gesture_id);
+            }
+        }
+        let unresolved_conflicts = project.unresolved_conflicts.clone();
+
+        self.ui_state.invalidate(project_id);
+
+        PlanningUIID::global_broadcast(world).on_project_conflicts(
+            project_id,
+            unresolved_conflicts,
+            false,
+            world,
+        );
+    }
+
+    /// Resolves a gesture that was changed both in the project and by others,
+    /// either by keeping the version of the project, or by adding a step to the
+    /// project that brings back the current version of the master plan.
+    pub fn resolve_conflict(
+        &mut self,
+        project_id: ProjectID,
+        gesture_id: GestureID,
+        keep_own: bool,
+        token: &EditorToken,
+        world: &mut World,
+    ) {
+        if !self.permits(token, world) {
+            return;
+        }
+
+        let unresolved_conflicts = match self.projects.get_mut(project_id) {
+            Some(project) => {
+                project.unresolved_conflicts = project
+                    .unresolved_conflicts
+                    .iter()
+                    .filter(|unresolved_id| **unresolved_id != gesture_id)
+                    .cloned()
+                    .collect();
+
+                if !keep_own {
+                    if let Some(VersionedGesture(master_version, _)) =
+                        self.master_plan.gestures.get(gesture_id)
+                    {
+                        project.set_ongoing_step(Plan::from_gestures(Some((
+                            gesture_id,
+                            master_version.clone(),
+                        ))));
+                        project.start_new_step();
+                    }
+                }
+
+                project.unresolved_conflicts.clone()
+            }
+            None => return,
+        };
+
+        self.ui_state.invalidate(project_id);
+
+        PlanningUIID::global_broadcast(world).on_project_conflicts(
+            project_id,
+            unresolved_conflicts,
+            false,
+            world,
+        );
+    }
+
+    /// Returns false and reports the conflicts if the project can't be implemented as is
+    fn check_conflicts(&self, project_id: ProjectID, world: &mut World) -> bool {
+        let (colliding_gestures, can_rebase) = {
+            let project = self.projects.get(project_id).expect("Project should exist");
+            let colliding_gestures = self.colliding_gestures(project);
+            if colliding_gestures.is_empty() {
+                (project.unresolved_conflicts.to_vec(), false)
+            } else {
+                (colliding_gestures, true)
+            }
+        };
+
+        if colliding_gestures.is_empty() {
+            return true;
+        }
+
+        warn(
+            LOG_T,
+            if can_rebase {
+                format!(
+                    "Project {:?} conflicts with {} gestures implemented since it was started",
+                    project_id,
+                    colliding_gestures.len()
+                )
+            } else {
+                format!(
+                    "Project {:?} has {} unresolved conflicts",
+                    project_id,
+                    colliding_gestures.len()
+                )
+            },
+            self.id,
+            world,
+        );
+        PlanningUIID::global_broadcast(world).on_project_conflicts(
+            project_id,
+            colliding_gestures.into(),
+            can_rebase,
+            world,
+        );
+        false
+    }
+
+    pub fn implement(&mut self, project_id: ProjectID, token: &EditorToken, world: &mut World) {
+        if !self.permits(token, world) {
+            return;
+        }
 
-        self.master_plan = project.apply_to(&self.master_plan);
+        if !self.check_conflicts(project_id, world) {
+            return;
+        }
 
-        match self.master_plan.calculate_result() {
+        let maybe_result = self
+            .projects
+            .get(project_id)
+            .expect("Project should exist")
+            .apply_to(&self.master_plan)
+            .calculate_result_reusing_growth_of(&self.master_result);
+
+        match maybe_result {
             Ok(result) => {
                 let (actions, new_prototypes) = self.master_result.actions_to(&result);
+                let cost = construction_cost(&actions, &new_prototypes);
+                self.request_funds(project_id, cost, world);
+            }
+            Err(err) => self.report_implement_error(err, world),
+        }
+    }
+
+    /// Only the treasury learns the nonce, so only its replies are accepted.
+    /// A newer request for the same project makes replies to older ones void.
+    fn request_funds(&mut self, project_id: ProjectID, cost: ResourceAmount, world: &mut World) {
+        let nonce = thread_rng().next_u64();
+        self.pending_funding.insert(project_id, nonce);
+        TreasuryID::global_first(world).request_funds(project_id, cost, nonce, self.id, world);
+    }
+
+    fn take_pending_funding(&mut self, project_id: ProjectID, nonce: FundingNonce) -> bool {
+        if self.pending_funding.get(project_id) == Some(&nonce) {
+            self.pending_funding.remove(project_id);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn funds_granted(
+        &mut self,
+        project_id: ProjectID,
+        cost: ResourceAmount,
+        nonce: FundingNonce,
+        world: &mut World,
+    ) {
+        if !self.take_pending_funding(project_id, nonce) {
+            // releases the funds if they were really reserved for an older request
+            TreasuryID::global_first(world).settle_funds(nonce, 0.0, world);
+        } else if !self.projects.contains_key(project_id) {
+            // was already implemented in the meantime
+            TreasuryID::global_first(world).settle_funds(nonce, 0.0, world);
+        } else if !self.check_conflicts(project_id, world) {
+            // other projects were implemented while waiting for the funds
+            TreasuryID::global_first(world).settle_funds(nonce, 0.0, world);
+        } else {
+            self.implement_without_funding(project_id, Some((nonce, cost)), world);
+        }
+    }
+
+    pub fn funds_denied(&mut self, project_id: ProjectID, nonce: FundingNonce, world: &mut World) {
+        if !self.take_pending_funding(project_id, nonce) {
+            return;
+        }
+
+        info(
+            LOG_T,
+            format!(
+                "Project {:?} rejected because the city can't afford it",
+                project_id
+            ),
+            self.id,
+            world,
+        );
+    }
+
+    /// If the project was paid for, charges only what it actually ended up constructing.
+    /// If it was edited to cost more since, the funds are requested again instead.
+    fn implement_without_funding(
+        &mut self,
+        project_id: ProjectID,
+        funding: Option<(FundingNonce, ResourceAmount)>,
+        world: &mut World,
+    ) {
+        let new_master_plan = self
+            .projects
+            .get(project_id)
+            .expect("Project should exist")
+            .apply_to(&self.master_plan);
+
+        match new_master_plan.calculate_result_reusing_growth_of(&self.master_result) {
+            Ok(result) => {
+                let (actions, new_prototypes) = self.master_result.actions_to(&result);
+                if let Some((nonce, paid)) = funding {
+                    let actual_cost = construction_cost(&actions, &new_prototypes);
+                    if actual_cost > paid {
+                        TreasuryID::global_first(world).settle_funds(nonce, 0.0, world);
+                        self.request_funds(project_id, actual_cost, world);
+                        return;
+                    }
+                    TreasuryID::global_first(world).settle_funds(nonce, actual_cost, world);
+                }
+
+                let project = self
+                    .projects
+                    .remove(project_id)
+                    .expect("Project should exist");
+                self.ui_state.forget(project_id);
+                self.master_plan = new_master_plan;
+
                 ConstructionID::global_first(world).implement(actions, new_prototypes, world);
                 self.implemented_projects.insert(project_id, project);
                 self.master_result = result;
@@ -869,17 +1545,29 @@ impl PlanManager {
                 self.ui_state.invalidate_all();
             }
             Err(err) => {
-                let err_str = match err {
-                    ::descartes::AreaError::LeftOver(string) => {
-                        format!("Implement Plan Error: {}", string)
-                    }
-                    _ => format!("Implement Plan Error: {:?}", err),
-                };
-                error(LOG_T, err_str, self.id, world);
+                if let Some((nonce, _)) = funding {
+                    TreasuryID::global_first(world).settle_funds(nonce, 0.0, world);
+                }
+                // the project is gone even if it fails, so are its previews
+                self.projects.remove(project_id);
+                self.ui_state.forget(project_id);
+                self.master_plan = new_master_plan;
+
+                self.report_implement_error(err, world)
             }
         }
     }
 
+    fn report_implement_error(&self, err: AreaError, world: &mut World) {
+        let err_str = match err {
+            ::descartes::AreaError::LeftOver(string) => {
+                format!("Implement Plan Error: {}", string)
+            }
+            _ => format!("Implement Plan Error: {:?}", err),
+        };
+        error(LOG_T, err_str, self.id, world);
+    }
+
     pub fn implement_artificial_project(
         &mut self,
         project: &Project,
@@ -890,9 +1578,16 @@ impl PlanManager {
             .iter()
             .all(|prototype_id| self.master_result.prototypes.contains_key(*prototype_id))
         {
+            // artificial projects are the city developing on its own, not paid by the city
             let project_id = ProjectID::new();
-            self.projects.insert(project_id, project.clone());
-            self.implement(project_id, world);
+            self.projects.insert(
+                project_id,
+                Project {
+                    based_on: self.master_plan.latest_step_id(),
+                    ..project.clone()
+                },
+            );
+            self.implement_without_funding(project_id, None, world);
         } else {
             info(
                 LOG_T,
@@ -908,12 +1603,62 @@ pub fn setup(system: &mut ActorSystem) {
     system.register::<PlanManager>();
     auto_setup(system);
     interaction::auto_setup(system);
+    exchange::auto_setup(system);
+    timeline::auto_setup(system);
     ui::auto_setup(system);
 }
 
-pub fn spawn(world: &mut World) -> PlanManagerID {
-    PlanManagerID::spawn(world)
+pub fn spawn(world: &mut World, access: &AccessControl) -> PlanManagerID {
+    PlanManagerID::spawn(access.clone(), world)
 }
 
 pub mod kay_auto;
 pub use self::kay_auto::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn road(points: &[(N, N)]) -> Gesture {
+        Gesture::new(
+            points.iter().map(|&(x, y)| P2::new(x, y)).collect(),
+            GestureIntent::Road(RoadIntent::new(1, 1)),
+        )
+    }
+
+    fn sorted_prototype_ids(result: &PlanResult) -> Vec<PrototypeID> {
+        let mut ids = result.prototypes.keys().cloned().collect::<Vec<_>>();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn incremental_result_equals_full_result() {
+        let crossing_id = GestureID::new();
+        let previous_history = PlanHistory::new().and_then(&[Plan::from_gestures(vec![
+            (GestureID::new(), road(&[(-300.0, 20.0), (300.0, 20.0)])),
+            (crossing_id, road(&[(-40.0, -250.0), (-40.0, 250.0)])),
+            (GestureID::new(), road(&[(150.0, -250.0), (150.0, 250.0)])),
+        ])]);
+        let previous_result = previous_history.calculate_result().unwrap();
+
+        // moves a road across the cell border at the origin and adds a far away one
+        let history = previous_history.and_then(&[Plan::from_gestures(vec![
+            (crossing_id, road(&[(30.0, -250.0), (30.0, 250.0)])),
+            (
+                GestureID::new(),
+                road(&[(-900.0, -900.0), (-600.0, -900.0)]),
+            ),
+        ])]);
+
+        let full_result = history.calculate_result().unwrap();
+        let incremental_result = history
+            .calculate_result_based_on(&previous_history, &previous_result)
+            .unwrap();
+
+        assert_eq!(
+            sorted_prototype_ids(&incremental_result),
+            sorted_prototype_ids(&full_result)
+        );
+    }
+}
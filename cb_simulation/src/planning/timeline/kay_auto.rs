@@ -0,0 +1,43 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+
+
+impl PlanManagerID {
+    pub fn get_implemented_projects(self, ui: PlanningUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_get_implemented_projects(ui));
+    }
+    
+    pub fn revert_implemented_project(self, implemented_project_id: ProjectID, revert_project_id: ProjectID, author: SessionID, token: EditorToken, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_revert_implemented_project(implemented_project_id, revert_project_id, author, token));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_get_implemented_projects(pub PlanningUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_revert_implemented_project(pub ProjectID, pub ProjectID, pub SessionID, pub EditorToken);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_get_implemented_projects(ui), instance, world| {
+            instance.get_implemented_projects(ui, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_revert_implemented_project(implemented_project_id, revert_project_id, author, ref token), instance, world| {
+            instance.revert_implemented_project(implemented_project_id, revert_project_id, author, token, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,219 @@
+use kay::World;
+use std::collections::{HashMap, HashSet};
+use planning::{PlanManager, ProjectID, Project, ProjectAuthor, SessionID, Plan, Gesture,
+GestureID, StepID};
+use planning::ui::PlanningUIID;
+use access::EditorToken;
+
+use log::{info, warn};
+const LOG_T: &str = "Planning Timeline";
+
+#[derive(Copy, Clone, Serialize, Deserialize, Debug)]
+pub struct ImplementedProjectInfo {
+    pub project_id: ProjectID,
+    pub author: ProjectAuthor,
+    pub n_gestures: u32,
+}
+
+impl PlanManager {
+    fn master_step_index(&self, step_id: StepID) -> Option<usize> {
+        self.master_plan
+            .steps
+            .iter()
+            .position(|step| *step == step_id)
+    }
+
+    /// Where the (last step of the) project ended up in the master plan history
+    fn implementation_index(&self, project: &Project) -> Option<usize> {
+        project
+            .current_history()
+            .iter()
+            .filter_map(|plan| self.master_step_index(plan.step_id))
+            .max()
+    }
+
+    /// All implemented projects planned by people, oldest first.
+    /// Projects of the city developing on its own are left out.
+    pub fn get_implemented_projects(&mut self, ui: PlanningUIID, world: &mut World) {
+        let mut projects_with_index = self
+            .implemented_projects
+            .pairs()
+            .filter(|(_, project)| project.author != ProjectAuthor::Simulation)
+            .filter_map(|(project_id, project)| {
+                self.implementation_index(project).map(|index| {
+                    let gesture_ids = project
+                        .current_history()
+                        .iter()
+                        .flat_map(|plan| plan.gestures.keys())
+                        .collect::<HashSet<_>>();
+
+                    (
+                        index,
+                        ImplementedProjectInfo {
+                            project_id: *project_id,
+                            author: project.author,
+                            n_gestures: gesture_ids.len() as u32,
+                        },
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+
+        projects_with_index.sort_by_key(|(index, _)| *index);
+
+        ui.on_implemented_projects(
+            projects_with_index
+                .into_iter()
+                .map(|(_, info)| info)
+                .collect(),
+            world,
+        );
+    }
+
+    /// Starts a new project that undoes all gestures of an implemented project,
+    /// restoring the versions they had before or deleting them if they were new.
+    /// Gestures that were changed again by later projects are left as they are.
+    /// Like for `start_new_project`, the editor token is what is checked, `author`
+    /// only attributes the new project to the session of an editor.
+    pub fn revert_implemented_project(
+        &mut self,
+        implemented_project_id: ProjectID,
+        revert_project_id: ProjectID,
+        author: SessionID,
+        token: &EditorToken,
+        world: &mut World,
+    ) {
+        if !self.permits(token, world) {
+            return;
+        }
+
+        if self.projects.contains_key(revert_project_id)
+            || self.implemented_projects.contains_key(revert_project_id)
+        {
+            warn(
+                LOG_T,
+                format!(
+                    "Can't revert into project {:?}, which already exists",
+                    revert_project_id
+                ),
+                self.id,
+                world,
+            );
+            return;
+        }
+
+        let inverse_gestures = {
+            let project_to_revert = match self.implemented_projects.get(implemented_project_id) {
+                Some(project) => project,
+                None => {
+                    warn(
+                        LOG_T,
+                        format!("Can't revert unknown project {:?}", implemented_project_id),
+                        self.id,
+                        world,
+                    );
+                    return;
+                }
+            };
+
+            let touched_gestures = project_to_revert
+                .current_history()
+                .iter()
+                .flat_map(|plan| plan.gestures.keys().cloned())
+                .collect::<HashSet<GestureID>>();
+
+            // (master step index, version, is from the project to revert) of all touched gestures
+            let mut versions = HashMap::<GestureID, Vec<(usize, &Gesture, bool)>>::new();
+
+            for (project_id, project) in self.implemented_projects.pairs() {
+                for plan in project.current_history() {
+                    if let Some(index) = self.master_step_index(plan.step_id) {
+                        for (gesture_id, gesture) in plan.gestures.pairs() {
+                            if touched_gestures.contains(gesture_id) {
+                                versions.entry(*gesture_id).or_insert_with(Vec::new).push((
+                                    index,
+                                    gesture,
+                                    *project_id == implemented_project_id,
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+
+            let mut n_changed_later = 0;
+
+            let inverse_gestures = versions
+                .into_iter()
+                .filter_map(|(gesture_id, mut gesture_versions)| {
+                    gesture_versions.sort_by_key(|(index, _, _)| *index);
+
+                    let &(_, latest_version, latest_is_from_reverted) = gesture_versions
+                        .last()
+                        .expect("Should have at least one version");
+
+                    if !latest_is_from_reverted {
+                        n_changed_later += 1;
+                        return None;
+                    }
+
+                    let first_reverted_index = gesture_versions
+                        .iter()
+                        .find(|(_, _, is_from_reverted)| *is_from_reverted)
+                        .map(|(index, _, _)| *index)
+                        .expect("Should have a version from the reverted project");
+
+                    let maybe_version_before = gesture_versions
+                        .iter()
+                        .filter(|(index, _, _)| *index < first_reverted_index)
+                        .last()
+                        .map(|(_, gesture, _)| (*gesture).clone());
+
+                    Some((
+                        gesture_id,
+                        maybe_version_before.unwrap_or_else(|| Gesture {
+                            deleted: true,
+                            ..latest_version.clone()
+                        }),
+                    ))
+                })
+                .collect::<Vec<_>>();
+
+            if n_changed_later > 0 {
+                warn(
+                    LOG_T,
+                    format!(
+                        "{} gestures were changed again since project {:?} and won't be reverted",
+                        n_changed_later, implemented_project_id
+                    ),
+                    self.id,
+                    world,
+                );
+            }
+
+            inverse_gestures
+        };
+
+        let n_inverse_gestures = inverse_gestures.len();
+        let mut revert_project = Project::new(
+            ProjectAuthor::Session(author),
+            self.master_plan.latest_step_id(),
+        );
+        revert_project.set_ongoing_step(Plan::from_gestures(inverse_gestures));
+        revert_project.start_new_step();
+        self.projects.insert(revert_project_id, revert_project);
+
+        info(
+            LOG_T,
+            format!(
+                "Started reverting {} gestures of project {:?}",
+                n_inverse_gestures, implemented_project_id
+            ),
+            self.id,
+            world,
+        );
+    }
+}
+
+pub mod kay_auto;
+pub use self::kay_auto::*;
@@ -38,11 +38,26 @@ impl PlanningUIID {
     pub fn on_project_preview_update(self, project_id: ProjectID, effective_history: PlanHistory, result_update: PlanResultUpdate, new_actions: ActionGroups, world: &mut World) {
         world.send(self.as_raw(), MSG_PlanningUI_on_project_preview_update(project_id, effective_history, result_update, new_actions));
     }
+    
+    pub fn on_project_conflicts(self, project_id: ProjectID, colliding_gestures: CVec < GestureID >, can_rebase: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanningUI_on_project_conflicts(project_id, colliding_gestures, can_rebase));
+    }
+    
+    pub fn on_plan_exported(self, project_id: COption < ProjectID >, contents: CString, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanningUI_on_plan_exported(project_id, contents));
+    }
+    
+    pub fn on_implemented_projects(self, projects: CVec < ImplementedProjectInfo >, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanningUI_on_implemented_projects(projects));
+    }
 
     pub fn register_trait(system: &mut ActorSystem) {
         system.register_trait::<PlanningUIRepresentative>();
         system.register_trait_message::<MSG_PlanningUI_on_plans_update>();
         system.register_trait_message::<MSG_PlanningUI_on_project_preview_update>();
+        system.register_trait_message::<MSG_PlanningUI_on_project_conflicts>();
+        system.register_trait_message::<MSG_PlanningUI_on_plan_exported>();
+        system.register_trait_message::<MSG_PlanningUI_on_implemented_projects>();
     }
 
     pub fn register_implementor<A: Actor + PlanningUI>(system: &mut ActorSystem) {
@@ -58,6 +73,24 @@ impl PlanningUIID {
                 instance.on_project_preview_update(project_id, effective_history, result_update, new_actions, world); Fate::Live
             }, false
         );
+        
+        system.add_handler::<A, _, _>(
+            |&MSG_PlanningUI_on_project_conflicts(project_id, ref colliding_gestures, can_rebase), instance, world| {
+                instance.on_project_conflicts(project_id, colliding_gestures, can_rebase, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<A, _, _>(
+            |&MSG_PlanningUI_on_plan_exported(ref project_id, ref contents), instance, world| {
+                instance.on_plan_exported(project_id, contents, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<A, _, _>(
+            |&MSG_PlanningUI_on_implemented_projects(ref projects), instance, world| {
+                instance.on_implemented_projects(projects, world); Fate::Live
+            }, false
+        );
     }
 }
 
@@ -65,6 +98,12 @@ impl PlanningUIID {
 struct MSG_PlanningUI_on_plans_update(pub PlanHistoryUpdate, pub CHashMap < ProjectID , ProjectUpdate >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_PlanningUI_on_project_preview_update(pub ProjectID, pub PlanHistory, pub PlanResultUpdate, pub ActionGroups);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanningUI_on_project_conflicts(pub ProjectID, pub CVec < GestureID >, pub bool);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanningUI_on_plan_exported(pub COption < ProjectID >, pub CString);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanningUI_on_implemented_projects(pub CVec < ImplementedProjectInfo >);
 
 
 
@@ -1,7 +1,9 @@
 use kay::World;
 use compact::CHashMap;
+use compact::{CVec, COption, CString};
 use super::{PlanHistory, PlanHistoryUpdate, ProjectID, ProjectUpdate,
-PlanResultUpdate, ActionGroups};
+PlanResultUpdate, ActionGroups, GestureID};
+use super::timeline::ImplementedProjectInfo;
 
 pub trait PlanningUI {
     fn on_plans_update(
@@ -19,6 +21,30 @@ pub trait PlanningUI {
         new_actions: &ActionGroups,
         _world: &mut World,
     );
+
+    /// An empty list of colliding gestures means that the project has no conflicts (anymore).
+    /// If the project can't be rebased, the colliding gestures were also changed by others
+    /// and each of them has to be resolved.
+    fn on_project_conflicts(
+        &mut self,
+        project_id: ProjectID,
+        colliding_gestures: &CVec<GestureID>,
+        can_rebase: bool,
+        world: &mut World,
+    );
+
+    fn on_plan_exported(
+        &mut self,
+        project_id: &COption<ProjectID>,
+        contents: &CString,
+        world: &mut World,
+    );
+
+    fn on_implemented_projects(
+        &mut self,
+        projects: &CVec<ImplementedProjectInfo>,
+        world: &mut World,
+    );
 }
 
 pub mod kay_auto;
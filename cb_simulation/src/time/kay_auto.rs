@@ -31,8 +31,8 @@ impl TypedID for TemporalID {
 impl<A: Actor + Temporal> TraitIDFrom<A> for TemporalID {}
 
 impl TemporalID {
-    pub fn tick(self, dt: f32, current_instant: Instant, world: &mut World) {
-        world.send(self.as_raw(), MSG_Temporal_tick(dt, current_instant));
+    pub fn tick(self, dt: f32, current_instant: Instant, parameters: SimulationParameters, world: &mut World) {
+        world.send(self.as_raw(), MSG_Temporal_tick(dt, current_instant, parameters));
     }
 
     pub fn register_trait(system: &mut ActorSystem) {
@@ -43,15 +43,15 @@ impl TemporalID {
     pub fn register_implementor<A: Actor + Temporal>(system: &mut ActorSystem) {
         system.register_implementor::<A, TemporalRepresentative>();
         system.add_handler::<A, _, _>(
-            |&MSG_Temporal_tick(dt, current_instant), instance, world| {
-                instance.tick(dt, current_instant, world); Fate::Live
+            |&MSG_Temporal_tick(dt, current_instant, ref parameters), instance, world| {
+                instance.tick(dt, current_instant, parameters, world); Fate::Live
             }, false
         );
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Temporal_tick(pub f32, pub Instant);
+struct MSG_Temporal_tick(pub f32, pub Instant, pub SimulationParameters);
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
 pub struct SleeperID {
     _raw_id: RawID
@@ -129,10 +129,10 @@ impl TypedID for TimeID {
 }
 
 impl TimeID {
-    pub fn spawn(world: &mut World) -> Self {
+    pub fn spawn(parameters: SimulationParameters, access: AccessControl, fast_forwarding: External < Arc < AtomicBool > >, world: &mut World) -> Self {
         let id = TimeID::from_raw(world.allocate_instance_id::<Time>());
         let swarm = world.local_broadcast::<Time>();
-        world.send(swarm, MSG_Time_spawn(id, ));
+        world.send(swarm, MSG_Time_spawn(id, parameters, access, fast_forwarding));
         id
     }
     
@@ -145,8 +145,8 @@ impl TimeID {
     }
 }
 
-#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Time_spawn(pub TimeID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_spawn(pub TimeID, pub SimulationParameters, pub AccessControl, pub External < Arc < AtomicBool > >);
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Time_progress();
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
@@ -160,8 +160,8 @@ pub fn auto_setup(system: &mut ActorSystem) {
     SleeperID::register_trait(system);
     
     system.add_spawner::<Time, _, _>(
-        |&MSG_Time_spawn(id, ), world| {
-            Time::spawn(id, world)
+        |&MSG_Time_spawn(id, parameters, ref access, ref fast_forwarding), world| {
+            Time::spawn(id, parameters, access, fast_forwarding, world)
         }, false
     );
     
@@ -1,59 +1,135 @@
-use kay::{ActorSystem, World, TypedID};
+use kay::{ActorSystem, World, TypedID, External};
 use compact::CVec;
+use parameters::SimulationParameters;
+use access::AccessControl;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 mod units;
 pub mod ui;
 
-pub use self::units::{Instant, Ticks, Duration, TICKS_PER_SIM_MINUTE, TICKS_PER_SIM_SECOND,
-TimeOfDay, TimeOfDayRange};
+pub use self::units::{Instant, Ticks, Duration, TimeOfDay, TimeOfDayRange,
+DEFAULT_TICKS_PER_SIM_SECOND, ticks_per_sim_second, ticks_per_sim_minute,
+set_ticks_per_sim_second};
 
+/// Simulation parameters are passed along with every tick, instead of copied into every actor
 pub trait Temporal {
-    fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World);
+    fn tick(
+        &mut self,
+        dt: f32,
+        current_instant: Instant,
+        parameters: &SimulationParameters,
+        world: &mut World,
+    );
 }
 
 pub trait Sleeper {
     fn wake(&mut self, current_instant: Instant, world: &mut World);
 }
 
+/// Upper bound for ticks per frame when stepping or running until an instant,
+/// so the server stays responsive to clients while catching up
+const MAX_TICKS_PER_FRAME: u32 = 32;
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum TimeControl {
+    /// Advances by `speed` ticks every frame
+    Running,
+    Paused,
+    /// Advances by the remaining ticks, then pauses
+    Stepping(u32),
+    /// Advances as fast as possible until the instant is reached, then pauses
+    RunningUntil(Instant),
+}
+
 #[derive(Compact, Clone)]
 pub struct Time {
     id: TimeID,
     current_instant: Instant,
     sleepers: CVec<(Instant, SleeperID)>,
+    parameters: SimulationParameters,
     speed: u16,
+    control: TimeControl,
+    access: AccessControl,
+    /// Tells the server loop not to wait for the next frame while running until an instant
+    fast_forwarding: External<Arc<AtomicBool>>,
 }
 
 impl Time {
-    pub fn spawn(id: TimeID, _: &mut World) -> Time {
+    pub fn spawn(
+        id: TimeID,
+        parameters: SimulationParameters,
+        access: &AccessControl,
+        fast_forwarding: &External<Arc<AtomicBool>>,
+        _: &mut World,
+    ) -> Time {
         Time {
             id,
             current_instant: Instant::new(0),
             sleepers: CVec::new(),
-            speed: 1,
+            parameters,
+            speed: parameters.initial_speed,
+            control: TimeControl::Running,
+            access: access.clone(),
+            fast_forwarding: fast_forwarding.steal(),
         }
     }
 
     pub fn progress(&mut self, world: &mut World) {
-        for _ in 0..self.speed {
-            TemporalID::global_broadcast(world).tick(
-                1.0 / (TICKS_PER_SIM_SECOND as f32),
-                self.current_instant,
-                world,
-            );
-            while self
-                .sleepers
-                .last()
-                .map(|&(end, _)| end < self.current_instant)
-                .unwrap_or(false)
-            {
-                let (_, sleeper) = self
-                    .sleepers
-                    .pop()
-                    .expect("just checked that there are sleepers");
-                sleeper.wake(self.current_instant, world);
+        let n_ticks = match self.control {
+            TimeControl::Running => u32::from(self.speed),
+            TimeControl::Paused => 0,
+            TimeControl::Stepping(remaining_ticks) => remaining_ticks.min(MAX_TICKS_PER_FRAME),
+            TimeControl::RunningUntil(until) => {
+                (until.ticks().saturating_sub(self.current_instant.ticks()) as u32)
+                    .min(MAX_TICKS_PER_FRAME)
+            }
+        };
+
+        for _ in 0..n_ticks {
+            self.tick_once(world);
+        }
+
+        self.control = match self.control {
+            TimeControl::Stepping(remaining_ticks) if remaining_ticks > n_ticks => {
+                TimeControl::Stepping(remaining_ticks - n_ticks)
+            }
+            TimeControl::RunningUntil(until) if self.current_instant < until => {
+                TimeControl::RunningUntil(until)
             }
-            self.current_instant += Ticks(1);
+            TimeControl::Stepping(_) | TimeControl::RunningUntil(_) => TimeControl::Paused,
+            control => control,
+        };
+
+        self.fast_forwarding.store(
+            match self.control {
+                TimeControl::RunningUntil(_) => true,
+                _ => false,
+            },
+            Ordering::SeqCst,
+        );
+    }
+
+    fn tick_once(&mut self, world: &mut World) {
+        TemporalID::global_broadcast(world).tick(
+            1.0 / (ticks_per_sim_second() as f32),
+            self.current_instant,
+            self.parameters,
+            world,
+        );
+        while self
+            .sleepers
+            .last()
+            .map(|&(end, _)| end < self.current_instant)
+            .unwrap_or(false)
+        {
+            let (_, sleeper) = self
+                .sleepers
+                .pop()
+                .expect("just checked that there are sleepers");
+            sleeper.wake(self.current_instant, world);
         }
+        self.current_instant += Ticks(1);
     }
 
     pub fn wake_up_in(&mut self, remaining_ticks: Ticks, sleeper_id: SleeperID, _: &mut World) {
@@ -74,8 +150,18 @@ pub fn setup(system: &mut ActorSystem) {
     ui::auto_setup(system);
 }
 
-pub fn spawn(world: &mut World) -> TimeID {
-    TimeID::spawn(world)
+pub fn spawn(
+    world: &mut World,
+    parameters: &SimulationParameters,
+    access: &AccessControl,
+    fast_forwarding: Arc<AtomicBool>,
+) -> TimeID {
+    TimeID::spawn(
+        *parameters,
+        access.clone(),
+        External::new(fast_forwarding),
+        world,
+    )
 }
 
 mod kay_auto;
@@ -31,8 +31,8 @@ impl TypedID for TimeUIID {
 impl<A: Actor + TimeUI> TraitIDFrom<A> for TimeUIID {}
 
 impl TimeUIID {
-    pub fn on_time_info(self, current_instant: :: time :: Instant, speed: u16, world: &mut World) {
-        world.send(self.as_raw(), MSG_TimeUI_on_time_info(current_instant, speed));
+    pub fn on_time_info(self, current_instant: :: time :: Instant, speed: u16, control: :: time :: TimeControl, world: &mut World) {
+        world.send(self.as_raw(), MSG_TimeUI_on_time_info(current_instant, speed, control));
     }
 
     pub fn register_trait(system: &mut ActorSystem) {
@@ -43,15 +43,15 @@ impl TimeUIID {
     pub fn register_implementor<A: Actor + TimeUI>(system: &mut ActorSystem) {
         system.register_implementor::<A, TimeUIRepresentative>();
         system.add_handler::<A, _, _>(
-            |&MSG_TimeUI_on_time_info(current_instant, speed), instance, world| {
-                instance.on_time_info(current_instant, speed, world); Fate::Live
+            |&MSG_TimeUI_on_time_info(current_instant, speed, control), instance, world| {
+                instance.on_time_info(current_instant, speed, control, world); Fate::Live
             }, false
         );
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_TimeUI_on_time_info(pub :: time :: Instant, pub u16);
+struct MSG_TimeUI_on_time_info(pub :: time :: Instant, pub u16, pub :: time :: TimeControl);
 
 
 
@@ -60,15 +60,45 @@ impl TimeID {
         world.send(self.as_raw(), MSG_Time_get_info(requester));
     }
     
-    pub fn set_speed(self, speed: u16, world: &mut World) {
-        world.send(self.as_raw(), MSG_Time_set_speed(speed));
+    pub fn set_speed(self, speed: u16, token: EditorToken, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_set_speed(speed, token));
+    }
+    
+    pub fn pause(self, token: EditorToken, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_pause(token));
+    }
+    
+    pub fn resume(self, token: EditorToken, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_resume(token));
+    }
+    
+    pub fn step(self, n_ticks: u32, token: EditorToken, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_step(n_ticks, token));
+    }
+    
+    pub fn run_until(self, instant: Instant, token: EditorToken, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_run_until(instant, token));
+    }
+    
+    pub fn run_until_time_of_day(self, time_of_day: TimeOfDay, token: EditorToken, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_run_until_time_of_day(time_of_day, token));
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Time_get_info(pub TimeUIID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Time_set_speed(pub u16);
+struct MSG_Time_set_speed(pub u16, pub EditorToken);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_pause(pub EditorToken);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_resume(pub EditorToken);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_step(pub u32, pub EditorToken);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_run_until(pub Instant, pub EditorToken);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_run_until_time_of_day(pub TimeOfDay, pub EditorToken);
 
 
 #[allow(unused_variables)]
@@ -83,8 +113,38 @@ pub fn auto_setup(system: &mut ActorSystem) {
     );
     
     system.add_handler::<Time, _, _>(
-        |&MSG_Time_set_speed(speed), instance, world| {
-            instance.set_speed(speed, world); Fate::Live
+        |&MSG_Time_set_speed(speed, ref token), instance, world| {
+            instance.set_speed(speed, token, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_pause(ref token), instance, world| {
+            instance.pause(token, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_resume(ref token), instance, world| {
+            instance.resume(token, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_step(n_ticks, ref token), instance, world| {
+            instance.step(n_ticks, token, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_run_until(instant, ref token), instance, world| {
+            instance.run_until(instant, token, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_run_until_time_of_day(time_of_day, ref token), instance, world| {
+            instance.run_until_time_of_day(time_of_day, token, world); Fate::Live
         }, false
     );
 }
\ No newline at end of file
@@ -1,17 +1,72 @@
 use kay::World;
-use super::{Time, TimeID};
+use super::{Time, TimeID, TimeControl, Instant, TimeOfDay};
+use access::EditorToken;
+use log::warn;
+const LOG_T: &str = "Time";
 
 pub trait TimeUI {
-    fn on_time_info(&mut self, current_instant: ::time::Instant, speed: u16, _world: &mut World);
+    fn on_time_info(
+        &mut self,
+        current_instant: ::time::Instant,
+        speed: u16,
+        control: ::time::TimeControl,
+        _world: &mut World,
+    );
 }
 
 impl Time {
     pub fn get_info(&mut self, requester: TimeUIID, world: &mut World) {
-        requester.on_time_info(self.current_instant, self.speed, world);
+        requester.on_time_info(self.current_instant, self.speed, self.control, world);
     }
 
-    pub fn set_speed(&mut self, speed: u16, _world: &mut World) {
-        self.speed = speed as u16;
+    fn permits(&self, token: &EditorToken, world: &mut World) -> bool {
+        let permitted = self.access.permits(token);
+        if !permitted {
+            warn(LOG_T, "Spectators can't control time", self.id, world);
+        }
+        permitted
+    }
+
+    pub fn set_speed(&mut self, speed: u16, token: &EditorToken, world: &mut World) {
+        if self.permits(token, world) {
+            self.speed = speed as u16;
+        }
+    }
+
+    pub fn pause(&mut self, token: &EditorToken, world: &mut World) {
+        if self.permits(token, world) {
+            self.control = TimeControl::Paused;
+        }
+    }
+
+    pub fn resume(&mut self, token: &EditorToken, world: &mut World) {
+        if self.permits(token, world) {
+            self.control = TimeControl::Running;
+        }
+    }
+
+    /// Advances exactly `n_ticks` ticks, regardless of speed, and pauses afterwards
+    pub fn step(&mut self, n_ticks: u32, token: &EditorToken, world: &mut World) {
+        if self.permits(token, world) && n_ticks > 0 {
+            self.control = TimeControl::Stepping(n_ticks);
+        }
+    }
+
+    /// Advances as fast as the simulation can until `instant`, and pauses afterwards
+    pub fn run_until(&mut self, instant: Instant, token: &EditorToken, world: &mut World) {
+        if self.permits(token, world) && instant > self.current_instant {
+            self.control = TimeControl::RunningUntil(instant);
+        }
+    }
+
+    pub fn run_until_time_of_day(
+        &mut self,
+        time_of_day: TimeOfDay,
+        token: &EditorToken,
+        world: &mut World,
+    ) {
+        let instant = self.current_instant.next_at(time_of_day);
+        self.run_until(instant, token, world);
     }
 }
 
@@ -1,12 +1,29 @@
-pub const TICKS_PER_SIM_SECOND: u32 = 3;
-pub const TICKS_PER_SIM_MINUTE: u32 = 60 * TICKS_PER_SIM_SECOND;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub const DEFAULT_TICKS_PER_SIM_SECOND: u32 = 3;
+
+// only set once at startup, the server and all browsers need to agree on it
+static TICKS_PER_SIM_SECOND: AtomicUsize = AtomicUsize::new(DEFAULT_TICKS_PER_SIM_SECOND as usize);
+
+pub fn ticks_per_sim_second() -> u32 {
+    TICKS_PER_SIM_SECOND.load(Ordering::Relaxed) as u32
+}
+
+pub fn ticks_per_sim_minute() -> u32 {
+    60 * ticks_per_sim_second()
+}
+
+/// Has to be called before any instants are created or converted
+pub fn set_ticks_per_sim_second(ticks: u32) {
+    TICKS_PER_SIM_SECOND.store(ticks.max(1) as usize, Ordering::Relaxed);
+}
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Ticks(pub u32);
 
 impl From<Duration> for Ticks {
     fn from(d_secs: Duration) -> Ticks {
-        Ticks(d_secs.0 * TICKS_PER_SIM_SECOND)
+        Ticks(d_secs.0 * ticks_per_sim_second())
     }
 }
 
@@ -72,6 +89,20 @@ impl Instant {
     pub fn iticks(self) -> isize {
         self.0 as isize
     }
+
+    /// The first instant after this one at which the given time of day begins
+    pub fn next_at(self, time_of_day: TimeOfDay) -> Instant {
+        let minutes = self.ticks() / ticks_per_sim_minute() as usize;
+        let current_minute_of_day = TimeOfDay::from(self).minutes_of_day as usize;
+        let minutes_until = match (time_of_day.minutes_of_day as usize + MINUTES_PER_DAY
+            - current_minute_of_day)
+            % MINUTES_PER_DAY
+        {
+            0 => MINUTES_PER_DAY,
+            minutes_until => minutes_until,
+        };
+        Instant::new((minutes + minutes_until) * ticks_per_sim_minute() as usize)
+    }
 }
 
 impl<D: Into<Ticks>> ::std::ops::Add<D> for Instant {
@@ -145,7 +176,7 @@ impl From<Instant> for TimeOfDay {
     fn from(instant: Instant) -> TimeOfDay {
         TimeOfDay {
             minutes_of_day: ((BEGINNING_TIME_OF_DAY * 60
-                + (instant.ticks() / TICKS_PER_SIM_MINUTE as usize))
+                + (instant.ticks() / ticks_per_sim_minute() as usize))
                 % MINUTES_PER_DAY) as u16,
         }
     }
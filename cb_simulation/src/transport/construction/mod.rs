@@ -9,12 +9,13 @@ use super::lane::{Lane, LaneID, SwitchLane, SwitchLaneID};
 use super::lane::connectivity::Interaction;
 use super::microtraffic::LaneLikeID;
 
-use planning::Prototype;
+use planning::{Prototype, PrototypeKind};
 use construction::{ConstructionID, Constructable, ConstructableID};
+use economy::treasury::TreasuryID;
 use super::transport_planning::{RoadPrototype, LanePrototype, SwitchLanePrototype,
 IntersectionPrototype};
 
-use log::debug;
+use log::{debug, warn};
 const LOG_T: &str = "Transport Construction";
 
 use dimensions::{LANE_CONNECTION_TOLERANCE, MAX_SWITCHING_LANE_DISTANCE,
@@ -70,13 +71,53 @@ impl RoadPrototype {
 }
 
 impl Constructable for Lane {
-    fn morph(&mut self, _new_prototype: &Prototype, report_to: ConstructionID, world: &mut World) {
+    fn morph(&mut self, new_prototype: &Prototype, report_to: ConstructionID, world: &mut World) {
+        let current_prototype = LanePrototype(
+            self.construction.path.clone(),
+            self.microtraffic.timings().clone(),
+        );
+
+        let maybe_new_lane_prototype = match new_prototype.kind {
+            PrototypeKind::Road(RoadPrototype::Lane(ref lane_prototype)) => Some(lane_prototype),
+            PrototypeKind::Road(RoadPrototype::Intersection(IntersectionPrototype {
+                ref connecting_lanes,
+                ..
+            })) => connecting_lanes
+                .values()
+                .flat_map(|group| group.iter())
+                .find(|lane_prototype| {
+                    lane_prototype.morphable_from_with_fixed_ends(&current_prototype)
+                }),
+            _ => None,
+        };
+
+        if let Some(&LanePrototype(ref new_path, ref new_timings)) = maybe_new_lane_prototype {
+            self.morph_to(new_path, new_timings, world);
+        } else {
+            warn(
+                LOG_T,
+                "Lane can't be morphed to a prototype without a matching lane",
+                self.id,
+                world,
+            );
+        }
+
+        if self.construction.closed_for_construction {
+            self.construction.closed_for_construction = false;
+            ::transport::pathfinding::Link::on_reopened(self);
+        }
         report_to.action_done(self.id_as(), world);
     }
     fn destruct(&mut self, report_to: ConstructionID, world: &mut World) -> Fate {
         self.unbuild(report_to, world);
         Fate::Live
     }
+    fn close_for_construction(&mut self, _world: &mut World) {
+        if !self.construction.closed_for_construction {
+            self.construction.closed_for_construction = true;
+            ::transport::pathfinding::Link::on_closed(self);
+        }
+    }
 }
 
 impl Constructable for SwitchLane {
@@ -87,6 +128,9 @@ impl Constructable for SwitchLane {
         self.unbuild(report_to, world);
         Fate::Live
     }
+    fn close_for_construction(&mut self, _world: &mut World) {
+        // switching is already prevented by the closed lanes on either side
+    }
 }
 
 #[derive(Compact, Clone)]
@@ -94,6 +138,8 @@ pub struct ConstructionInfo {
     pub length: f32,
     pub path: LinePath,
     pub progress: f32,
+    /// While being rebuilt or torn down, no new cars may enter
+    pub closed_for_construction: bool,
     unbuilding_for: Option<ConstructionID>,
     disconnects_remaining: u8,
 }
@@ -104,6 +150,7 @@ impl ConstructionInfo {
             length: path.length(),
             path,
             progress: 0.0,
+            closed_for_construction: false,
             unbuilding_for: None,
             disconnects_remaining: 0,
         }
@@ -136,9 +183,73 @@ impl Lane {
             SwitchLaneID::global_broadcast(world).connect_switch_to_normal(id, path.clone(), world);
         }
         report_to.action_done(id.into(), world);
+        TreasuryID::global_first(world).lane_built(path.length(), world);
         Lane::spawn(id, path, on_intersection, timings, world)
     }
 
+    /// Changes geometry and timings in place, keeping cars, connections and routes
+    fn morph_to(&mut self, new_path: &LinePath, new_timings: &CVec<bool>, world: &mut World) {
+        let length_ratio = new_path.length() / self.construction.length;
+
+        self.microtraffic.morph(new_timings, length_ratio);
+
+        if !new_path.rough_eq_by(&self.construction.path, 0.05) {
+            super::ui::on_unbuild(self, world);
+
+            for interaction in self.connectivity.interactions.iter_mut() {
+                match *interaction {
+                    Interaction::Conflicting {
+                        ref mut start,
+                        ref mut end,
+                        ..
+                    }
+                    | Interaction::Switch {
+                        ref mut start,
+                        ref mut end,
+                        ..
+                    } => {
+                        *start *= length_ratio;
+                        *end *= length_ratio;
+                    }
+                    Interaction::Previous { .. } | Interaction::Next { .. } => {}
+                }
+            }
+
+            let treasury = TreasuryID::global_first(world);
+            treasury.lane_unbuilt(self.construction.length, world);
+            treasury.lane_built(new_path.length(), world);
+
+            let ends_moved = !new_path
+                .start()
+                .rough_eq_by(self.construction.path.start(), LANE_CONNECTION_TOLERANCE)
+                || !new_path
+                    .end()
+                    .rough_eq_by(self.construction.path.end(), LANE_CONNECTION_TOLERANCE);
+
+            self.construction.path = new_path.clone();
+            self.construction.length = new_path.length();
+            unsafe {
+                MEMOIZED_BANDS_OUTLINES
+                    .get_or_insert_with(FnvHashMap::default)
+                    .remove(&self.id_as());
+            }
+
+            // lets following lanes know about our new length. Lanes that we used to connect to
+            // at moved ends are part of resized intersections and will disconnect themselves
+            // when they are rebuilt, but we need replies from the lanes at our new ends
+            LaneID::global_broadcast(world).connect(
+                self.id,
+                new_path.start(),
+                new_path.end(),
+                new_path.length(),
+                ends_moved,
+                world,
+            );
+
+            super::ui::on_build(self, world);
+        }
+    }
+
     pub fn start_connecting_overlaps(&mut self, lanes: &CVec<LaneID>, world: &mut World) {
         for &lane_id in lanes.iter() {
             lane_id.connect_overlaps(self.id, self.construction.path.clone(), true, world);
@@ -184,14 +295,20 @@ impl Lane {
         if other_end.rough_eq_by(self.construction.path.start(), LANE_CONNECTION_TOLERANCE) {
             connected = true;
 
-            let already_a_partner =
-                self.connectivity
-                    .interactions
-                    .iter()
-                    .any(|interaction| match *interaction {
-                        Interaction::Previous { previous, .. } => previous == other_id,
-                        _ => false,
-                    });
+            let mut already_a_partner = false;
+            for interaction in self.connectivity.interactions.iter_mut() {
+                if let Interaction::Previous {
+                    previous,
+                    ref mut previous_length,
+                } = *interaction
+                {
+                    if previous == other_id {
+                        // the previous lane might have been morphed
+                        *previous_length = other_length;
+                        already_a_partner = true;
+                    }
+                }
+            }
             if !already_a_partner {
                 self.connectivity.interactions.push(Interaction::Previous {
                     previous: other_id,
@@ -418,6 +535,7 @@ impl Lane {
 impl Lane {
     fn finalize(&self, report_to: ConstructionID, world: &mut World) {
         report_to.action_done(self.id_as(), world);
+        TreasuryID::global_first(world).lane_unbuilt(self.construction.length, world);
 
         for car in &self.microtraffic.cars {
             car.trip.finish(
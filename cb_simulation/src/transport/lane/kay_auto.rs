@@ -42,12 +42,22 @@ impl LaneID {
         world.send(swarm, MSG_Lane_spawn(id, path, on_intersection, timings));
         id
     }
+    
+    pub fn report_to_heatmaps(self, collector: HeatmapsID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_report_to_heatmaps(collector));
+    }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Lane_spawn(pub LaneID, pub LinePath, pub bool, pub CVec < bool >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_report_to_heatmaps(pub HeatmapsID);
 
-
+impl Into<InspectableID> for LaneID {
+    fn into(self) -> InspectableID {
+        InspectableID::from_raw(self.as_raw())
+    }
+}
 impl Actor for SwitchLane {
     type ID = SwitchLaneID;
 
@@ -88,18 +98,29 @@ impl SwitchLaneID {
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_SwitchLane_spawn(pub SwitchLaneID, pub LinePath);
 
+impl Into<InspectableID> for SwitchLaneID {
+    fn into(self) -> InspectableID {
+        InspectableID::from_raw(self.as_raw())
+    }
+}
 
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
-    
+    InspectableID::register_implementor::<Lane>(system);
     system.add_spawner::<Lane, _, _>(
         |&MSG_Lane_spawn(id, ref path, on_intersection, ref timings), world| {
             Lane::spawn(id, path, on_intersection, timings, world)
         }, false
     );
     
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_report_to_heatmaps(collector), instance, world| {
+            instance.report_to_heatmaps(collector, world); Fate::Live
+        }, false
+    );
+    InspectableID::register_implementor::<SwitchLane>(system);
     system.add_spawner::<SwitchLane, _, _>(
         |&MSG_SwitchLane_spawn(id, ref path), world| {
             SwitchLane::spawn(id, path, world)
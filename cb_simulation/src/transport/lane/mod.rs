@@ -1,12 +1,16 @@
 use compact::{CVec};
-use kay::{ActorSystem, World};
+use kay::{ActorSystem, World, TypedID};
 use descartes::{N, LinePath};
+use heatmaps::{HeatmapsID, TrafficSample, cell_of, traffic_sample_in};
 
 use super::construction::ConstructionInfo;
 pub mod connectivity;
 use self::connectivity::{ConnectivityInfo, SwitchConnectivityInfo};
 use super::microtraffic::{Microtraffic, TransferringMicrotraffic};
 use super::pathfinding::PathfindingCore;
+use inspection::{Inspectable, InspectableID, InspectorID, StateSummary};
+
+const HEATMAP_PIECE_LENGTH: N = 10.0;
 
 #[derive(Compact, Clone)]
 pub struct Lane {
@@ -37,6 +41,29 @@ impl Lane {
 
         lane
     }
+
+    /// Attributes length and cars to the grid cells the lane passes through
+    pub fn report_to_heatmaps(&mut self, collector: HeatmapsID, world: &mut World) {
+        let path = &self.construction.path;
+        let n_pieces = (self.construction.length / HEATMAP_PIECE_LENGTH)
+            .ceil()
+            .max(1.0) as usize;
+        let piece_length = self.construction.length / n_pieces as N;
+        let mut samples = CVec::<TrafficSample>::new();
+
+        for i in 0..n_pieces {
+            let position = path.along((i as N + 0.5) * piece_length);
+            traffic_sample_in(&mut samples, cell_of(position)).lane_length += piece_length;
+        }
+
+        for car in self.microtraffic.cars.iter() {
+            let sample = traffic_sample_in(&mut samples, cell_of(path.along(*car.position)));
+            sample.n_cars += 1;
+            sample.speed_sum += car.velocity;
+        }
+
+        collector.add_traffic_samples(samples, world);
+    }
 }
 
 #[derive(Compact, Clone)]
@@ -123,6 +150,45 @@ impl SwitchLane {
     }
 }
 
+impl Inspectable for Lane {
+    fn inspect(&mut self, requester: InspectorID, world: &mut World) {
+        let summary = StateSummary::new("Lane")
+            .field("cars", self.microtraffic.cars.len())
+            .field("obstacles", self.microtraffic.obstacles.len())
+            .field("routes", self.pathfinding.routes.len())
+            .field("landmark", self.pathfinding.location)
+            .field("hops from landmark", self.pathfinding.hops_from_landmark)
+            .field("green", self.microtraffic.green)
+            .field("yellow to green", self.microtraffic.yellow_to_green)
+            .field("yellow to red", self.microtraffic.yellow_to_red)
+            .field("interactions", self.connectivity.interactions.len())
+            .field("on intersection", self.connectivity.on_intersection)
+            .field("length", self.construction.length)
+            .field("construction progress", self.construction.progress)
+            .field(
+                "closed for construction",
+                self.construction.closed_for_construction,
+            );
+
+        requester.on_inspected(self.id.as_raw(), summary, world);
+    }
+}
+
+impl Inspectable for SwitchLane {
+    fn inspect(&mut self, requester: InspectorID, world: &mut World) {
+        let summary = StateSummary::new("SwitchLane")
+            .field("cars", self.microtraffic.cars.len())
+            .field("left obstacles", self.microtraffic.left_obstacles.len())
+            .field("right obstacles", self.microtraffic.right_obstacles.len())
+            .field("left lane", self.connectivity.left.map(|(lane, ..)| lane))
+            .field("right lane", self.connectivity.right.map(|(lane, ..)| lane))
+            .field("length", self.construction.length)
+            .field("construction progress", self.construction.progress);
+
+        requester.on_inspected(self.id.as_raw(), summary, world);
+    }
+}
+
 pub fn setup(system: &mut ActorSystem) {
     system.register::<Lane>();
     system.register::<SwitchLane>();
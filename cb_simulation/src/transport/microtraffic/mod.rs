@@ -39,9 +39,24 @@ impl Microtraffic {
     }
 }
 
-// makes "time pass slower" for traffic, so we can still use realistic
-// unit values while traffic happening at a slower pace to be visible
-const MICROTRAFFIC_UNREALISTIC_SLOWDOWN: f32 = 6.0;
+impl Microtraffic {
+    pub fn timings(&self) -> &CVec<bool> {
+        &self.timings
+    }
+
+    /// Keeps cars and obstacles at the same relative position along a lane that changed length
+    pub fn morph(&mut self, new_timings: &CVec<bool>, length_ratio: f32) {
+        self.timings = new_timings.clone();
+
+        for car in self.cars.iter_mut() {
+            *car.position *= length_ratio;
+        }
+
+        for &mut (ref mut obstacle, _) in self.obstacles.iter_mut() {
+            *obstacle.position *= length_ratio;
+        }
+    }
+}
 
 #[derive(Compact, Clone, Default)]
 pub struct TransferringMicrotraffic {
@@ -147,6 +162,7 @@ pub trait LaneLike {
 use self::pathfinding::StoredRoutingEntry;
 
 use time::{Temporal, TemporalID};
+use parameters::SimulationParameters;
 
 const TRAFFIC_LOGIC_THROTTLING: usize = 10;
 const PATHFINDING_THROTTLING: usize = 10;
@@ -246,8 +262,14 @@ impl Lane {
 }
 
 impl Temporal for Lane {
-    fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World) {
-        let dt = dt / MICROTRAFFIC_UNREALISTIC_SLOWDOWN;
+    fn tick(
+        &mut self,
+        dt: f32,
+        current_instant: Instant,
+        parameters: &SimulationParameters,
+        world: &mut World,
+    ) {
+        let dt = dt / parameters.microtraffic_unrealistic_slowdown;
 
         self.construction.progress += dt * 400.0;
 
@@ -274,6 +296,10 @@ impl Temporal for Lane {
                 [(current_instant.ticks() / 30) % self.microtraffic.timings.len()]
         };
 
+        if self.construction.closed_for_construction {
+            self.microtraffic.green = false;
+        }
+
         // TODO: this is just a hacky way to update new lanes about existing lane's green
         if old_green != self.microtraffic.green || do_traffic {
             for interaction in &self.connectivity.interactions {
@@ -529,8 +555,14 @@ impl LaneLike for SwitchLane {
 }
 
 impl Temporal for SwitchLane {
-    fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World) {
-        let dt = dt / MICROTRAFFIC_UNREALISTIC_SLOWDOWN;
+    fn tick(
+        &mut self,
+        dt: f32,
+        current_instant: Instant,
+        parameters: &SimulationParameters,
+        world: &mut World,
+    ) {
+        let dt = dt / parameters.microtraffic_unrealistic_slowdown;
 
         self.construction.progress += dt * 400.0;
 
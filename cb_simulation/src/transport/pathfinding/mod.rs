@@ -24,6 +24,26 @@ pub trait Link: Actor {
 
     fn after_route_forgotten(&mut self, forgotten_route: Location, world: &mut World);
 
+    /// Closed links don't pass on any routes, so traffic is routed around them
+    fn is_closed(&self) -> bool {
+        false
+    }
+
+    fn on_closed(&mut self) {
+        let forget = self
+            .core()
+            .routes
+            .keys()
+            .cloned()
+            .chain(self.self_as_route().map(|(location, _)| location))
+            .collect::<Vec<_>>();
+        self.core_mut().tell_to_forget_next_tick.extend(forget);
+    }
+
+    fn on_reopened(&mut self) {
+        self.core_mut().routes_changed = true;
+    }
+
     fn on_connect(&mut self) {
         self.core_mut().routing_timeout = ROUTING_TIMEOUT_AFTER_CHANGE;
     }
@@ -97,6 +117,11 @@ pub trait Link: Actor {
     }
 
     fn query_routes(&mut self, requester: LinkID, connection_cost: f32, world: &mut World) {
+        if self.is_closed() {
+            requester.on_routes(CDict::new(), self.id_as(), world);
+            return;
+        }
+
         requester.on_routes(
             self.core()
                 .routes
@@ -184,6 +209,10 @@ pub trait Link: Actor {
                 forgotten_routes.push(destination_to_forget);
             }
         }
+        if !forgotten_routes.is_empty() {
+            // other successors might still know a way there
+            self.core_mut().query_routes_next_tick = true;
+        }
         self.core_mut().tell_to_forget_next_tick = forgotten_routes;
     }
 
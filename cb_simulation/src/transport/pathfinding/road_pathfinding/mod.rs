@@ -89,6 +89,10 @@ impl Link for Lane {
             .collect()
     }
 
+    fn is_closed(&self) -> bool {
+        self.construction.closed_for_construction
+    }
+
     fn after_route_forgotten(&mut self, forgotten_route: Location, world: &mut World) {
         let self_as_rough_location = self.id_as();
 
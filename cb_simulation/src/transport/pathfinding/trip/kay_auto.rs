@@ -117,6 +117,12 @@ impl Into<LocationRequesterID> for TripID {
         LocationRequesterID::from_raw(self.as_raw())
     }
 }
+
+impl Into<InspectableID> for TripID {
+    fn into(self) -> InspectableID {
+        InspectableID::from_raw(self.as_raw())
+    }
+}
 impl Actor for TripCreator {
     type ID = TripCreatorID;
 
@@ -237,6 +243,7 @@ impl Into<PositionRequesterID> for FailedTripDebuggerID {
 pub fn auto_setup(system: &mut ActorSystem) {
     TripListenerID::register_trait(system);
     LocationRequesterID::register_implementor::<Trip>(system);
+    InspectableID::register_implementor::<Trip>(system);
     system.add_spawner::<Trip, _, _>(
         |&MSG_Trip_spawn(id, rough_source, rough_destination, listener, instant), world| {
             Trip::spawn(id, rough_source, rough_destination, listener, instant, world)
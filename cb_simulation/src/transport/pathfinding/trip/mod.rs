@@ -10,6 +10,8 @@ use itertools::Itertools;
 use super::super::lane::Lane;
 
 use log::{debug, warn};
+use inspection::{Inspectable, InspectableID, InspectorID, StateSummary};
+use heatmaps::HeatmapsID;
 const LOG_T: &str = "Trips";
 
 #[derive(Compact, Clone)]
@@ -85,6 +87,7 @@ impl Trip {
                     self.id(),
                     world,
                 );
+                HeatmapsID::global_first(world).add_failed_trip(self.rough_source, world);
                 if DEBUG_FAILED_TRIPS_VISUALLY {
                     FailedTripDebuggerID::spawn(self.rough_source, self.rough_destination, world);
                 }
@@ -174,6 +177,19 @@ impl LocationRequester for Trip {
     }
 }
 
+impl Inspectable for Trip {
+    fn inspect(&mut self, requester: InspectorID, world: &mut World) {
+        let summary = StateSummary::new("Trip")
+            .field("rough source", self.rough_source)
+            .field("rough destination", self.rough_destination)
+            .field("source", self.source)
+            .field("destination", self.destination)
+            .field("listener", self.listener);
+
+        requester.on_inspected(self.id.as_raw(), summary, world);
+    }
+}
+
 use time::{TimeID, Sleeper, SleeperID};
 use time::Ticks;
 use super::super::microtraffic::{LaneLikeID, LaneCar, Obstacle};
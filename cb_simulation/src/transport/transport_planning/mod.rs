@@ -9,12 +9,30 @@ GestureIntent, Prototype, PrototypeKind, GestureID};
 mod intersection_connections;
 pub mod smooth_path;
 use dimensions::{LANE_DISTANCE, CENTER_LANE_DISTANCE, MIN_SWITCHING_LANE_LENGTH,
-SWITCHING_LANE_OVERLAP_TOLERANCE};
+SWITCHING_LANE_OVERLAP_TOLERANCE, LANE_CONNECTION_TOLERANCE};
+use environment::terrain::height_at;
+use environment::water::{water_areas, path_crosses_water};
+
+/// How far the course of a lane may shift while still being morphed in place
+const MAX_LANE_MORPH_DEVIATION: N = 1.0;
+/// How far the ends of a lane may move along its course while still being morphed in place.
+/// Intersections grow or shrink when the number of lanes of a road through them changes,
+/// which moves the ends of all lanes leading up to them.
+const MAX_LANE_MORPH_END_SHIFT: N = 4.0 * LANE_DISTANCE;
+
+/// Steepest grade (rise per run) a road may have
+pub const MAX_ROAD_GRADE: N = 0.08;
+/// How far a road may be cut into or raised above the terrain to keep its grade
+pub const MAX_ROAD_CUT_OR_FILL: N = 6.0;
+const ELEVATION_SAMPLE_DISTANCE: N = 5.0;
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 pub struct RoadIntent {
     pub n_lanes_forward: u8,
     pub n_lanes_backward: u8,
+    /// Only bridges may cross water
+    #[serde(default)]
+    pub is_bridge: bool,
 }
 
 impl RoadIntent {
@@ -22,6 +40,7 @@ impl RoadIntent {
         RoadIntent {
             n_lanes_forward,
             n_lanes_backward,
+            is_bridge: false,
         }
     }
 }
@@ -56,13 +75,41 @@ impl RoadPrototype {
 pub struct LanePrototype(pub LinePath, pub CVec<bool>);
 
 impl LanePrototype {
+    /// Lanes can be morphed as long as they keep their course, their timings may change
+    /// and their ends may move along their course, for example when a road is widened
     pub fn morphable_from(&self, other: &LanePrototype) -> bool {
-        match (self, other) {
-            (
-                &LanePrototype(ref path_1, ref timings_1),
-                &LanePrototype(ref path_2, ref timings_2),
-            ) => path_1.rough_eq_by(path_2, 0.05) && timings_1[..] == timings_2[..],
-        }
+        self.morphable_with_end_shift(other, MAX_LANE_MORPH_END_SHIFT)
+    }
+
+    /// Lanes on intersections connect fixed connectors, so only their timings
+    /// and exact course in between are allowed to change
+    pub fn morphable_from_with_fixed_ends(&self, other: &LanePrototype) -> bool {
+        self.morphable_with_end_shift(other, LANE_CONNECTION_TOLERANCE)
+    }
+
+    fn morphable_with_end_shift(&self, other: &LanePrototype, max_end_shift: N) -> bool {
+        let (&LanePrototype(ref path_1, _), &LanePrototype(ref path_2, _)) = (self, other);
+
+        // on lanes shorter than this, moved ends could match a different lane
+        let long_enough = |path: &LinePath| {
+            max_end_shift <= LANE_CONNECTION_TOLERANCE || path.length() > 2.0 * max_end_shift
+        };
+
+        // where one lane is longer than the other, it may extend beyond the other's ends
+        let follows = |path_a: &LinePath, path_b: &LinePath| {
+            path_a.points.iter().all(|point| {
+                path_b.distance_to(*point) < MAX_LANE_MORPH_DEVIATION
+                    || point.rough_eq_by(path_b.start(), max_end_shift)
+                    || point.rough_eq_by(path_b.end(), max_end_shift)
+            })
+        };
+
+        long_enough(path_1)
+            && long_enough(path_2)
+            && path_1.start().rough_eq_by(path_2.start(), max_end_shift)
+            && path_1.end().rough_eq_by(path_2.end(), max_end_shift)
+            && follows(path_1, path_2)
+            && follows(path_2, path_1)
     }
 }
 
@@ -132,8 +179,30 @@ pub struct IntersectionPrototype {
 
 impl IntersectionPrototype {
     pub fn morphable_from(&self, other: &IntersectionPrototype) -> bool {
-        // TODO: make this better!!
+        // every connecting lane needs exactly one counterpart to morph from
+        let n_connecting_lanes = |intersection: &IntersectionPrototype| {
+            intersection
+                .connecting_lanes
+                .values()
+                .map(|group| group.len())
+                .sum::<usize>()
+        };
+
         (&self.area).rough_eq_by(&other.area, 0.1)
+            && n_connecting_lanes(self) == n_connecting_lanes(other)
+            && self
+                .connecting_lanes
+                .values()
+                .flat_map(|group| group.iter())
+                .all(|lane| {
+                    other
+                        .connecting_lanes
+                        .values()
+                        .flat_map(|group| group.iter())
+                        .filter(|other_lane| lane.morphable_from_with_fixed_ends(other_lane))
+                        .count()
+                        == 1
+                })
     }
 }
 
@@ -175,12 +244,105 @@ pub fn gesture_intent_smooth_paths(
         .collect::<Vec<_>>()
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElevationSample {
+    pub along: N,
+    pub terrain_height: N,
+    pub road_height: N,
+}
+
+/// Samples the height of a road along its path, following the terrain
+/// as closely as possible without exceeding `MAX_ROAD_GRADE`
+pub fn road_elevation_profile(path: &LinePath) -> Vec<ElevationSample> {
+    let n_steps = (path.length() / ELEVATION_SAMPLE_DISTANCE).ceil().max(1.0) as usize;
+    let step_length = path.length() / n_steps as N;
+    let max_rise = MAX_ROAD_GRADE * step_length;
+
+    let terrain_heights = (0..=n_steps)
+        .map(|i| height_at(path.along(i as N * step_length)))
+        .collect::<Vec<_>>();
+
+    // limiting the grade only forwards or only backwards biases the road
+    // towards one end, so we average both
+    let mut forward_heights = terrain_heights.clone();
+    for i in 1..forward_heights.len() {
+        let previous = forward_heights[i - 1];
+        forward_heights[i] = forward_heights[i]
+            .max(previous - max_rise)
+            .min(previous + max_rise);
+    }
+
+    let mut backward_heights = terrain_heights.clone();
+    for i in (0..backward_heights.len() - 1).rev() {
+        let next = backward_heights[i + 1];
+        backward_heights[i] = backward_heights[i]
+            .max(next - max_rise)
+            .min(next + max_rise);
+    }
+
+    terrain_heights
+        .into_iter()
+        .zip(forward_heights.into_iter().zip(backward_heights))
+        .enumerate()
+        .map(
+            |(i, (terrain_height, (forward_height, backward_height)))| ElevationSample {
+                along: i as N * step_length,
+                terrain_height,
+                road_height: (forward_height + backward_height) / 2.0,
+            },
+        )
+        .collect()
+}
+
+/// Why a road gesture doesn't result in any road
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum RoadRejection {
+    /// Keeping the grade limit would need more than `MAX_ROAD_CUT_OR_FILL`
+    TooSteep,
+    /// Only bridges may cross water
+    CrossesWater,
+}
+
+/// Whether (and why) a road along this path with this elevation profile can't be built
+pub fn road_rejection(
+    road_intent: &RoadIntent,
+    path: &LinePath,
+    elevation_profile: &[ElevationSample],
+    water_areas: &[&Area],
+) -> Option<RoadRejection> {
+    if !elevation_profile
+        .iter()
+        .all(|sample| (sample.road_height - sample.terrain_height).abs() <= MAX_ROAD_CUT_OR_FILL)
+    {
+        Some(RoadRejection::TooSteep)
+    } else if !road_intent.is_bridge && path_crosses_water(path, water_areas) {
+        Some(RoadRejection::CrossesWater)
+    } else {
+        None
+    }
+}
+
 #[allow(clippy::cyclomatic_complexity)]
 pub fn calculate_prototypes(
     history: &PlanHistory,
-    _current_result: &PlanResult,
+    current_result: &PlanResult,
 ) -> Result<Vec<Prototype>, AreaError> {
-    let gesture_intent_smooth_paths = gesture_intent_smooth_paths(history);
+    let water_areas = water_areas(current_result);
+
+    // roads on too steep terrain or through water (unless they're bridges) are not built
+    let gesture_intent_smooth_paths = gesture_intent_smooth_paths(history)
+        .into_iter()
+        .filter(|(_, _, road_intent, path)| {
+            road_rejection(
+                road_intent,
+                path,
+                &road_elevation_profile(path),
+                &water_areas,
+            )
+            .is_none()
+        })
+        .collect::<Vec<_>>();
 
     let gesture_areas_for_intersection = gesture_intent_smooth_paths
         .iter()